@@ -0,0 +1,51 @@
+/* The C API of up_finder, see the documentation of the up_finder_ffi crate. */
+
+#ifndef UP_FINDER_H
+#define UP_FINDER_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Search for files. */
+#define UP_FINDER_KIND_FILE 0
+/* Search for directories. */
+#define UP_FINDER_KIND_DIR 1
+/* Search for entries of any kind. */
+#define UP_FINDER_KIND_ANY 2
+
+/*
+ * A list of paths returned by up_finder_find_up_all, nearest first. Empty,
+ * with paths null, when nothing was found or the arguments are invalid.
+ */
+typedef struct UpFinderPaths {
+  char **paths;
+  size_t len;
+} UpFinderPaths;
+
+/*
+ * The nearest match of name from cwd upward, null if there is none or the
+ * arguments are invalid. Free it with up_finder_free_string.
+ */
+char *up_finder_find_up_first(const char *cwd, const char *name, uint32_t kind);
+
+/*
+ * Every match of name from cwd upward, nearest first. Free it with
+ * up_finder_free_paths.
+ */
+UpFinderPaths up_finder_find_up_all(const char *cwd, const char *name, uint32_t kind);
+
+/* Free a string returned by up_finder_find_up_first. Does nothing for null. */
+void up_finder_free_string(char *path);
+
+/* Free a list returned by up_finder_find_up_all and all of its paths. */
+void up_finder_free_paths(UpFinderPaths paths);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* UP_FINDER_H */
@@ -0,0 +1,240 @@
+//! A C API for [`up_finder`] over the real filesystem, for tools that aren't
+//! written in Rust.
+//!
+//! The crate is built as a `cdylib` and a `staticlib`, declared in
+//! `include/up_finder.h`. Every function takes the start directory and the
+//! name as NUL-terminated strings and the kind as one of the
+//! `UP_FINDER_KIND_` constants, and is exported with an `up_finder_` prefix,
+//! e.g. `up_finder_find_up_first`. Paths are bytes on Unix and UTF-8
+//! elsewhere. Strings and path lists returned by the library must be freed
+//! with [`free_string`] and [`free_paths`].
+//!
+//! ```c
+//! char *path = up_finder_find_up_first(".", "package.json", UP_FINDER_KIND_FILE);
+//! if (path) {
+//!   puts(path);
+//!   up_finder_free_string(path);
+//! }
+//!
+//! UpFinderPaths paths = up_finder_find_up_all(".", ".editorconfig", UP_FINDER_KIND_FILE);
+//! for (size_t i = 0; i < paths.len; i++) {
+//!   puts(paths.paths[i]);
+//! }
+//! up_finder_free_paths(paths);
+//! ```
+
+use std::{
+  ffi::{CStr, CString, c_char},
+  path::{Path, PathBuf},
+  ptr,
+};
+
+use up_finder::{FindUpKind, FindUpResult, UpFinder};
+
+/// Search for files, see [`FindUpKind::File`].
+pub const UP_FINDER_KIND_FILE: u32 = 0;
+/// Search for directories, see [`FindUpKind::Dir`].
+pub const UP_FINDER_KIND_DIR: u32 = 1;
+/// Search for entries of any kind, see [`FindUpKind::Any`].
+pub const UP_FINDER_KIND_ANY: u32 = 2;
+
+/// A list of paths returned by [`find_up_all`], nearest first. Empty, with
+/// `paths` null, when nothing was found or the arguments are invalid.
+#[repr(C)]
+#[derive(Debug)]
+pub struct UpFinderPaths {
+  pub paths: *mut *mut c_char,
+  pub len: usize,
+}
+
+impl UpFinderPaths {
+  fn empty() -> Self {
+    Self {
+      paths: ptr::null_mut(),
+      len: 0,
+    }
+  }
+}
+
+/// The nearest match of `name` from `cwd` upward, null if there is none or the
+/// arguments are invalid.
+///
+/// # Safety
+///
+/// `cwd` and `name` must be null or point to NUL-terminated strings.
+#[unsafe(export_name = "up_finder_find_up_first")]
+pub unsafe extern "C" fn find_up_first(
+  cwd: *const c_char,
+  name: *const c_char,
+  kind: u32,
+) -> *mut c_char {
+  let Some((up_finder, name)) = (unsafe { search(cwd, name, kind) }) else {
+    return ptr::null_mut();
+  };
+
+  up_finder
+    .find_up_with(&name, FindUpResult::Last)
+    .first()
+    .map_or(ptr::null_mut(), |path| path_to_c(path))
+}
+
+/// Every match of `name` from `cwd` upward, nearest first.
+///
+/// # Safety
+///
+/// `cwd` and `name` must be null or point to NUL-terminated strings.
+#[unsafe(export_name = "up_finder_find_up_all")]
+pub unsafe extern "C" fn find_up_all(
+  cwd: *const c_char,
+  name: *const c_char,
+  kind: u32,
+) -> UpFinderPaths {
+  let Some((up_finder, name)) = (unsafe { search(cwd, name, kind) }) else {
+    return UpFinderPaths::empty();
+  };
+
+  let paths: Box<[*mut c_char]> = up_finder
+    .find_up(&name)
+    .iter()
+    .map(|path| path_to_c(path))
+    .filter(|path| !path.is_null())
+    .collect();
+  if paths.is_empty() {
+    return UpFinderPaths::empty();
+  }
+
+  UpFinderPaths {
+    len: paths.len(),
+    paths: Box::into_raw(paths).cast(),
+  }
+}
+
+/// Free a string returned by [`find_up_first`]. Does nothing for null.
+///
+/// # Safety
+///
+/// `path` must be null or a string returned by this library that wasn't freed
+/// yet.
+#[unsafe(export_name = "up_finder_free_string")]
+pub unsafe extern "C" fn free_string(path: *mut c_char) {
+  if !path.is_null() {
+    drop(unsafe { CString::from_raw(path) });
+  }
+}
+
+/// Free a list returned by [`find_up_all`] and all of its paths.
+///
+/// # Safety
+///
+/// `paths` must be a list returned by this library that wasn't freed yet.
+#[unsafe(export_name = "up_finder_free_paths")]
+pub unsafe extern "C" fn free_paths(paths: UpFinderPaths) {
+  if paths.paths.is_null() {
+    return;
+  }
+
+  let list = unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(paths.paths, paths.len)) };
+  for &path in &list {
+    unsafe { free_string(path) };
+  }
+}
+
+/// The search for `name` from `cwd`, `None` for invalid arguments.
+///
+/// # Safety
+///
+/// `cwd` and `name` must be null or point to NUL-terminated strings.
+unsafe fn search(
+  cwd: *const c_char,
+  name: *const c_char,
+  kind: u32,
+) -> Option<(UpFinder<PathBuf>, String)> {
+  if cwd.is_null() || name.is_null() {
+    return None;
+  }
+
+  let kind = match kind {
+    UP_FINDER_KIND_FILE => FindUpKind::File,
+    UP_FINDER_KIND_DIR => FindUpKind::Dir,
+    UP_FINDER_KIND_ANY => FindUpKind::Any,
+    _ => return None,
+  };
+  let (cwd, name) = unsafe { (CStr::from_ptr(cwd), CStr::from_ptr(name)) };
+  let name = name.to_str().ok()?.to_owned();
+
+  let up_finder = UpFinder::builder()
+    .cwd(path_from_c(cwd)?)
+    .kind(kind)
+    .build();
+
+  Some((up_finder, name))
+}
+
+#[cfg(unix)]
+fn path_from_c(path: &CStr) -> Option<PathBuf> {
+  use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+  Some(OsStr::from_bytes(path.to_bytes()).into())
+}
+
+#[cfg(not(unix))]
+fn path_from_c(path: &CStr) -> Option<PathBuf> {
+  path.to_str().ok().map(PathBuf::from)
+}
+
+/// `path` as a string for C, null if it can't be one.
+#[cfg(unix)]
+fn path_to_c(path: &Path) -> *mut c_char {
+  use std::os::unix::ffi::OsStrExt;
+
+  CString::new(path.as_os_str().as_bytes()).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// `path` as a string for C, null if it can't be one.
+#[cfg(not(unix))]
+fn path_to_c(path: &Path) -> *mut c_char {
+  path
+    .to_str()
+    .and_then(|path| CString::new(path).ok())
+    .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_find_and_free_paths_through_the_c_api() {
+    let cwd = c"../../fixtures/a/b/c/d";
+    let name = c"package.json";
+
+    unsafe {
+      let first = find_up_first(cwd.as_ptr(), name.as_ptr(), UP_FINDER_KIND_FILE);
+      assert_eq!(
+        CStr::from_ptr(first).to_str(),
+        Ok("../../fixtures/a/b/c/d/package.json")
+      );
+      free_string(first);
+
+      let all = find_up_all(cwd.as_ptr(), name.as_ptr(), UP_FINDER_KIND_FILE);
+      let paths: Vec<&str> = std::slice::from_raw_parts(all.paths, all.len)
+        .iter()
+        .map(|&path| CStr::from_ptr(path).to_str().unwrap())
+        .collect();
+      assert_eq!(
+        paths[..4],
+        [
+          "../../fixtures/a/b/c/d/package.json",
+          "../../fixtures/a/b/c/package.json",
+          "../../fixtures/a/b/package.json",
+          "../../fixtures/a/package.json",
+        ]
+      );
+      free_paths(all);
+
+      assert!(find_up_first(cwd.as_ptr(), name.as_ptr(), UP_FINDER_KIND_DIR).is_null());
+      assert!(find_up_first(ptr::null(), name.as_ptr(), UP_FINDER_KIND_FILE).is_null());
+      assert!(find_up_all(cwd.as_ptr(), name.as_ptr(), 7).paths.is_null());
+    }
+  }
+}
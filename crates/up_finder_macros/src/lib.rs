@@ -0,0 +1,70 @@
+//! The compile-time macros of `up_finder`, re-exported by it with the
+//! `macros` feature.
+
+use std::{env, path::PathBuf};
+
+use proc_macro::{Literal, TokenStream, TokenTree};
+
+/// The path of the nearest file named `name` in the directory of the crate
+/// being compiled (`CARGO_MANIFEST_DIR`) or any of its parents, as a string
+/// literal, so it can be passed to `include_str!` or `include_bytes!` instead
+/// of a brittle `../..` path. Not finding one is a compile error.
+///
+/// The search runs when the crate is compiled, a file appearing nearer later
+/// is only picked up by the next build that compiles the crate again.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder_macros::find_up_path;
+///
+/// const LICENSE: &str = include_str!(find_up_path!("LICENSE"));
+///
+/// assert!(LICENSE.starts_with("MIT License"));
+/// ```
+#[proc_macro]
+pub fn find_up_path(input: TokenStream) -> TokenStream {
+  match find_up(input) {
+    Ok(path) => TokenTree::Literal(Literal::string(&path)).into(),
+    Err(message) => format!("compile_error!({message:?})")
+      .parse()
+      .expect("a `compile_error!` invocation"),
+  }
+}
+
+fn find_up(input: TokenStream) -> Result<String, String> {
+  let name = parse_name(input)?;
+  let manifest_dir = env::var_os("CARGO_MANIFEST_DIR")
+    .map(PathBuf::from)
+    .ok_or("`CARGO_MANIFEST_DIR` is not set")?;
+
+  let path = manifest_dir
+    .ancestors()
+    .map(|dir| dir.join(&name))
+    .find(|path| path.is_file())
+    .ok_or_else(|| format!("no `{name}` in {} or above", manifest_dir.display()))?;
+
+  path
+    .into_os_string()
+    .into_string()
+    .map_err(|path| format!("{} is not valid UTF-8", PathBuf::from(path).display()))
+}
+
+/// The name in the single string literal of `input`.
+fn parse_name(input: TokenStream) -> Result<String, String> {
+  let mut tokens = input.into_iter();
+  let (Some(TokenTree::Literal(literal)), None) = (tokens.next(), tokens.next()) else {
+    return Err("expected a file name, e.g. `find_up_path!(\"LICENSE\")`".to_owned());
+  };
+
+  let literal = literal.to_string();
+  let name = literal
+    .strip_prefix('"')
+    .and_then(|literal| literal.strip_suffix('"'))
+    .ok_or("expected a string literal")?;
+  if name.is_empty() || name.contains('\\') {
+    return Err(format!("unsupported file name {literal}"));
+  }
+
+  Ok(name.to_owned())
+}
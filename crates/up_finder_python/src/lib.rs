@@ -0,0 +1,175 @@
+//! The `up_finder` Python module over [`up_finder`], built into a `cdylib`,
+//! e.g. by `maturin build`.
+//!
+//! ```python
+//! from up_finder import UpFinder
+//!
+//! finder = UpFinder(".", kind="dir", max_depth=8, exclude_dependency_dirs=True)
+//! print(finder.find_up(".git"))
+//! ```
+
+use std::{collections::HashMap, path::PathBuf};
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+use up_finder::{FindUpKind, UpFinder};
+
+/// `UpFinder` for Python, taking the options of the builder as keyword
+/// arguments.
+#[pyclass(name = "UpFinder", module = "up_finder", frozen)]
+#[derive(Debug)]
+struct PyUpFinder {
+  cwd: PathBuf,
+  up_finder: UpFinder<PathBuf>,
+}
+
+#[pymethods]
+impl PyUpFinder {
+  #[new]
+  #[pyo3(signature = (
+    cwd,
+    *,
+    kind = "file",
+    stop_at = None,
+    restrict_to = None,
+    max_depth = None,
+    config_dir = false,
+    probe_dirs = vec![],
+    exclude_dependency_dirs = false,
+    preserve_case = false,
+    normalize_unicode = false,
+    dedupe_hard_links = false,
+    env_override = None,
+  ))]
+  #[allow(clippy::too_many_arguments)]
+  fn new(
+    cwd: PathBuf,
+    kind: &str,
+    stop_at: Option<PathBuf>,
+    restrict_to: Option<PathBuf>,
+    max_depth: Option<usize>,
+    config_dir: bool,
+    probe_dirs: Vec<PathBuf>,
+    exclude_dependency_dirs: bool,
+    preserve_case: bool,
+    normalize_unicode: bool,
+    dedupe_hard_links: bool,
+    env_override: Option<String>,
+  ) -> PyResult<Self> {
+    let kind = kind
+      .parse::<FindUpKind>()
+      .map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+    let up_finder = UpFinder::builder()
+      .cwd(cwd.clone())
+      .kind(kind)
+      .stop_at_opt(stop_at)
+      .restrict_to_opt(restrict_to)
+      .max_depth_opt(max_depth)
+      .config_dir(config_dir)
+      .probe_dirs(probe_dirs)
+      .exclude_dependency_dirs(exclude_dependency_dirs)
+      .preserve_case(preserve_case)
+      .normalize_unicode(normalize_unicode)
+      .dedupe_hard_links(dedupe_hard_links)
+      .env_override_opt(env_override)
+      .build();
+
+    Ok(Self { cwd, up_finder })
+  }
+
+  /// Every match of `name`, nearest first.
+  fn find_up(&self, py: Python<'_>, name: &str) -> Vec<PathBuf> {
+    py.allow_threads(|| self.up_finder.find_up(name))
+  }
+
+  /// The matches of every name in `names`, by name.
+  fn find_up_multi(&self, py: Python<'_>, names: Vec<String>) -> HashMap<String, Vec<PathBuf>> {
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+    py.allow_threads(|| {
+      self
+        .up_finder
+        .find_up_multi(&names)
+        .into_iter()
+        .map(|(name, paths)| (name.to_owned(), paths))
+        .collect()
+    })
+  }
+
+  /// Like `find_up`, raising `ValueError` if the search could not be run,
+  /// e.g. for a start directory outside of `restrict_to`.
+  fn try_find_up(&self, py: Python<'_>, name: &str) -> PyResult<Vec<PathBuf>> {
+    py.allow_threads(|| self.up_finder.try_find_up(name))
+      .map_err(|error| PyValueError::new_err(error.to_string()))
+  }
+
+  /// Whether `find_up` would find `name`.
+  fn exists_up(&self, py: Python<'_>, name: &str) -> bool {
+    py.allow_threads(|| self.up_finder.exists_up(name))
+  }
+
+  /// How many matches of `name` there are, without the fallback directories.
+  fn count_up(&self, py: Python<'_>, name: &str) -> usize {
+    py.allow_threads(|| self.up_finder.count_up(name))
+  }
+
+  /// Every match of `name` in the start directory and at most `max_depth`
+  /// levels of subdirectories, nearest first.
+  fn find_down(&self, py: Python<'_>, name: &str, max_depth: usize) -> Vec<PathBuf> {
+    py.allow_threads(|| self.up_finder.find_down(name, max_depth))
+  }
+
+  /// The paths a search for `name` would look up, in order.
+  fn plan(&self, name: &str) -> Vec<PathBuf> {
+    self.up_finder.plan(name)
+  }
+
+  fn __repr__(&self) -> String {
+    format!("UpFinder({:?})", self.cwd)
+  }
+}
+
+#[pymodule]
+#[pyo3(name = "up_finder")]
+fn py_up_finder(module: &Bound<'_, PyModule>) -> PyResult<()> {
+  module.add_class::<PyUpFinder>()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn finder(kind: &str) -> PyResult<PyUpFinder> {
+    PyUpFinder::new(
+      "../../fixtures/a/b/c/d".into(),
+      kind,
+      Some("../../fixtures/a".into()),
+      None,
+      None,
+      false,
+      vec![],
+      false,
+      false,
+      false,
+      false,
+      None,
+    )
+  }
+
+  #[test]
+  fn should_parse_the_kind_and_search() {
+    pyo3::prepare_freethreaded_python();
+
+    Python::with_gil(|py| {
+      assert_eq!(
+        finder("symlink").unwrap_err().to_string(),
+        "ValueError: unknown kind `symlink`, expected one of file, dir, any"
+      );
+      assert_eq!(
+        finder("dir").unwrap().find_up(py, "b"),
+        [PathBuf::from("../../fixtures/a/b")]
+      );
+      assert_eq!(finder("file").unwrap().count_up(py, "package.json"), 4);
+    });
+  }
+}
@@ -0,0 +1,242 @@
+//! The JavaScript API of [`up_finder`] on `wasm32-unknown-unknown`, built
+//! into an npm package by `wasm-pack build --target web`. The crate is empty
+//! on other targets.
+//!
+//! There is no filesystem in the browser, so every search runs over a
+//! `FileTree` the caller supplies, e.g. the virtual tree of a browser-based
+//! IDE.
+//!
+//! ```js
+//! import init, { UpFinder } from 'up_finder_wasm'
+//!
+//! await init()
+//!
+//! const files = new Map([['/repo/package.json', '{}'], ['/repo/src/index.ts', '']])
+//! const tree = {
+//!   stat: (path) => files.has(path) ? { type: 'file', size: files.get(path).length }
+//!     : [...files.keys()].some((file) => file.startsWith(path + '/')) ? { type: 'dir' }
+//!     : undefined,
+//!   readDir: (path) => [...new Set([...files.keys()]
+//!     .filter((file) => file.startsWith(path + '/'))
+//!     .map((file) => file.slice(path.length + 1).split('/')[0]))],
+//!   readFile: (path) => new TextEncoder().encode(files.get(path)),
+//! }
+//!
+//! const finder = new UpFinder('/repo/src', tree, { maxDepth: 8 })
+//! console.log(finder.findUp('package.json'))
+//! ```
+
+#![cfg(all(
+  target_family = "wasm",
+  target_os = "unknown",
+  not(target_feature = "atomics")
+))]
+
+use std::{
+  ffi::OsString,
+  fmt, io,
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+use wasm_bindgen::prelude::*;
+
+use up_finder::{FileType, FindUpKind, Fs, Metadata, UpFinder};
+
+#[wasm_bindgen(typescript_custom_section)]
+const FILE_TREE: &str = r#"
+/** The virtual file tree a search runs over. Paths are absolute, with `/` between names. */
+export interface FileTree {
+  /** The entry at `path`, following symlinks, `undefined` if there is none. */
+  stat(path: string): { type: "file" | "dir" | "other"; size?: number } | undefined;
+  /** The names of the entries in the directory at `path`. */
+  readDir(path: string): string[];
+  /** The contents of the file at `path`. */
+  readFile(path: string): Uint8Array;
+}
+
+/** The options of an `UpFinder`, all optional. */
+export interface UpFinderOptions {
+  kind?: "file" | "dir" | "any";
+  stopAt?: string;
+  restrictTo?: string;
+  maxDepth?: number;
+  configDir?: boolean;
+  excludeDependencyDirs?: boolean;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+  #[wasm_bindgen(typescript_type = "FileTree")]
+  pub type FileTree;
+
+  #[wasm_bindgen(method, catch)]
+  fn stat(this: &FileTree, path: &str) -> Result<Option<Stat>, JsValue>;
+
+  #[wasm_bindgen(method, catch, js_name = readDir)]
+  fn read_dir(this: &FileTree, path: &str) -> Result<Vec<String>, JsValue>;
+
+  #[wasm_bindgen(method, catch, js_name = readFile)]
+  fn read_file(this: &FileTree, path: &str) -> Result<Vec<u8>, JsValue>;
+
+  type Stat;
+
+  #[wasm_bindgen(method, getter, js_name = type)]
+  fn kind(this: &Stat) -> String;
+
+  #[wasm_bindgen(method, getter)]
+  fn size(this: &Stat) -> Option<f64>;
+
+  #[wasm_bindgen(typescript_type = "UpFinderOptions")]
+  pub type UpFinderOptions;
+
+  #[wasm_bindgen(method, getter)]
+  fn kind(this: &UpFinderOptions) -> Option<String>;
+
+  #[wasm_bindgen(method, getter, js_name = stopAt)]
+  fn stop_at(this: &UpFinderOptions) -> Option<String>;
+
+  #[wasm_bindgen(method, getter, js_name = restrictTo)]
+  fn restrict_to(this: &UpFinderOptions) -> Option<String>;
+
+  #[wasm_bindgen(method, getter, js_name = maxDepth)]
+  fn max_depth(this: &UpFinderOptions) -> Option<u32>;
+
+  #[wasm_bindgen(method, getter, js_name = configDir)]
+  fn config_dir(this: &UpFinderOptions) -> Option<bool>;
+
+  #[wasm_bindgen(method, getter, js_name = excludeDependencyDirs)]
+  fn exclude_dependency_dirs(this: &UpFinderOptions) -> Option<bool>;
+}
+
+/// A [`FileTree`] as an [`Fs`].
+struct JsFs {
+  tree: FileTree,
+}
+
+// Without the `atomics` target feature there is only the one thread the
+// `FileTree` lives on.
+unsafe impl Send for JsFs {}
+unsafe impl Sync for JsFs {}
+
+impl fmt::Debug for JsFs {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("JsFs").finish_non_exhaustive()
+  }
+}
+
+impl Fs for JsFs {
+  fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+    let stat = self
+      .tree
+      .stat(&path_to_js(path)?)
+      .map_err(js_error)?
+      .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+
+    let file_type = match stat.kind().as_str() {
+      "file" => FileType::File,
+      "dir" => FileType::Dir,
+      _ => FileType::Other,
+    };
+
+    Ok(Metadata::new(file_type, stat.size().unwrap_or(0.0) as u64))
+  }
+
+  fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+    let names = self.tree.read_dir(&path_to_js(path)?).map_err(js_error)?;
+
+    Ok(names.into_iter().map(OsString::from).collect())
+  }
+
+  fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+    self.tree.read_file(&path_to_js(path)?).map_err(js_error)
+  }
+}
+
+/// `UpFinder` for JavaScript, searching a [`FileTree`].
+#[wasm_bindgen(js_name = UpFinder)]
+#[derive(Debug)]
+pub struct JsUpFinder {
+  up_finder: UpFinder<PathBuf>,
+}
+
+#[wasm_bindgen(js_class = UpFinder)]
+impl JsUpFinder {
+  #[wasm_bindgen(constructor)]
+  pub fn new(
+    cwd: String,
+    tree: FileTree,
+    options: Option<UpFinderOptions>,
+  ) -> Result<JsUpFinder, JsError> {
+    let mut builder = UpFinder::builder()
+      .cwd(PathBuf::from(cwd))
+      .fs(Arc::new(JsFs { tree }));
+
+    if let Some(options) = options {
+      if let Some(kind) = options.kind() {
+        let kind = kind
+          .parse::<FindUpKind>()
+          .map_err(|error| JsError::new(&error.to_string()))?;
+        builder = builder.kind(kind);
+      }
+      builder = builder
+        .stop_at_opt(options.stop_at().map(PathBuf::from))
+        .restrict_to_opt(options.restrict_to().map(PathBuf::from))
+        .max_depth_opt(options.max_depth().map(|max_depth| max_depth as usize))
+        .config_dir(options.config_dir().unwrap_or(false))
+        .exclude_dependency_dirs(options.exclude_dependency_dirs().unwrap_or(false));
+    }
+    let up_finder = builder.build();
+
+    Ok(Self { up_finder })
+  }
+
+  /// Every match of `name`, nearest first.
+  #[wasm_bindgen(js_name = findUp)]
+  pub fn find_up(&self, name: &str) -> Vec<String> {
+    paths_to_js(self.up_finder.find_up(name))
+  }
+
+  /// Whether `findUp` would find `name`.
+  #[wasm_bindgen(js_name = existsUp)]
+  pub fn exists_up(&self, name: &str) -> bool {
+    self.up_finder.exists_up(name)
+  }
+
+  /// How many matches of `name` there are, without the fallback directories.
+  #[wasm_bindgen(js_name = countUp)]
+  pub fn count_up(&self, name: &str) -> usize {
+    self.up_finder.count_up(name)
+  }
+
+  /// Every match of `name` in the start directory and at most `maxDepth`
+  /// levels of subdirectories, nearest first.
+  #[wasm_bindgen(js_name = findDown)]
+  pub fn find_down(&self, name: &str, max_depth: usize) -> Vec<String> {
+    paths_to_js(self.up_finder.find_down(name, max_depth))
+  }
+
+  /// The paths a search for `name` would look up, in order.
+  pub fn plan(&self, name: &str) -> Vec<String> {
+    paths_to_js(self.up_finder.plan(name))
+  }
+}
+
+fn js_error(error: JsValue) -> io::Error {
+  io::Error::other(format!("{error:?}"))
+}
+
+fn path_to_js(path: &Path) -> io::Result<String> {
+  path
+    .to_str()
+    .map(str::to_owned)
+    .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))
+}
+
+fn paths_to_js(paths: Vec<PathBuf>) -> Vec<String> {
+  paths
+    .into_iter()
+    .filter_map(|path| path.into_os_string().into_string().ok())
+    .collect()
+}
@@ -0,0 +1,4 @@
+fn main() {
+  // Node resolves the N-API symbols of the addon when it loads it.
+  napi_build::setup();
+}
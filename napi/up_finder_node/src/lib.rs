@@ -0,0 +1,278 @@
+//! The Node.js addon over [`up_finder`], built into a `cdylib`, e.g. by
+//! `napi build`, and published as the `up_finder-node` npm package. `findUp`
+//! and `findUpSync` take the arguments of the `find-up` npm package, so the
+//! addon can stand in for it in native tooling.
+//!
+//! ```js
+//! const { findUp, findUpMultiSync } = require('up_finder-node')
+//!
+//! const root = await findUp('package.json', { type: 'file', stopAt: '/repo' })
+//! const configs = findUpMultiSync(['.editorconfig', '.npmrc'], { cwd: 'src' })
+//! ```
+
+use std::{
+  collections::HashMap,
+  io,
+  path::{self, Component, PathBuf},
+};
+
+use napi::{
+  Either, Env, Error, Result, Status, Task,
+  bindgen_prelude::{AsyncTask, Undefined},
+};
+use napi_derive::napi;
+
+use up_finder::{FindUpKind, FindUpResult, UpFinder};
+
+/// The options of every search, all optional.
+#[napi(object)]
+#[derive(Debug, Default)]
+pub struct FindUpOptions {
+  /// The start directory, the current working directory by default.
+  pub cwd: Option<String>,
+  /// `file` (the default), `directory` or `any`.
+  #[napi(js_name = "type")]
+  pub kind: Option<String>,
+  /// The last directory to search.
+  pub stop_at: Option<String>,
+  /// The number of levels above `cwd` to search at most.
+  pub max_depth: Option<u32>,
+}
+
+impl FindUpOptions {
+  fn up_finder(self) -> Result<UpFinder<PathBuf>> {
+    let kind = match self.kind.as_deref() {
+      None => FindUpKind::File,
+      Some("directory") => FindUpKind::Dir,
+      Some(kind) => kind.parse().map_err(|_| {
+        Error::new(
+          Status::InvalidArg,
+          format!("unknown type `{kind}`, expected one of file, directory, any"),
+        )
+      })?,
+    };
+    let cwd = resolve(self.cwd.as_deref().unwrap_or("."))?;
+    let stop_at = self.stop_at.as_deref().map(resolve).transpose()?;
+
+    Ok(
+      UpFinder::builder()
+        .cwd(cwd)
+        .kind(kind)
+        .stop_at_opt(stop_at)
+        .max_depth_opt(self.max_depth.map(|max_depth| max_depth as usize))
+        .build(),
+    )
+  }
+}
+
+/// `path` from the current directory, without `.` and `..`, like
+/// `path.resolve` does for `find-up`.
+fn resolve(path: &str) -> io::Result<PathBuf> {
+  let mut resolved = PathBuf::new();
+  for component in path::absolute(path)?.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        resolved.pop();
+      }
+      component => resolved.push(component),
+    }
+  }
+
+  Ok(resolved)
+}
+
+/// The nearest match of `name`, `undefined` if there is none.
+#[napi]
+pub fn find_up_sync(
+  name: String,
+  options: Option<FindUpOptions>,
+) -> Result<Either<String, Undefined>> {
+  let up_finder = options.unwrap_or_default().up_finder()?;
+
+  Ok(or_undefined(nearest(&up_finder, &name)))
+}
+
+/// Like `findUpSync`, searching on the libuv thread pool.
+#[napi(ts_return_type = "Promise<string | undefined>")]
+pub fn find_up(name: String, options: Option<FindUpOptions>) -> Result<AsyncTask<FindUp>> {
+  let up_finder = options.unwrap_or_default().up_finder()?;
+
+  Ok(AsyncTask::new(FindUp { up_finder, name }))
+}
+
+/// Every match of every name in `names`, nearest first, by name.
+#[napi]
+pub fn find_up_multi_sync(
+  names: Vec<String>,
+  options: Option<FindUpOptions>,
+) -> Result<HashMap<String, Vec<String>>> {
+  let up_finder = options.unwrap_or_default().up_finder()?;
+
+  Ok(all(&up_finder, &names))
+}
+
+/// Like `findUpMultiSync`, searching on the libuv thread pool.
+#[napi(ts_return_type = "Promise<Record<string, string[]>>")]
+pub fn find_up_multi(
+  names: Vec<String>,
+  options: Option<FindUpOptions>,
+) -> Result<AsyncTask<FindUpMulti>> {
+  let up_finder = options.unwrap_or_default().up_finder()?;
+
+  Ok(AsyncTask::new(FindUpMulti { up_finder, names }))
+}
+
+#[derive(Debug)]
+pub struct FindUp {
+  up_finder: UpFinder<PathBuf>,
+  name: String,
+}
+
+impl Task for FindUp {
+  type Output = Option<String>;
+  type JsValue = Either<String, Undefined>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    Ok(nearest(&self.up_finder, &self.name))
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(or_undefined(output))
+  }
+}
+
+#[derive(Debug)]
+pub struct FindUpMulti {
+  up_finder: UpFinder<PathBuf>,
+  names: Vec<String>,
+}
+
+impl Task for FindUpMulti {
+  type Output = HashMap<String, Vec<String>>;
+  type JsValue = HashMap<String, Vec<String>>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    Ok(all(&self.up_finder, &self.names))
+  }
+
+  fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+fn nearest(up_finder: &UpFinder<PathBuf>, name: &str) -> Option<String> {
+  up_finder
+    .find_up_with(name, FindUpResult::Last)
+    .into_iter()
+    .find_map(path_to_js)
+}
+
+fn all(up_finder: &UpFinder<PathBuf>, names: &[String]) -> HashMap<String, Vec<String>> {
+  let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+  up_finder
+    .find_up_multi(&names)
+    .into_iter()
+    .map(|(name, paths)| {
+      let paths = paths.into_iter().filter_map(path_to_js).collect();
+      (name.to_owned(), paths)
+    })
+    .collect()
+}
+
+/// `path`, or `undefined` like `find-up` instead of `null`.
+fn or_undefined(path: Option<String>) -> Either<String, Undefined> {
+  path.map_or(Either::B(()), Either::A)
+}
+
+/// `path` as a JavaScript string, `None` if it isn't valid UTF-8.
+fn path_to_js(path: PathBuf) -> Option<String> {
+  path.into_os_string().into_string().ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn fixtures(path: &str) -> String {
+    let fixtures = resolve("../../fixtures").unwrap();
+    fixtures.join(path).into_os_string().into_string().unwrap()
+  }
+
+  #[test]
+  fn should_take_the_options_of_the_find_up_package() {
+    let options = || FindUpOptions {
+      cwd: Some("../../fixtures/a/b/c/d".to_owned()),
+      ..FindUpOptions::default()
+    };
+
+    assert!(matches!(
+      find_up_sync("package.json".to_owned(), Some(options())),
+      Ok(Either::A(path)) if path == fixtures("a/b/c/d/package.json")
+    ));
+    assert!(matches!(
+      find_up_sync(
+        "package.json".to_owned(),
+        Some(FindUpOptions {
+          kind: Some("directory".to_owned()),
+          ..options()
+        })
+      ),
+      Ok(Either::B(()))
+    ));
+    assert!(
+      find_up_sync(
+        "package.json".to_owned(),
+        Some(FindUpOptions {
+          kind: Some("symlink".to_owned()),
+          ..options()
+        })
+      )
+      .is_err()
+    );
+
+    let found = find_up_multi_sync(
+      vec!["package.json".to_owned()],
+      Some(FindUpOptions {
+        stop_at: Some("../../fixtures/a/b/c".to_owned()),
+        ..options()
+      }),
+    )
+    .unwrap();
+    assert_eq!(
+      found["package.json"],
+      [
+        fixtures("a/b/c/d/package.json"),
+        fixtures("a/b/c/package.json")
+      ]
+    );
+  }
+
+  #[test]
+  fn should_search_in_the_async_tasks() {
+    let options = || FindUpOptions {
+      cwd: Some("../../fixtures/a/b/c/d".to_owned()),
+      max_depth: Some(1),
+      ..FindUpOptions::default()
+    };
+
+    let mut task = FindUp {
+      up_finder: options().up_finder().unwrap(),
+      name: ".node-version".to_owned(),
+    };
+    assert_eq!(
+      task.compute().unwrap(),
+      Some(fixtures("a/b/c/.node-version"))
+    );
+
+    let mut multi_task = FindUpMulti {
+      up_finder: options().up_finder().unwrap(),
+      names: vec!["package.json".to_owned()],
+    };
+    assert_eq!(multi_task.compute().unwrap()["package.json"].len(), 2);
+
+    assert!(find_up("package.json".to_owned(), Some(options())).is_ok());
+    assert!(find_up_multi(vec!["package.json".to_owned()], Some(options())).is_ok());
+  }
+}
@@ -0,0 +1,120 @@
+use std::{
+  io,
+  path::{self, Path, PathBuf},
+};
+
+use crate::{FindUpKind, StartFrom, UpFinder, restrict::clean};
+
+/// The options of a search as command line flags, to `#[command(flatten)]`
+/// into the arguments of a `clap` command, with the `clap` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use clap::Parser;
+/// use up_finder::FindUpArgs;
+///
+/// #[derive(Parser)]
+/// struct Cli {
+///   #[command(flatten)]
+///   find_up: FindUpArgs,
+/// }
+///
+/// let cli = Cli::parse_from(["tool", "--type", "dir", "--max-depth", "3"]);
+/// let find_up = cli.find_up.up_finder()?;
+/// let paths = find_up.find_up(".git");
+///
+/// println!("{:#?}", paths);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, clap::Args)]
+pub struct FindUpArgs {
+  /// The directory to start from instead of the current directory.
+  #[arg(long, value_name = "DIR")]
+  pub start: Option<PathBuf>,
+
+  /// The kind of entry to look for.
+  #[arg(long = "type", value_enum, default_value_t = FindUpKind::File)]
+  pub kind: FindUpKind,
+
+  /// The last directory to search.
+  #[arg(long, value_name = "PATH")]
+  pub stop_at: Option<PathBuf>,
+
+  /// How many levels above the start directory to search at most.
+  #[arg(long, value_name = "N")]
+  pub max_depth: Option<usize>,
+}
+
+impl FindUpArgs {
+  /// An [`UpFinder`] with these options over the real filesystem, with the
+  /// start directory and `stop_at` made absolute.
+  pub fn up_finder(&self) -> io::Result<UpFinder<PathBuf>> {
+    let start = match &self.start {
+      Some(start) => absolute(start)?,
+      None => StartFrom::CurrentDir.dir()?,
+    };
+    let stop_at = self.stop_at.as_deref().map(absolute).transpose()?;
+
+    Ok(
+      UpFinder::builder()
+        .cwd(start)
+        .kind(self.kind.clone())
+        .stop_at_opt(stop_at)
+        .max_depth_opt(self.max_depth)
+        .build(),
+    )
+  }
+}
+
+impl TryFrom<FindUpArgs> for UpFinder<PathBuf> {
+  type Error = io::Error;
+
+  fn try_from(args: FindUpArgs) -> io::Result<Self> {
+    args.up_finder()
+  }
+}
+
+/// `path` from the current directory, without `.` and `..`.
+fn absolute(path: &Path) -> io::Result<PathBuf> {
+  Ok(clean(&path::absolute(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+  use clap::Parser;
+
+  use super::*;
+
+  #[derive(Debug, Parser)]
+  struct Cli {
+    #[command(flatten)]
+    find_up: FindUpArgs,
+  }
+
+  #[test]
+  fn should_parse_the_options_of_a_search() {
+    let cli = Cli::parse_from([
+      "tool",
+      "--start",
+      "fixtures/a/b/c/d",
+      "--type",
+      "dir",
+      "--stop-at",
+      "fixtures/a/b/c/../..",
+      "--max-depth",
+      "3",
+    ]);
+    let up_finder = cli.find_up.up_finder().unwrap();
+
+    let cwd = std::env::current_dir().unwrap();
+    assert_eq!(up_finder.cwd, cwd.join("fixtures/a/b/c/d"));
+    assert_eq!(up_finder.kind, FindUpKind::Dir);
+    assert_eq!(up_finder.stop_at, Some(cwd.join("fixtures/a")));
+    assert_eq!(up_finder.max_depth, Some(3));
+
+    let cli = Cli::parse_from(["tool"]);
+    assert_eq!(cli.find_up.kind, FindUpKind::File);
+    assert!(Cli::try_parse_from(["tool", "--type", "symlink"]).is_err());
+  }
+}
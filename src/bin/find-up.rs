@@ -0,0 +1,823 @@
+//! Find files or directories upward from the shell.
+//!
+//! ```sh
+//! $ find-up package.json
+//! /home/me/project/package.json
+//! ```
+//!
+//! Defaults for the options of the walk and of `root` can be kept in
+//! `~/.config/find-up/config.toml`, or `$XDG_CONFIG_HOME/find-up/config.toml`:
+//!
+//! ```toml
+//! markers = [".git", "Cargo.toml"]
+//! stop-at = "/home/me"
+//! max-depth = 8
+//! exclude-dependency-dirs = true
+//! ```
+//!
+//! The `FIND_UP_MARKERS`, `FIND_UP_STOP_AT`, `FIND_UP_MAX_DEPTH` and
+//! `FIND_UP_EXCLUDE_DEPENDENCY_DIRS` environment variables override the file,
+//! and flags override both.
+//!
+//! # Exit status
+//!
+//! - `0`: something was found, or the `--exec` command exited with `0`
+//! - `1`: nothing was found
+//! - `2`: the arguments, a pattern or the configuration are invalid
+//! - `3`: permission was denied, e.g. to search the start directory
+//! - `4`: any other error
+//!
+//! With `--exec` the status of the command is passed on instead.
+use std::{
+  borrow::Cow,
+  ffi::{OsStr, OsString},
+  fs,
+  io::{self, Write},
+  path::{self, Component, Path, PathBuf},
+  process::{Command, ExitCode},
+};
+
+use clap::{Parser, ValueEnum};
+use regex::Regex;
+use serde_json::{Value, json};
+use toml::{Table, Value as TomlValue};
+use up_finder::{
+  ConfigFallback, FindUpError, FindUpKind, FindUpMatch, Glob, StartFrom, UpFinder, clean,
+};
+
+/// Print the nearest of NAMES in the current directory or one of its parents,
+/// or with `--all` every one of them. Exits with 1 if there is none.
+#[derive(Debug, Parser)]
+#[command(
+  name = "find-up",
+  version,
+  after_help = "Exits with 0 if something was found, 1 if not, 2 for invalid arguments, 3 if \
+                permission was denied and 4 for other errors.",
+  args_conflicts_with_subcommands = true,
+  subcommand_negates_reqs = true
+)]
+struct Cli {
+  #[command(subcommand)]
+  command: Option<Subcommand>,
+
+  /// The names to look for. Names with `*`, `?`, `[` or `\` are globs,
+  /// quote them to keep the shell from expanding them. Of several names found
+  /// on the same level, the first one given is printed. Write `./root` to
+  /// look for a file named like a subcommand.
+  #[arg(required_unless_present = "shell")]
+  names: Vec<String>,
+
+  /// Take NAMES as regular expressions, matched anywhere in the name unless
+  /// anchored with `^` and `$`.
+  #[arg(long)]
+  regex: bool,
+
+  /// The kind of entry to look for.
+  #[arg(long = "type", value_enum, default_value_t = FindUpKind::File)]
+  kind: FindUpKind,
+
+  #[command(flatten)]
+  walk: Walk,
+
+  /// Print only the nearest match, the default.
+  #[arg(long, overrides_with = "all")]
+  first: bool,
+
+  /// Print every match, nearest first.
+  #[arg(long, overrides_with = "first")]
+  all: bool,
+
+  /// Print a JSON object with the matches of every name, each with its
+  /// `name`, `path`, `depth`, `kind` and the `pattern` it matched.
+  #[arg(long)]
+  json: bool,
+
+  /// End every path with a NUL byte instead of a newline, for `xargs -0`.
+  #[arg(short = '0', long, conflicts_with = "json")]
+  print0: bool,
+
+  /// Run CMD instead of printing the nearest match, with the arguments given
+  /// after `--`. `{}` in them is replaced by the path of the match, which is
+  /// passed as the last argument if there is no `{}`. Exits with the status
+  /// of CMD.
+  #[arg(long, value_name = "CMD", conflicts_with_all = ["all", "json", "print0"])]
+  exec: Option<String>,
+
+  /// The arguments of the `--exec` command.
+  #[arg(last = true, value_name = "ARGS", requires = "exec")]
+  exec_args: Vec<String>,
+
+  /// Run the `--exec` command in the directory containing the match.
+  #[arg(long, requires = "exec")]
+  chdir: bool,
+
+  /// Print the nearest match of every name, or with `--all` every match,
+  /// under a heading for its name or for the directory it was found in.
+  #[arg(long, value_name = "BY", value_enum, conflicts_with_all = ["json", "print0", "exec"])]
+  group_by: Option<GroupBy>,
+
+  /// Print nothing, only exit with 0 if something was found and 1 if not.
+  #[arg(
+    short,
+    long,
+    conflicts_with_all = ["json", "print0", "exec", "group_by", "count"]
+  )]
+  quiet: bool,
+
+  /// Print the paths relative to BASE, the current directory by default,
+  /// instead of absolute ones.
+  #[arg(
+    long,
+    value_name = "BASE",
+    num_args = 0..=1,
+    require_equals = true,
+    default_missing_value = "."
+  )]
+  relative: Option<PathBuf>,
+
+  /// Keep running and print the nearest match again whenever it changes,
+  /// with an empty line while there is none. Takes plain names only.
+  #[arg(
+    long,
+    conflicts_with_all = ["regex", "all", "json", "print0", "exec", "group_by", "quiet", "count"]
+  )]
+  watch: bool,
+
+  /// Print the number of matches, counting every match like `--all`.
+  #[arg(long, conflicts_with_all = ["json", "print0", "exec", "group_by"])]
+  count: bool,
+
+  /// Print a `cdroot` function for SHELL that changes to the directory
+  /// `find-up root` prints, taking the same options. Add it to the shell's
+  /// startup file, e.g. `eval "$(find-up --shell bash)"` in `~/.bashrc`.
+  #[arg(long, value_name = "SHELL", value_enum, exclusive = true)]
+  shell: Option<Shell>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Subcommand {
+  /// Print the nearest directory containing any of the markers.
+  Root {
+    /// The names whose presence marks a root, files and directories alike.
+    /// Defaults to `.git,Cargo.toml,package.json`.
+    #[arg(
+      long,
+      value_name = "NAMES",
+      value_delimiter = ',',
+      env = "FIND_UP_MARKERS"
+    )]
+    markers: Option<Vec<String>>,
+
+    #[command(flatten)]
+    walk: Walk,
+  },
+}
+
+/// Where the walk starts and ends.
+#[derive(Debug, clap::Args)]
+struct Walk {
+  /// The last directory to search.
+  #[arg(long, value_name = "PATH", env = "FIND_UP_STOP_AT")]
+  stop_at: Option<PathBuf>,
+
+  /// How many levels above the start directory to search at most.
+  #[arg(long, value_name = "N", env = "FIND_UP_MAX_DEPTH")]
+  max_depth: Option<usize>,
+
+  /// Skip matches inside `node_modules`, `target` and other dependency
+  /// directories.
+  #[arg(
+    long,
+    value_name = "BOOL",
+    num_args = 0..=1,
+    require_equals = true,
+    default_missing_value = "true",
+    env = "FIND_UP_EXCLUDE_DEPENDENCY_DIRS"
+  )]
+  exclude_dependency_dirs: Option<bool>,
+
+  /// The directory to start from instead of the current directory.
+  #[arg(long, value_name = "DIR")]
+  start: Option<PathBuf>,
+}
+
+impl Walk {
+  /// The absolute start directory.
+  fn start(&self) -> io::Result<PathBuf> {
+    let start = match &self.start {
+      Some(start) => absolute(start)?,
+      None => StartFrom::CurrentDir.dir()?,
+    };
+    // A start directory that can't be searched would look like one without
+    // any matches.
+    fs::read_dir(&start)
+      .map_err(|error| io::Error::new(error.kind(), format!("{}: {error}", start.display())))?;
+
+    Ok(start)
+  }
+
+  fn up_finder(&self, kind: FindUpKind, config: &Config) -> io::Result<UpFinder<PathBuf>> {
+    let start = self.start()?;
+    let stop_at = self.stop_at.as_ref().or(config.stop_at.as_ref());
+    let stop_at = stop_at.map(|path| absolute(path)).transpose()?;
+
+    Ok(
+      UpFinder::builder()
+        .cwd(start)
+        .kind(kind)
+        .stop_at_opt(stop_at)
+        .max_depth_opt(self.max_depth.or(config.max_depth))
+        .exclude_dependency_dirs(
+          self
+            .exclude_dependency_dirs
+            .or(config.exclude_dependency_dirs)
+            .unwrap_or_default(),
+        )
+        .build(),
+    )
+  }
+}
+
+/// The defaults read from `config.toml`.
+#[derive(Debug, Default)]
+struct Config {
+  markers: Option<Vec<String>>,
+  stop_at: Option<PathBuf>,
+  max_depth: Option<usize>,
+  exclude_dependency_dirs: Option<bool>,
+}
+
+impl Config {
+  /// Read `find-up/config.toml` from the user's configuration directory, if
+  /// there is one.
+  fn load() -> io::Result<Self> {
+    let Some(dir) = ConfigFallback::xdg("find-up").user_dirs().first().cloned() else {
+      return Ok(Self::default());
+    };
+    let path = dir.join("config.toml");
+
+    let text = match fs::read_to_string(&path) {
+      Ok(text) => text,
+      Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+      Err(error) => return Err(error),
+    };
+
+    Self::parse(&text).map_err(|message| {
+      io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {message}", path.display()),
+      )
+    })
+  }
+
+  fn parse(text: &str) -> Result<Self, String> {
+    let table: Table = text.parse().map_err(|error| format!("{error}"))?;
+    let mut config = Self::default();
+
+    for (key, value) in table {
+      let invalid = || format!("invalid value for `{key}`");
+      match key.as_str() {
+        "markers" => {
+          let TomlValue::Array(markers) = value else {
+            return Err(invalid());
+          };
+          let markers = markers.into_iter().map(|marker| match marker {
+            TomlValue::String(marker) => Some(marker),
+            _ => None,
+          });
+          config.markers = Some(markers.collect::<Option<_>>().ok_or_else(invalid)?);
+        }
+        "stop-at" => {
+          let TomlValue::String(path) = value else {
+            return Err(invalid());
+          };
+          config.stop_at = Some(path.into());
+        }
+        "max-depth" => {
+          let TomlValue::Integer(depth) = value else {
+            return Err(invalid());
+          };
+          config.max_depth = Some(usize::try_from(depth).map_err(|_| invalid())?);
+        }
+        "exclude-dependency-dirs" => {
+          let TomlValue::Boolean(exclude) = value else {
+            return Err(invalid());
+          };
+          config.exclude_dependency_dirs = Some(exclude);
+        }
+        _ => return Err(format!("unknown key `{key}`")),
+      }
+    }
+
+    Ok(config)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum GroupBy {
+  Name,
+  Dir,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Shell {
+  Bash,
+  Zsh,
+  Fish,
+  Powershell,
+}
+
+impl Shell {
+  /// The `cdroot` function, in this shell's syntax.
+  fn cdroot(self) -> &'static str {
+    match self {
+      Shell::Bash | Shell::Zsh => {
+        r#"cdroot() {
+  local dir
+  dir="$(command find-up root "$@")" && cd -- "$dir"
+}
+"#
+      }
+      Shell::Fish => {
+        r#"function cdroot
+  set -l dir (command find-up root $argv); and cd -- $dir
+end
+"#
+      }
+      Shell::Powershell => {
+        r#"function cdroot {
+  $dir = & find-up root @args
+  if ($LASTEXITCODE -eq 0) { Set-Location -LiteralPath $dir }
+}
+"#
+      }
+    }
+  }
+}
+
+// The exit statuses for errors, see the crate docs. clap exits with `USAGE`
+// for invalid arguments by itself.
+const USAGE: u8 = 2;
+const PERMISSION_DENIED: u8 = 3;
+const FAILED: u8 = 4;
+
+/// The markers of `root` unless configured otherwise.
+const DEFAULT_MARKERS: &[&str] = &[".git", "Cargo.toml", "package.json"];
+
+fn main() -> ExitCode {
+  let cli = Cli::parse();
+
+  if let Some(shell) = cli.shell {
+    print!("{}", shell.cdroot());
+    return ExitCode::SUCCESS;
+  }
+
+  let result = Config::load().and_then(|config| match &cli.command {
+    Some(Subcommand::Root { markers, walk }) => {
+      let markers = match markers.as_ref().or(config.markers.as_ref()) {
+        Some(markers) => markers.iter().map(String::as_str).collect(),
+        None => DEFAULT_MARKERS.to_vec(),
+      };
+      root(&markers, walk, &config)
+    }
+    None => run(&cli, &config),
+  });
+
+  match result {
+    Ok(code) => code,
+    Err(error) => {
+      eprintln!("find-up: {error}");
+      ExitCode::from(exit_status(&error))
+    }
+  }
+}
+
+/// The exit status for `error`, see the crate docs.
+fn exit_status(error: &io::Error) -> u8 {
+  match error.kind() {
+    io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData => USAGE,
+    io::ErrorKind::PermissionDenied => PERMISSION_DENIED,
+    _ => FAILED,
+  }
+}
+
+/// Print the matches or run the `--exec` command.
+fn run(args: &Cli, config: &Config) -> io::Result<ExitCode> {
+  let up_finder = args.walk.up_finder(args.kind.clone(), config)?;
+
+  let names: Vec<&str> = args.names.iter().map(String::as_str).collect();
+
+  if args.watch {
+    return watch(args, &up_finder, &names);
+  }
+
+  let mut found = find(args, &up_finder, &names)?;
+
+  if args.group_by.is_some() {
+    if !args.all {
+      let mut seen = Vec::new();
+      found.retain(|&(name, _)| {
+        let first = !seen.contains(&name);
+        seen.push(name);
+        first
+      });
+    }
+  } else if !args.all && !args.count {
+    found.truncate(1);
+  }
+
+  if let Some(command) = &args.exec {
+    return match found.first() {
+      Some((_, found)) => exec(command, &args.exec_args, found.path(), args.chdir),
+      None => Ok(ExitCode::FAILURE),
+    };
+  }
+
+  let base = args.relative.as_deref().map(absolute).transpose()?;
+  let base = base.as_deref();
+
+  if args.quiet {
+    // Only the exit status is wanted.
+  } else if args.count {
+    println!("{}", found.len());
+  } else if args.json {
+    println!("{}", to_json(&names, &found, base));
+  } else if let Some(group_by) = args.group_by {
+    print_grouped(&names, &found, group_by, base)?;
+  } else {
+    let mut stdout = io::stdout().lock();
+    for (_, found) in &found {
+      // Written as is, `display` would replace what isn't valid Unicode.
+      let path = shown(found.path(), base);
+      stdout.write_all(path.as_os_str().as_encoded_bytes())?;
+      stdout.write_all(if args.print0 { b"\0" } else { b"\n" })?;
+    }
+    stdout.flush()?;
+  }
+
+  Ok(if found.is_empty() {
+    ExitCode::FAILURE
+  } else {
+    ExitCode::SUCCESS
+  })
+}
+
+/// Every match of `names`, nearest first.
+fn find<'a>(
+  args: &Cli,
+  up_finder: &UpFinder<PathBuf>,
+  names: &[&'a str],
+) -> io::Result<Vec<(&'a str, FindUpMatch)>> {
+  let (patterns, plain): (Vec<&str>, Vec<&str>) = names
+    .iter()
+    .partition(|name| args.regex || Glob::is_pattern(name));
+
+  let mut matches = up_finder.try_find_up_multi_matches(&plain).map_err(to_io)?;
+  for &pattern in &patterns {
+    let found = if args.regex {
+      let regex =
+        Regex::new(pattern).map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+      up_finder.find_up_regex_matches(&regex)
+    } else {
+      up_finder.find_up_glob_matches(&Glob::new(pattern))
+    };
+    matches.insert(pattern, found);
+  }
+
+  // Sorting is stable, so matches on the same level stay in the order of
+  // `names`.
+  let mut found: Vec<(&str, FindUpMatch)> = names
+    .iter()
+    .flat_map(|&name| {
+      let found = matches.remove(name).unwrap_or_default();
+      found.into_iter().map(move |found| (name, found))
+    })
+    .collect();
+  found.sort_by_key(|(_, found)| found.depth());
+
+  Ok(found)
+}
+
+/// Print the nearest match, then again whenever another entry becomes the
+/// nearest one or the last one goes away, until interrupted. A line is empty
+/// while there is no match.
+fn watch(args: &Cli, up_finder: &UpFinder<PathBuf>, names: &[&str]) -> io::Result<ExitCode> {
+  if names.iter().any(|name| Glob::is_pattern(name)) {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      "--watch takes plain names, not patterns",
+    ));
+  }
+
+  let base = args.relative.as_deref().map(absolute).transpose()?;
+
+  for nearest in up_finder.watch_multi(names)? {
+    let mut stdout = io::stdout().lock();
+    if let Some(path) = nearest? {
+      stdout.write_all(shown(&path, base.as_deref()).as_os_str().as_encoded_bytes())?;
+    }
+    stdout.write_all(b"\n")?;
+    stdout.flush()?;
+  }
+
+  Ok(ExitCode::SUCCESS)
+}
+
+/// `error` as an I/O error, whose kind gives the exit status, see `main`.
+fn to_io(error: FindUpError) -> io::Error {
+  match error {
+    FindUpError::Io(error) => error,
+    error => io::Error::other(error),
+  }
+}
+
+/// Print the matches under a heading for each name or directory, in the order
+/// the names were given or the directories were reached.
+fn print_grouped(
+  names: &[&str],
+  found: &[(&str, FindUpMatch)],
+  group_by: GroupBy,
+  base: Option<&Path>,
+) -> io::Result<()> {
+  let mut groups: Vec<(OsString, Vec<OsString>)> = Vec::new();
+
+  for (name, found) in found {
+    let path = found.path();
+    let (heading, entry) = match group_by {
+      GroupBy::Name => (name.into(), shown(path, base).into_owned().into()),
+      GroupBy::Dir => (
+        shown(path.parent().unwrap_or(path), base)
+          .into_owned()
+          .into(),
+        path.file_name().unwrap_or(path.as_os_str()).to_owned(),
+      ),
+    };
+
+    match groups.iter_mut().find(|(group, _)| *group == heading) {
+      Some((_, entries)) => entries.push(entry),
+      None => groups.push((heading, vec![entry])),
+    }
+  }
+
+  if group_by == GroupBy::Name {
+    groups.sort_by_key(|(heading, _)| names.iter().position(|name| OsStr::new(name) == heading));
+  }
+
+  let mut stdout = io::stdout().lock();
+  for (i, (heading, entries)) in groups.iter().enumerate() {
+    if i > 0 {
+      stdout.write_all(b"\n")?;
+    }
+    stdout.write_all(heading.as_encoded_bytes())?;
+    stdout.write_all(b":\n")?;
+    for entry in entries {
+      stdout.write_all(entry.as_encoded_bytes())?;
+      stdout.write_all(b"\n")?;
+    }
+  }
+  stdout.flush()
+}
+
+/// Print the directory of the nearest marker.
+fn root(markers: &[&str], walk: &Walk, config: &Config) -> io::Result<ExitCode> {
+  let matches = walk
+    .up_finder(FindUpKind::Any, config)?
+    .try_find_up_multi_matches(markers)
+    .map_err(to_io)?;
+
+  let root = markers
+    .iter()
+    .filter_map(|marker| matches[marker].first())
+    .min_by_key(|found| found.depth())
+    .map(FindUpMatch::dir);
+
+  let Some(root) = root else {
+    return Ok(ExitCode::FAILURE);
+  };
+
+  let mut stdout = io::stdout().lock();
+  stdout.write_all(root.as_os_str().as_encoded_bytes())?;
+  stdout.write_all(b"\n")?;
+  stdout.flush()?;
+
+  Ok(ExitCode::SUCCESS)
+}
+
+/// Run `program` with `args`, `{}` in them replaced by `path` or `path`
+/// appended if there is none, passing on its exit status.
+fn exec(program: &str, args: &[String], path: &Path, chdir: bool) -> io::Result<ExitCode> {
+  let substitute = |arg: &String| {
+    let mut substituted = OsString::new();
+    for (i, part) in arg.split("{}").enumerate() {
+      if i > 0 {
+        substituted.push(path);
+      }
+      substituted.push(part);
+    }
+    substituted
+  };
+
+  let mut process = Command::new(program);
+  process.args(args.iter().map(substitute));
+  if !args.iter().any(|arg| arg.contains("{}")) {
+    process.arg(path);
+  }
+
+  if chdir {
+    if let Some(dir) = path.parent() {
+      process.current_dir(dir);
+    }
+  }
+
+  let status = process.status()?;
+
+  // Killed by a signal, there is no code to pass on.
+  Ok(match status.code() {
+    Some(code) => ExitCode::from(u8::try_from(code).unwrap_or(1)),
+    None => ExitCode::FAILURE,
+  })
+}
+
+/// The matches grouped by the name they were found for.
+fn to_json(names: &[&str], found: &[(&str, FindUpMatch)], base: Option<&Path>) -> Value {
+  let groups = names
+    .iter()
+    .map(|&pattern| {
+      let matches = found
+        .iter()
+        .filter(|(name, _)| *name == pattern)
+        .map(|(_, found)| {
+          let path = found.path();
+          let kind = match fs::metadata(path) {
+            Ok(metadata) if metadata.is_file() => "file",
+            Ok(metadata) if metadata.is_dir() => "dir",
+            _ => "other",
+          };
+          json!({
+            "name": path.file_name().map(|name| name.to_string_lossy()),
+            "path": shown(path, base).to_string_lossy(),
+            "depth": found.depth(),
+            "kind": kind,
+            "pattern": pattern,
+          })
+        })
+        .collect();
+      (pattern.to_owned(), Value::Array(matches))
+    })
+    .collect();
+
+  Value::Object(groups)
+}
+
+/// `path` as printed, relative to `base` if there is one.
+fn shown<'a>(path: &'a Path, base: Option<&Path>) -> Cow<'a, Path> {
+  match base {
+    Some(base) => Cow::Owned(relative(path, base)),
+    None => Cow::Borrowed(path),
+  }
+}
+
+/// `path` relative to `base`, both absolute and lexically normalized. Paths
+/// on another drive stay absolute.
+fn relative(path: &Path, base: &Path) -> PathBuf {
+  let mut path_components = path.components().peekable();
+  let mut base_components = base.components().peekable();
+
+  if path_components.peek() != base_components.peek() {
+    return path.to_path_buf();
+  }
+
+  while path_components.peek().is_some() && path_components.peek() == base_components.peek() {
+    path_components.next();
+    base_components.next();
+  }
+
+  let relative: PathBuf = base_components
+    .map(|_| Component::ParentDir)
+    .chain(path_components)
+    .collect();
+
+  if relative.as_os_str().is_empty() {
+    PathBuf::from(".")
+  } else {
+    relative
+  }
+}
+
+/// `path` relative to the current directory, with `.` and `..` resolved
+/// lexically so it lines up with the ancestors of the start directory.
+fn absolute(path: &Path) -> io::Result<PathBuf> {
+  Ok(clean(&path::absolute(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_parse_the_config() {
+    let config = Config::parse(
+      r#"
+        markers = [".git", "Cargo.toml"]
+        stop-at = "/home/me"
+        max-depth = 8
+        exclude-dependency-dirs = true
+      "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      config.markers,
+      Some(vec![".git".into(), "Cargo.toml".into()])
+    );
+    assert_eq!(config.stop_at, Some(PathBuf::from("/home/me")));
+    assert_eq!(config.max_depth, Some(8));
+    assert_eq!(config.exclude_dependency_dirs, Some(true));
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn should_make_paths_relative() {
+    let relative = |path: &str, base: &str| relative(Path::new(path), Path::new(base));
+
+    assert_eq!(relative("/a/b/c.txt", "/a/b"), Path::new("c.txt"));
+    assert_eq!(relative("/a/c.txt", "/a/b/d"), Path::new("../../c.txt"));
+    assert_eq!(relative("/a/b", "/a/b"), Path::new("."));
+    assert_eq!(relative("/c.txt", "/"), Path::new("c.txt"));
+  }
+
+  #[test]
+  fn should_reject_unknown_keys_and_invalid_values() {
+    assert_eq!(
+      Config::parse("marker = []").unwrap_err(),
+      "unknown key `marker`"
+    );
+    assert_eq!(
+      Config::parse("max-depth = -1").unwrap_err(),
+      "invalid value for `max-depth`"
+    );
+    assert_eq!(
+      Config::parse("markers = [1]").unwrap_err(),
+      "invalid value for `markers`"
+    );
+  }
+
+  #[test]
+  fn should_take_the_names_after_the_exec_command() {
+    let cli = Cli::try_parse_from(["find-up", "--exec", "cat", "package.json"]).unwrap();
+    assert_eq!(cli.exec.as_deref(), Some("cat"));
+    assert!(cli.exec_args.is_empty());
+    assert_eq!(cli.names, ["package.json"]);
+
+    let cli = Cli::try_parse_from([
+      "find-up",
+      "package.json",
+      "--exec",
+      "grep",
+      "--",
+      "-n",
+      "{}",
+    ])
+    .unwrap();
+    assert_eq!(cli.exec.as_deref(), Some("grep"));
+    assert_eq!(cli.exec_args, ["-n", "{}"]);
+    assert_eq!(cli.names, ["package.json"]);
+
+    assert!(Cli::try_parse_from(["find-up", "package.json", "--", "-n"]).is_err());
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn should_pass_the_match_to_the_exec_command() {
+    let path = Path::new("Cargo.toml");
+    let status = |args: &[&str]| {
+      let args: Vec<String> = args.iter().map(|&arg| arg.into()).collect();
+      exec("sh", &args, path, false).unwrap()
+    };
+
+    assert_eq!(
+      status(&["-c", "test \"$0\" = Cargo.toml"]),
+      ExitCode::SUCCESS
+    );
+    assert_eq!(
+      status(&["-c", "test \"$1\" = Cargo.toml", "{}", "{}"]),
+      ExitCode::SUCCESS
+    );
+    assert_eq!(status(&["-c", "exit 3"]), ExitCode::from(3));
+  }
+
+  #[test]
+  fn should_exit_with_the_status_of_the_search_error() {
+    let status = |error: FindUpError| exit_status(&to_io(error));
+
+    assert_eq!(
+      status(io::Error::from(io::ErrorKind::PermissionDenied).into()),
+      PERMISSION_DENIED
+    );
+    assert_eq!(
+      status(io::Error::from(io::ErrorKind::TimedOut).into()),
+      FAILED
+    );
+    assert_eq!(status(FindUpError::Escape("/link".into())), FAILED);
+  }
+}
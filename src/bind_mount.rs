@@ -0,0 +1,69 @@
+use std::{io, path::Path};
+
+/// What the walk does on reaching a directory it has already searched under
+/// another path, as happens when a bind mount or a symlinked ancestor makes a
+/// tree show up inside itself.
+///
+/// Directories are told apart by [`Fs::file_id`](crate::Fs::file_id), their
+/// device and inode numbers for [`StdFs`](crate::StdFs) on Unix. Filesystems
+/// that can't identify directories never report duplicates.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{BindMountPolicy, UpFinder};
+///
+/// // A container with the project bind mounted below itself.
+/// let find_up = UpFinder::builder()
+///   .cwd(".")
+///   .bind_mounts(BindMountPolicy::Skip)
+///   .build();
+/// let paths = find_up.find_up("package.json");
+///
+/// println!("{:#?}", paths);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BindMountPolicy {
+  /// Search every level, even if it was already searched under another path.
+  #[default]
+  Follow,
+  /// Don't search levels that were already searched, but go on to their parents.
+  Skip,
+  /// Stop below the first level that was already searched.
+  Stop,
+}
+
+/// The device and inode numbers of the entry at `path`.
+#[cfg_attr(
+  all(target_family = "wasm", target_os = "unknown"),
+  allow(dead_code, reason = "there is no `StdFs` to call it")
+)]
+pub(crate) fn file_id(path: &Path) -> io::Result<Option<(u64, u64)>> {
+  let path = if path.as_os_str().is_empty() {
+    Path::new(".")
+  } else {
+    path
+  };
+
+  sys::file_id(path)
+}
+
+#[cfg(unix)]
+mod sys {
+  use std::{fs, io, os::unix::fs::MetadataExt, path::Path};
+
+  pub(super) fn file_id(path: &Path) -> io::Result<Option<(u64, u64)>> {
+    let metadata = fs::metadata(path)?;
+
+    Ok(Some((metadata.dev(), metadata.ino())))
+  }
+}
+
+#[cfg(not(unix))]
+mod sys {
+  use std::{io, path::Path};
+
+  pub(super) fn file_id(_path: &Path) -> io::Result<Option<(u64, u64)>> {
+    Ok(None)
+  }
+}
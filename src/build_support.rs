@@ -0,0 +1,129 @@
+//! Searches for build scripts that keep Cargo informed about what they
+//! depend on.
+//!
+//! A build script that looks up e.g. a `.env` with [`UpFinder::find_up`]
+//! isn't run again when one appears at a higher level later. The searches
+//! here also print `cargo:rerun-if-changed` for every path they looked up on
+//! their way, whether there was a file or not, so the build script runs
+//! again whenever a match appears, disappears or changes.
+//!
+//! Paths are tracked instead of the directories they are in, since Cargo
+//! looks at everything below a directory it tracks, e.g. `target`. Cargo
+//! also runs a build script again on every build while one of its tracked
+//! paths doesn't exist, so searches that reach high up are best bounded with
+//! `stop_at` or `max_depth`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! // build.rs
+//! use up_finder::build_support::find_up_tracked;
+//!
+//! if let Some(config) = find_up_tracked("build-config.toml").first() {
+//!   println!("cargo:rustc-env=BUILD_CONFIG={}", config.display());
+//! }
+//! ```
+
+use std::{
+  env,
+  path::{Path, PathBuf},
+};
+
+use crate::{FindUpMatch, UpFinder, hash::HashSet};
+
+/// Find a file from the directory of the package being built, see
+/// [`UpFinder::find_up_tracked`].
+pub fn find_up_tracked(name: &str) -> Vec<PathBuf> {
+  let cwd = env::var_os("CARGO_MANIFEST_DIR").map_or_else(|| PathBuf::from("."), PathBuf::from);
+
+  UpFinder::builder().cwd(cwd).build().find_up_tracked(name)
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Like [`UpFinder::find_up`], printing `cargo:rerun-if-changed` for every
+  /// path the search looked up, see [`build_support`](crate::build_support).
+  /// Matched directories are not tracked.
+  pub fn find_up_tracked(&self, name: &str) -> Vec<PathBuf> {
+    let (paths, tracked) = self.find_up_tracking(name);
+    for path in tracked {
+      println!("cargo:rerun-if-changed={}", path.display());
+    }
+
+    paths
+  }
+
+  /// The matches of `name` and the paths that tell when they change, in the
+  /// order the search looked them up.
+  fn find_up_tracking(&self, name: &str) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let up_finder = UpFinder {
+      record_visits: true,
+      ..self.borrowed()
+    };
+    let (matches, report) = up_finder.find_up_report(name);
+
+    let mut seen: HashSet<&Path> = HashSet::default();
+    let tracked = report
+      .visits()
+      .iter()
+      .flat_map(|visit| visit.candidates().iter().map(PathBuf::as_path))
+      .chain(matches.iter().map(|found| found.path()))
+      .filter(|path| {
+        seen.insert(path)
+          && !self
+            .fs
+            .metadata(path)
+            .is_ok_and(|metadata| metadata.is_dir())
+      })
+      .map(Path::to_path_buf)
+      .collect();
+
+    let paths = matches.into_iter().map(FindUpMatch::into_path).collect();
+
+    (paths, tracked)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::{FindUpKind, MemoryFs};
+
+  #[test]
+  fn should_track_every_path_looked_up() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/.config/build.toml", "")
+      .with_dir("/repo/crates/app/build.toml")
+      .with_file("/build.toml", "");
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/crates/app")
+      .kind(FindUpKind::Any)
+      .config_dir(true)
+      .fs(Arc::new(fs))
+      .build();
+
+    let (paths, tracked) = up_finder.find_up_tracking("build.toml");
+
+    assert_eq!(
+      paths,
+      [
+        PathBuf::from("/repo/crates/app/build.toml"),
+        PathBuf::from("/repo/.config/build.toml"),
+        PathBuf::from("/build.toml")
+      ]
+    );
+    assert_eq!(
+      tracked,
+      [
+        PathBuf::from("/repo/crates/app/.config/build.toml"),
+        PathBuf::from("/repo/crates/build.toml"),
+        PathBuf::from("/repo/crates/.config/build.toml"),
+        PathBuf::from("/repo/build.toml"),
+        PathBuf::from("/repo/.config/build.toml"),
+        PathBuf::from("/build.toml"),
+        PathBuf::from("/.config/build.toml"),
+      ]
+    );
+  }
+}
@@ -0,0 +1,281 @@
+//! The builder of [`UpFinder`]. A missing `cwd`, or a missing `fs` on
+//! `wasm32-unknown-unknown`, is caught by [`UpFinderBuilder::build`].
+
+use std::{
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+use crate::MemoryFs;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use crate::StdFs;
+use crate::{
+  BindMountPolicy, CandidateHook, ConfigFallback, DirHook, FindUpKind, Fs, HookResult,
+  NetworkMountPolicy, OwnershipPolicy, ReparsePointPolicy, UpFinder, fs::SharedFs,
+};
+
+/// The builder returned by [`UpFinder::builder`].
+#[derive(Debug)]
+pub struct UpFinderBuilder<P> {
+  /// The settings so far, without a `cwd` until it is set.
+  finder: UpFinder<Option<P>>,
+  /// Whether `fs` was set, the default one can't search anything here.
+  #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+  has_fs: bool,
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Start building an [`UpFinder`].
+  pub fn builder() -> UpFinderBuilder<P> {
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let fs = Arc::new(StdFs);
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    let fs = Arc::new(MemoryFs::new());
+
+    UpFinderBuilder {
+      finder: UpFinder {
+        cwd: None,
+        kind: FindUpKind::File,
+        network_mounts: NetworkMountPolicy::default(),
+        reparse_points: ReparsePointPolicy::default(),
+        bind_mounts: BindMountPolicy::default(),
+        ownership: OwnershipPolicy::default(),
+        verbatim: false,
+        msys_paths: false,
+        normalize_unicode: false,
+        preserve_case: false,
+        dedupe_hard_links: false,
+        config_dir: false,
+        probe_dirs: vec![],
+        exclude_dependency_dirs: false,
+        restrict_to: None,
+        stop_at: None,
+        max_depth: None,
+        record_visits: false,
+        on_enter_dir: None,
+        on_candidate: None,
+        config_fallback: None,
+        env_override: None,
+        fs: SharedFs(fs),
+      },
+      #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+      has_fs: false,
+    }
+  }
+}
+
+/// Setters that store their argument as it is.
+macro_rules! setters {
+  ($($(#[$doc:meta])* $name:ident: $ty:ty,)*) => {
+    $(
+      $(#[$doc])*
+      pub fn $name(mut self, $name: $ty) -> Self {
+        self.finder.$name = $name;
+        self
+      }
+    )*
+  };
+}
+
+impl<P: AsRef<Path>> UpFinderBuilder<P> {
+  /// The current working directory. Required.
+  pub fn cwd(mut self, cwd: P) -> Self {
+    self.finder.cwd = Some(cwd);
+    self
+  }
+
+  setters! {
+    /// The kind of file to search for.
+    kind: FindUpKind,
+    /// How ancestors on network filesystems are probed.
+    network_mounts: NetworkMountPolicy,
+    /// What to do on reaching a junction, mount point or symlinked ancestor.
+    reparse_points: ReparsePointPolicy,
+    /// What to do on reaching a directory that was already searched under
+    /// another path.
+    bind_mounts: BindMountPolicy,
+    /// What to do on reaching an ancestor owned by another user.
+    ownership: OwnershipPolicy,
+    /// Report matches as extended-length `\\?\` paths on Windows.
+    verbatim: bool,
+    /// Accept MSYS2 and Git Bash style `/c/...` start directories on Windows.
+    msys_paths: bool,
+    /// Also match names stored in another Unicode normalization form.
+    normalize_unicode: bool,
+    /// Report matches with the name as it is stored.
+    preserve_case: bool,
+    /// Report files that are hard links to an earlier match once.
+    dedupe_hard_links: bool,
+    /// Also look for every name in the `.config` subdirectory of every level.
+    config_dir: bool,
+    /// Don't report matches inside `node_modules`, `target` and the other
+    /// [`DEPENDENCY_DIRS`](crate::DEPENDENCY_DIRS).
+    exclude_dependency_dirs: bool,
+    /// Record the directories and candidates of every search for its
+    /// [`SearchReport`](crate::SearchReport).
+    record_visits: bool,
+  }
+
+  /// More subdirectories to look for every name in on every level, relative
+  /// to the level, after `.config`.
+  pub fn probe_dirs(mut self, dirs: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+    self.finder.probe_dirs = dirs.into_iter().map(Into::into).collect();
+    self
+  }
+
+  /// Never search above this directory, and fail for start directories
+  /// outside of it.
+  pub fn restrict_to(mut self, restrict_to: impl Into<PathBuf>) -> Self {
+    self.finder.restrict_to = Some(restrict_to.into());
+    self
+  }
+
+  /// Like [`UpFinderBuilder::restrict_to`], `None` to allow any start
+  /// directory.
+  pub fn restrict_to_opt(mut self, restrict_to: Option<PathBuf>) -> Self {
+    self.finder.restrict_to = restrict_to;
+    self
+  }
+
+  /// The last directory to search.
+  pub fn stop_at(mut self, stop_at: impl Into<PathBuf>) -> Self {
+    self.finder.stop_at = Some(stop_at.into());
+    self
+  }
+
+  /// Like [`UpFinderBuilder::stop_at`], `None` to search up to the root.
+  pub fn stop_at_opt(mut self, stop_at: Option<PathBuf>) -> Self {
+    self.finder.stop_at = stop_at;
+    self
+  }
+
+  /// How many levels above the start directory to search at most.
+  pub fn max_depth(mut self, max_depth: usize) -> Self {
+    self.finder.max_depth = Some(max_depth);
+    self
+  }
+
+  /// Like [`UpFinderBuilder::max_depth`], `None` for no limit.
+  pub fn max_depth_opt(mut self, max_depth: Option<usize>) -> Self {
+    self.finder.max_depth = max_depth;
+    self
+  }
+
+  /// Called with every directory and its depth before searching it, see
+  /// [`HookResult`].
+  pub fn on_enter_dir(
+    mut self,
+    hook: impl Fn(&Path, usize) -> HookResult + Send + Sync + 'static,
+  ) -> Self {
+    self.finder.on_enter_dir = Some(DirHook(Arc::new(hook)));
+    self
+  }
+
+  /// Called with every candidate of the right kind before the matcher, see
+  /// [`HookResult`].
+  pub fn on_candidate(
+    mut self,
+    hook: impl Fn(&Path) -> HookResult + Send + Sync + 'static,
+  ) -> Self {
+    self.finder.on_candidate = Some(CandidateHook(Arc::new(hook)));
+    self
+  }
+
+  /// Where to look for names the walk didn't find.
+  pub fn config_fallback(mut self, config_fallback: ConfigFallback) -> Self {
+    self.finder.config_fallback = Some(config_fallback);
+    self
+  }
+
+  /// An environment variable naming the file to use instead of searching.
+  pub fn env_override(mut self, env_override: impl Into<String>) -> Self {
+    self.finder.env_override = Some(env_override.into());
+    self
+  }
+
+  /// Like [`UpFinderBuilder::env_override`], `None` to always search.
+  pub fn env_override_opt(mut self, env_override: Option<String>) -> Self {
+    self.finder.env_override = env_override;
+    self
+  }
+
+  /// The filesystem to search, the real one by default. Required on
+  /// `wasm32-unknown-unknown`, where there is no real filesystem.
+  pub fn fs(mut self, fs: Arc<dyn Fs>) -> Self {
+    self.finder.fs = SharedFs(fs);
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+      self.has_fs = true;
+    }
+    self
+  }
+
+  /// Build the [`UpFinder`].
+  ///
+  /// # Panics
+  ///
+  /// If `cwd` wasn't set, or `fs` wasn't set on `wasm32-unknown-unknown`.
+  pub fn build(self) -> UpFinder<P> {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    assert!(self.has_fs, "`fs` is required on wasm32-unknown-unknown");
+
+    let UpFinderBuilder { mut finder, .. } = self;
+    let cwd = finder.cwd.take().expect("`cwd` is required");
+
+    finder.with_cwd(cwd)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::MemoryFs;
+
+  #[test]
+  fn should_build_with_the_defaults() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/.nvmrc", "")
+      .with_dir("/repo/app");
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/app")
+      .fs(Arc::new(fs))
+      .max_depth_opt(Some(1))
+      .build();
+
+    assert_eq!(up_finder.kind, FindUpKind::File);
+    assert_eq!(up_finder.stop_at, None);
+    assert_eq!(up_finder.find_up(".nvmrc"), [PathBuf::from("/repo/.nvmrc")]);
+  }
+
+  #[test]
+  fn should_compare_searches_sharing_the_filesystem() {
+    let fs: Arc<dyn Fs> = Arc::new(MemoryFs::new());
+    let build = |fs: &Arc<dyn Fs>| {
+      UpFinder::builder()
+        .cwd("/repo")
+        .max_depth(2)
+        .fs(Arc::clone(fs))
+        .build()
+    };
+
+    assert_eq!(build(&fs), build(&fs));
+    assert_ne!(
+      build(&fs),
+      build(&(Arc::new(MemoryFs::new()) as Arc<dyn Fs>))
+    );
+    assert_ne!(
+      build(&fs),
+      UpFinder {
+        max_depth: None,
+        ..build(&fs)
+      }
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "`cwd` is required")]
+  fn should_panic_without_cwd() {
+    UpFinder::<&str>::builder().build();
+  }
+}
@@ -0,0 +1,271 @@
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+
+use crate::{ConfigFormat, FindUpError, UpFinder, parse::parse};
+
+/// The order in which [`UpFinder::cascade`] merges the configs it finds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CascadeOrder {
+  /// Start with the config nearest to the root and merge the ones below it on
+  /// top, so nearer configs are merged last.
+  #[default]
+  RootToLeaf,
+  /// Start with the nearest config and merge the ones above it on top.
+  LeafToRoot,
+}
+
+/// How [`UpFinder::cascade`] combines two configs, implemented by closures
+/// taking the merged config so far and the next one.
+pub trait MergeStrategy<T> {
+  fn merge(&self, merged: T, next: T) -> T;
+}
+
+impl<T, F> MergeStrategy<T> for F
+where
+  F: Fn(T, T) -> T,
+{
+  fn merge(&self, merged: T, next: T) -> T {
+    self(merged, next)
+  }
+}
+
+/// Merges maps recursively, values of the next config replace everything else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DeepMerge;
+
+#[cfg(feature = "json")]
+impl MergeStrategy<serde_json::Value> for DeepMerge {
+  fn merge(&self, merged: serde_json::Value, next: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match (merged, next) {
+      (Value::Object(mut merged), Value::Object(next)) => {
+        for (key, value) in next {
+          let value = match merged.remove(&key) {
+            Some(existing) => self.merge(existing, value),
+            None => value,
+          };
+          merged.insert(key, value);
+        }
+        Value::Object(merged)
+      }
+      (_, next) => next,
+    }
+  }
+}
+
+#[cfg(feature = "toml")]
+impl MergeStrategy<::toml::Table> for DeepMerge {
+  fn merge(&self, mut merged: ::toml::Table, next: ::toml::Table) -> ::toml::Table {
+    use ::toml::Value;
+
+    for (key, value) in next {
+      let value = match (merged.remove(&key), value) {
+        (Some(Value::Table(existing)), Value::Table(value)) => {
+          Value::Table(self.merge(existing, value))
+        }
+        (_, value) => value,
+      };
+      merged.insert(key, value);
+    }
+
+    merged
+  }
+}
+
+/// The effective config produced by [`UpFinder::cascade`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cascade<T> {
+  config: T,
+  paths: Vec<PathBuf>,
+}
+
+impl<T> Cascade<T> {
+  /// The merged config.
+  pub fn config(&self) -> &T {
+    &self.config
+  }
+
+  /// The files that were merged, in merge order.
+  pub fn paths(&self) -> &[PathBuf] {
+    &self.paths
+  }
+
+  pub fn into_parts(self) -> (T, Vec<PathBuf>) {
+    (self.config, self.paths)
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find every `name`, deserialize them and merge them in `order` with
+  /// `strategy`, choosing the format by extension, see
+  /// [`ConfigFormat::from_extension`]. `None` if there are no matches.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::sync::Arc;
+  ///
+  /// use up_finder::{CascadeOrder, MemoryFs, UpFinder};
+  ///
+  /// let fs = MemoryFs::new()
+  ///   .with_file("/.eslintrc.json", r#"{ "semi": true, "quotes": "double" }"#)
+  ///   .with_file("/app/.eslintrc.json", r#"{ "quotes": "single" }"#)
+  ///   .with_dir("/app/src");
+  ///
+  /// let find_up = UpFinder::builder()
+  ///   .cwd("/app/src")
+  ///   .fs(Arc::new(fs))
+  ///   .build();
+  ///
+  /// # #[cfg(feature = "json")]
+  /// # {
+  /// use std::collections::BTreeMap;
+  ///
+  /// // Nearer configs are merged last, so they win.
+  /// let cascade = find_up
+  ///   .cascade(
+  ///     ".eslintrc.json",
+  ///     CascadeOrder::RootToLeaf,
+  ///     |mut merged: BTreeMap<String, serde_json::Value>, next| {
+  ///       merged.extend(next);
+  ///       merged
+  ///     },
+  ///   )
+  ///   .unwrap()
+  ///   .unwrap();
+  ///
+  /// assert_eq!(cascade.config()["quotes"], "single");
+  /// assert_eq!(cascade.paths().len(), 2);
+  /// # }
+  /// ```
+  pub fn cascade<T, M>(
+    &self,
+    name: &str,
+    order: CascadeOrder,
+    strategy: M,
+  ) -> Result<Option<Cascade<T>>, FindUpError>
+  where
+    T: DeserializeOwned,
+    M: MergeStrategy<T>,
+  {
+    let format = ConfigFormat::from_extension(Path::new(name))
+      .ok_or_else(|| FindUpError::UnknownFormat(name.to_owned()))?;
+
+    self.cascade_as(name, format, order, strategy)
+  }
+
+  /// Like [`UpFinder::cascade`], deserializing every match as `format`.
+  pub fn cascade_as<T, M>(
+    &self,
+    name: &str,
+    format: ConfigFormat,
+    order: CascadeOrder,
+    strategy: M,
+  ) -> Result<Option<Cascade<T>>, FindUpError>
+  where
+    T: DeserializeOwned,
+    M: MergeStrategy<T>,
+  {
+    let mut paths = self.try_find_up(name)?;
+
+    if order == CascadeOrder::RootToLeaf {
+      paths.reverse();
+    }
+
+    let mut merged = None;
+
+    for path in &paths {
+      let config = parse(&*self.fs, path, format)?;
+
+      merged = Some(match merged {
+        Some(merged) => strategy.merge(merged, config),
+        None => config,
+      });
+    }
+
+    Ok(merged.map(|config| Cascade { config, paths }))
+  }
+}
+
+#[cfg(all(test, any(feature = "json", feature = "toml")))]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn should_deep_merge_json_configs() {
+    use serde_json::{Value, json};
+
+    let fs = MemoryFs::new()
+      .with_file(
+        "/tsconfig.json",
+        r#"{ "compilerOptions": { "strict": true, "target": "es2020" } }"#,
+      )
+      .with_file(
+        "/app/tsconfig.json",
+        r#"{ "compilerOptions": { "target": "es2024" } }"#,
+      )
+      .with_dir("/app/src");
+
+    let up_finder = UpFinder::builder().cwd("/app/src").fs(Arc::new(fs)).build();
+
+    let root_to_leaf = up_finder
+      .cascade::<Value, _>("tsconfig.json", CascadeOrder::RootToLeaf, DeepMerge)
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(
+      root_to_leaf.config(),
+      &json!({ "compilerOptions": { "strict": true, "target": "es2024" } })
+    );
+    assert_eq!(
+      root_to_leaf.paths(),
+      [
+        PathBuf::from("/tsconfig.json"),
+        PathBuf::from("/app/tsconfig.json")
+      ]
+    );
+
+    let leaf_to_root = up_finder
+      .cascade::<Value, _>("tsconfig.json", CascadeOrder::LeafToRoot, DeepMerge)
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(leaf_to_root.config()["compilerOptions"]["target"], "es2020");
+    assert!(
+      up_finder
+        .cascade::<Value, _>("missing.json", CascadeOrder::RootToLeaf, DeepMerge)
+        .unwrap()
+        .is_none()
+    );
+  }
+
+  #[cfg(feature = "toml")]
+  #[test]
+  fn should_deep_merge_toml_configs() {
+    let fs = MemoryFs::new()
+      .with_file(
+        "/rustfmt.toml",
+        "edition = \"2021\"\n[format]\nwidth = 100\ntabs = false",
+      )
+      .with_file("/app/rustfmt.toml", "[format]\nwidth = 80")
+      .with_dir("/app/src");
+
+    let up_finder = UpFinder::builder().cwd("/app/src").fs(Arc::new(fs)).build();
+
+    let (config, _) = up_finder
+      .cascade::<::toml::Table, _>("rustfmt.toml", CascadeOrder::RootToLeaf, DeepMerge)
+      .unwrap()
+      .unwrap()
+      .into_parts();
+
+    assert_eq!(config["edition"].as_str(), Some("2021"));
+    assert_eq!(config["format"]["width"].as_integer(), Some(80));
+    assert_eq!(config["format"]["tabs"].as_bool(), Some(false));
+  }
+}
@@ -0,0 +1,47 @@
+use std::ffi::OsString;
+
+/// The entry among `entries` that is `name` with different casing, for
+/// reporting a match on a case-insensitive filesystem the way it is stored.
+pub(crate) fn find_case_insensitive(entries: &[OsString], name: &str) -> Option<OsString> {
+  // An exact entry wins over entries that only differ in casing, which can
+  // both exist in case-sensitive directories on otherwise insensitive volumes.
+  if entries.iter().any(|entry| entry == name) {
+    return None;
+  }
+
+  entries
+    .iter()
+    .find(|entry| {
+      entry
+        .to_str()
+        .is_some_and(|entry| eq_ignore_case(entry, name))
+    })
+    .cloned()
+}
+
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+  a.chars()
+    .flat_map(char::to_lowercase)
+    .eq(b.chars().flat_map(char::to_lowercase))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_find_names_with_other_casing() {
+    let entries = vec![OsString::from("Package.JSON"), OsString::from("ÄRGER.md")];
+
+    assert_eq!(
+      find_case_insensitive(&entries, "package.json"),
+      Some(OsString::from("Package.JSON"))
+    );
+    assert_eq!(
+      find_case_insensitive(&entries, "ärger.md"),
+      Some(OsString::from("ÄRGER.md"))
+    );
+    assert_eq!(find_case_insensitive(&entries, "Package.JSON"), None);
+    assert_eq!(find_case_insensitive(&entries, "cargo.toml"), None);
+  }
+}
@@ -0,0 +1,343 @@
+//! Searches with the options and semantics of the npm
+//! [`find-up`](https://github.com/sindresorhus/find-up) package, to ease
+//! porting JavaScript tools.
+//!
+//! # Example
+//!
+//! ```rust
+//! use up_finder::compat::{self, Options, Type};
+//!
+//! // findUp(['package.json', 'Cargo.toml'], { cwd: 'src', type: 'file' })
+//! let options = Options::builder().cwd("src").kind(Type::File).build();
+//! let path = compat::find_up(&["package.json", "Cargo.toml"], &options);
+//!
+//! assert!(path.unwrap().ends_with("Cargo.toml"));
+//! ```
+use std::{
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+use crate::MemoryFs;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use crate::StdFs;
+use crate::{Fs, ReparsePoint, restrict::clean};
+
+/// The `type` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Type {
+  /// `'file'`
+  #[default]
+  File,
+  /// `'directory'`
+  Directory,
+}
+
+/// What a matcher function returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatcherResult {
+  /// A path, relative to the directory, that is a match if it exists and is of
+  /// the right [`Type`].
+  Path(PathBuf),
+  /// `findUpStop`: end the search without a match on this level.
+  Stop,
+  /// `undefined`: go on to the parent directory.
+  NotFound,
+}
+
+/// The options of `findUp`.
+#[derive(Debug)]
+pub struct Options {
+  /// The directory to start from, resolved against the current directory.
+  cwd: PathBuf,
+  /// The `type` option.
+  kind: Type,
+  /// Whether symlinks count as matches.
+  allow_symlinks: bool,
+  /// The last directory to search, resolved against `cwd`. The root by
+  /// default.
+  stop_at: Option<PathBuf>,
+  /// The filesystem to search, the real one by default. Required on
+  /// `wasm32-unknown-unknown`, where there is no real filesystem.
+  fs: Arc<dyn Fs>,
+}
+
+/// The builder returned by [`Options::builder`]. A missing `fs` on
+/// `wasm32-unknown-unknown` is caught by [`OptionsBuilder::build`].
+#[derive(Debug)]
+pub struct OptionsBuilder {
+  options: Options,
+  /// Whether `fs` was set, the default one can't search anything here.
+  #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+  has_fs: bool,
+}
+
+impl Options {
+  /// Start building [`Options`].
+  pub fn builder() -> OptionsBuilder {
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let fs = Arc::new(StdFs);
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    let fs = Arc::new(MemoryFs::new());
+
+    OptionsBuilder {
+      options: Options {
+        cwd: PathBuf::from("."),
+        kind: Type::default(),
+        allow_symlinks: true,
+        stop_at: None,
+        fs,
+      },
+      #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+      has_fs: false,
+    }
+  }
+}
+
+impl OptionsBuilder {
+  /// The directory to start from, resolved against the current directory.
+  pub fn cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+    self.options.cwd = cwd.into();
+    self
+  }
+
+  /// The `type` option.
+  pub fn kind(mut self, kind: Type) -> Self {
+    self.options.kind = kind;
+    self
+  }
+
+  /// Whether symlinks count as matches.
+  pub fn allow_symlinks(mut self, allow_symlinks: bool) -> Self {
+    self.options.allow_symlinks = allow_symlinks;
+    self
+  }
+
+  /// The last directory to search, resolved against `cwd`.
+  pub fn stop_at(mut self, stop_at: impl Into<PathBuf>) -> Self {
+    self.options.stop_at = Some(stop_at.into());
+    self
+  }
+
+  /// The filesystem to search, the real one by default.
+  pub fn fs(mut self, fs: Arc<dyn Fs>) -> Self {
+    self.options.fs = fs;
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+      self.has_fs = true;
+    }
+    self
+  }
+
+  /// Build the [`Options`].
+  ///
+  /// # Panics
+  ///
+  /// If `fs` wasn't set on `wasm32-unknown-unknown`.
+  pub fn build(self) -> Options {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    assert!(self.has_fs, "`fs` is required on wasm32-unknown-unknown");
+
+    self.options
+  }
+}
+
+/// `findUp(names, options)`: the first of `names` found in the nearest
+/// directory containing any of them.
+pub fn find_up(names: &[&str], options: &Options) -> Option<PathBuf> {
+  walk(names_matcher(names), options, Some(1)).pop()
+}
+
+/// `findUpMultiple(names, options)`: the first of `names` in every directory
+/// containing any of them, nearest first.
+pub fn find_up_multiple(names: &[&str], options: &Options) -> Vec<PathBuf> {
+  walk(names_matcher(names), options, None)
+}
+
+/// `findUp(matcher, options)`: `matcher` is called with every directory, and
+/// the first path it returns that exists is the result.
+pub fn find_up_with<F>(matcher: F, options: &Options) -> Option<PathBuf>
+where
+  F: FnMut(&Path) -> MatcherResult,
+{
+  walk(matcher_candidates(matcher), options, Some(1)).pop()
+}
+
+/// `findUpMultiple(matcher, options)`, see [`find_up_with`].
+pub fn find_up_multiple_with<F>(matcher: F, options: &Options) -> Vec<PathBuf>
+where
+  F: FnMut(&Path) -> MatcherResult,
+{
+  walk(matcher_candidates(matcher), options, None)
+}
+
+/// The candidates of a directory, `None` to stop.
+type Candidates<'a> = Box<dyn FnMut(&Path) -> Option<Vec<PathBuf>> + 'a>;
+
+fn names_matcher<'a>(names: &'a [&str]) -> Candidates<'a> {
+  Box::new(|_| Some(names.iter().map(PathBuf::from).collect()))
+}
+
+fn matcher_candidates<'a, F>(mut matcher: F) -> Candidates<'a>
+where
+  F: FnMut(&Path) -> MatcherResult + 'a,
+{
+  Box::new(move |dir| match matcher(dir) {
+    MatcherResult::Path(path) => Some(vec![path]),
+    MatcherResult::Stop => None,
+    MatcherResult::NotFound => Some(vec![]),
+  })
+}
+
+fn walk(mut candidates: Candidates, options: &Options, limit: Option<usize>) -> Vec<PathBuf> {
+  let mut dir = resolve(&options.cwd);
+  let stop_at = options
+    .stop_at
+    .as_ref()
+    .map(|stop_at| clean(&dir.join(stop_at)));
+
+  let mut matches = vec![];
+
+  loop {
+    let Some(candidates) = candidates(&dir) else {
+      break;
+    };
+
+    if let Some(found) = candidates
+      .into_iter()
+      .map(|candidate| dir.join(candidate))
+      .find(|path| is_match(path, options))
+    {
+      matches.push(clean(&found));
+    }
+
+    if stop_at.as_ref() == Some(&dir) || limit.is_some_and(|limit| matches.len() >= limit) {
+      break;
+    }
+
+    match dir.parent() {
+      Some(parent) => dir = parent.to_path_buf(),
+      None => break,
+    }
+  }
+
+  matches
+}
+
+/// `path.resolve(cwd)`.
+fn resolve(cwd: &Path) -> PathBuf {
+  clean(&std::path::absolute(cwd).unwrap_or_else(|_| cwd.to_path_buf()))
+}
+
+/// What `locate-path` checks for every candidate.
+fn is_match(path: &Path, options: &Options) -> bool {
+  if !options.allow_symlinks
+    && options
+      .fs
+      .reparse_point(path)
+      .is_ok_and(|reparse_point| reparse_point == Some(ReparsePoint::Symlink))
+  {
+    return false;
+  }
+
+  options
+    .fs
+    .metadata(path)
+    .is_ok_and(|metadata| match options.kind {
+      Type::File => metadata.is_file(),
+      Type::Directory => metadata.is_dir(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::MemoryFs;
+
+  fn fs() -> Arc<MemoryFs> {
+    Arc::new(
+      MemoryFs::new()
+        .with_file("/package.json", "{}")
+        .with_file("/repo/package.json", "{}")
+        .with_file("/repo/.npmrc", "")
+        .with_dir("/repo/app/src"),
+    )
+  }
+
+  #[test]
+  fn should_find_the_first_name_of_the_nearest_directory() {
+    let options = Options::builder().cwd("/repo/app/src").fs(fs()).build();
+
+    assert_eq!(
+      find_up(&[".npmrc", "package.json"], &options),
+      Some(PathBuf::from("/repo/.npmrc"))
+    );
+    assert_eq!(
+      find_up_multiple(&["package.json"], &options),
+      vec![
+        PathBuf::from("/repo/package.json"),
+        PathBuf::from("/package.json")
+      ]
+    );
+  }
+
+  #[test]
+  fn should_stop_at_the_stop_at_directory_inclusively() {
+    let options = Options::builder()
+      .cwd("/repo/app/src")
+      .stop_at("../..")
+      .fs(fs())
+      .build();
+
+    assert_eq!(
+      find_up_multiple(&["package.json"], &options),
+      vec![PathBuf::from("/repo/package.json")]
+    );
+  }
+
+  #[test]
+  fn should_honor_the_type_and_matcher_results() {
+    let options = Options::builder()
+      .cwd("/repo/app/src")
+      .kind(Type::Directory)
+      .fs(fs())
+      .build();
+
+    assert_eq!(
+      find_up(&["app"], &options),
+      Some(PathBuf::from("/repo/app"))
+    );
+    assert_eq!(find_up(&["package.json"], &options), None);
+
+    let mut visited = vec![];
+    let found = find_up_with(
+      |dir| {
+        visited.push(dir.to_path_buf());
+        if dir == Path::new("/repo") {
+          MatcherResult::Stop
+        } else {
+          MatcherResult::Path("src".into())
+        }
+      },
+      &options,
+    );
+
+    assert_eq!(found, Some(PathBuf::from("/repo/app/src")));
+    assert_eq!(
+      find_up_multiple_with(
+        |dir| if dir == Path::new("/repo") {
+          MatcherResult::Stop
+        } else {
+          MatcherResult::NotFound
+        },
+        &options,
+      ),
+      Vec::<PathBuf>::new()
+    );
+    assert_eq!(
+      visited,
+      vec![PathBuf::from("/repo/app/src"), PathBuf::from("/repo/app")]
+    );
+  }
+}
@@ -0,0 +1,217 @@
+use std::path::{Path, PathBuf};
+
+use crate::{FindUpResult, UpFinder};
+
+/// The extensions of `.{name}rc` files, after the bare one.
+const RC_EXTENSIONS: &[&str] = &["json", "yaml", "yml", "js", "ts", "mjs", "cjs"];
+
+/// The extensions of `{name}.config` files.
+const CONFIG_EXTENSIONS: &[&str] = &["js", "ts", "mjs", "cjs"];
+
+/// Finds the configuration of a tool in the places
+/// [cosmiconfig](https://github.com/cosmiconfig/cosmiconfig) searches, checking
+/// every place of a level before going on to its parent.
+///
+/// The default places, in order, are a `{name}` property in `package.json`
+/// (with the `json` feature), `.{name}rc` without and with the extensions
+/// `json`, `yaml`, `yml`, `js`, `ts`, `mjs` and `cjs`, the same files without
+/// the leading dot in a `.config` subdirectory, e.g. `.config/{name}rc.json`,
+/// and `{name}.config.{js,ts,mjs,cjs}`. Files are only located, not loaded.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{ConfigFinder, UpFinder};
+///
+/// let find_up = UpFinder::builder().cwd(".").build();
+///
+/// if let Some(path) = ConfigFinder::new("prettier").find(&find_up) {
+///   println!("prettier is configured in {}", path.display());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigFinder {
+  name: String,
+  places: Vec<String>,
+}
+
+impl ConfigFinder {
+  /// Search the default places for the tool `name`.
+  pub fn new(name: impl Into<String>) -> Self {
+    let name = name.into();
+
+    let mut places = vec![];
+
+    if cfg!(feature = "json") {
+      places.push("package.json".to_owned());
+    }
+
+    // Inside `.config` the files drop the leading dot.
+    for prefix in [".", ".config/"] {
+      places.push(format!("{prefix}{name}rc"));
+      places.extend(
+        RC_EXTENSIONS
+          .iter()
+          .map(|extension| format!("{prefix}{name}rc.{extension}")),
+      );
+    }
+
+    places.extend(
+      CONFIG_EXTENSIONS
+        .iter()
+        .map(|extension| format!("{name}.config.{extension}")),
+    );
+
+    Self { name, places }
+  }
+
+  /// Search the given places for the tool `name`, relative to every level and
+  /// in order of preference. A `package.json` place only matches if it has a
+  /// property named after the tool, which needs the `json` feature.
+  pub fn with_places<I, S>(name: impl Into<String>, places: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
+    Self {
+      name: name.into(),
+      places: places.into_iter().map(Into::into).collect(),
+    }
+  }
+
+  /// The places searched at every level, in order of preference.
+  pub fn places(&self) -> &[String] {
+    &self.places
+  }
+
+  /// The nearest configuration, preferring earlier places on the same level.
+  pub fn find<P: AsRef<Path>>(&self, up_finder: &UpFinder<P>) -> Option<PathBuf> {
+    let places: Vec<&str> = self.places.iter().map(String::as_str).collect();
+
+    let mut matches = up_finder
+      .find_up_with_impl(
+        up_finder.cwd.as_ref().to_path_buf(),
+        &places,
+        |path| {
+          if path.file_name().is_some_and(|name| name == "package.json")
+            && !self.has_property(up_finder, &path)
+          {
+            return FindUpResult::Continue;
+          }
+          FindUpResult::Saved(path)
+        },
+        false,
+      )
+      .ok()?;
+
+    places
+      .iter()
+      .filter_map(|place| {
+        let found = matches.remove(place)?.into_iter().next()?;
+        Some((found.depth(), found.into_path()))
+      })
+      // `min_by_key` keeps the first of equally near places.
+      .min_by_key(|(depth, _)| *depth)
+      .map(|(_, path)| path)
+  }
+
+  /// The paths [`ConfigFinder::find`] would look up, in order, see
+  /// [`UpFinder::plan`]. `package.json` places are listed like any other.
+  pub fn plan<P: AsRef<Path>>(&self, up_finder: &UpFinder<P>) -> Vec<PathBuf> {
+    let places: Vec<&str> = self.places.iter().map(String::as_str).collect();
+
+    up_finder.plan_multi(&places)
+  }
+
+  #[cfg(feature = "json")]
+  fn has_property<P: AsRef<Path>>(&self, up_finder: &UpFinder<P>, path: &Path) -> bool {
+    up_finder
+      .fs
+      .read(path)
+      .is_ok_and(|contents| crate::JsonMatcher::has_key(self.name.as_str()).matches(&contents))
+  }
+
+  #[cfg(not(feature = "json"))]
+  fn has_property<P: AsRef<Path>>(&self, _up_finder: &UpFinder<P>, _path: &Path) -> bool {
+    false
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  fn find(fs: MemoryFs) -> Option<PathBuf> {
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/app/src")
+      .fs(Arc::new(fs.with_dir("/repo/app/src")))
+      .build();
+
+    ConfigFinder::new("prettier").find(&up_finder)
+  }
+
+  #[test]
+  fn should_prefer_nearer_levels_over_earlier_places() {
+    let found = find(
+      MemoryFs::new()
+        .with_file("/repo/.prettierrc", "")
+        .with_file("/repo/app/prettier.config.js", ""),
+    );
+
+    assert_eq!(found, Some(PathBuf::from("/repo/app/prettier.config.js")));
+  }
+
+  #[test]
+  fn should_prefer_earlier_places_on_the_same_level() {
+    let found = find(
+      MemoryFs::new()
+        .with_file("/repo/app/prettier.config.js", "")
+        .with_file("/repo/app/.config/prettierrc.yaml", "")
+        .with_file("/repo/app/.prettierrc.json", ""),
+    );
+
+    assert_eq!(found, Some(PathBuf::from("/repo/app/.prettierrc.json")));
+  }
+
+  #[test]
+  fn should_find_rc_files_without_the_dot_in_the_config_dir() {
+    let found = find(
+      MemoryFs::new()
+        .with_file("/repo/app/.config/.prettierrc.json", "")
+        .with_file("/repo/.config/prettierrc.json", ""),
+    );
+
+    assert_eq!(found, Some(PathBuf::from("/repo/.config/prettierrc.json")));
+  }
+
+  #[test]
+  fn should_plan_every_place_on_every_level() {
+    let up_finder = UpFinder::builder().cwd("/repo/app").max_depth(1).build();
+
+    assert_eq!(
+      ConfigFinder::with_places("tool", [".toolrc", ".config/toolrc"]).plan(&up_finder),
+      [
+        PathBuf::from("/repo/app/.toolrc"),
+        PathBuf::from("/repo/app/.config/toolrc"),
+        PathBuf::from("/repo/.toolrc"),
+        PathBuf::from("/repo/.config/toolrc"),
+      ]
+    );
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn should_only_use_package_json_files_with_the_property() {
+    let found = find(
+      MemoryFs::new()
+        .with_file("/repo/app/package.json", r#"{ "name": "app" }"#)
+        .with_file("/repo/package.json", r#"{ "prettier": { "semi": false } }"#)
+        .with_file("/.prettierrc", ""),
+    );
+
+    assert_eq!(found, Some(PathBuf::from("/repo/package.json")));
+  }
+}
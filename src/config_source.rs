@@ -0,0 +1,163 @@
+use std::{io, path::PathBuf};
+
+use config::{ConfigError, File, Map, Source, Value};
+
+use crate::{FindUpResult, StartFrom, UpFinder};
+
+/// A [`Source`] of the `config` crate reading the nearest file named `name`
+/// in the current directory or one of its parents, with the `config` feature.
+///
+/// The format follows the extension of the name, as for [`config::File`]:
+/// TOML, and JSON and YAML with the `json` and `yaml` features.
+/// Like a [`config::File`], the source is required unless
+/// [`required(false)`](FindUpSource::required) is set, then it adds nothing
+/// when no file is found.
+///
+/// # Example
+///
+/// ```rust
+/// use config::Config;
+/// use up_finder::FindUpSource;
+///
+/// let settings = Config::builder()
+///   .add_source(FindUpSource::new("app.toml").required(false))
+///   .build()
+///   .unwrap();
+///
+/// println!("{:#?}", settings);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FindUpSource {
+  name: String,
+  cwd: Option<PathBuf>,
+  stop_at: Option<PathBuf>,
+  max_depth: Option<usize>,
+  required: bool,
+}
+
+impl FindUpSource {
+  /// A required source reading the nearest `name` above the current
+  /// directory.
+  pub fn new(name: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      cwd: None,
+      stop_at: None,
+      max_depth: None,
+      required: true,
+    }
+  }
+
+  /// Start the search at `cwd` instead of the current directory.
+  pub fn with_cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+    self.cwd = Some(cwd.into());
+    self
+  }
+
+  /// End the search at `stop_at`, see
+  /// [`stop_at`](crate::UpFinderBuilder::stop_at).
+  pub fn with_stop_at(mut self, stop_at: impl Into<PathBuf>) -> Self {
+    self.stop_at = Some(stop_at.into());
+    self
+  }
+
+  /// Search at most `max_depth` levels above the start directory, see
+  /// [`max_depth`](crate::UpFinderBuilder::max_depth).
+  pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+    self.max_depth = Some(max_depth);
+    self
+  }
+
+  /// Whether building the configuration fails when no file is found.
+  pub fn required(mut self, required: bool) -> Self {
+    self.required = required;
+    self
+  }
+
+  /// The file the source reads, `None` if there is none.
+  pub fn path(&self) -> io::Result<Option<PathBuf>> {
+    let up_finder = UpFinder::builder()
+      .cwd(self.start()?)
+      .stop_at_opt(self.stop_at.clone())
+      .max_depth_opt(self.max_depth)
+      .build();
+
+    Ok(
+      up_finder
+        .find_up_with(&self.name, FindUpResult::Last)
+        .into_iter()
+        .next(),
+    )
+  }
+
+  fn start(&self) -> io::Result<PathBuf> {
+    match &self.cwd {
+      Some(cwd) => Ok(cwd.clone()),
+      None => StartFrom::CurrentDir.dir(),
+    }
+  }
+}
+
+impl Source for FindUpSource {
+  fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+    Box::new(self.clone())
+  }
+
+  fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+    let to_config_error = |error: io::Error| ConfigError::Foreign(Box::new(error));
+
+    match self.path().map_err(to_config_error)? {
+      Some(path) => File::from(path).collect(),
+      None if self.required => Err(ConfigError::Message(format!(
+        "no `{}` in {} or above",
+        self.name,
+        self.start().map_err(to_config_error)?.display()
+      ))),
+      None => Ok(Map::new()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use config::Config;
+
+  use super::*;
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn should_read_the_nearest_file() {
+    let settings = Config::builder()
+      .add_source(FindUpSource::new("package.json").with_cwd("fixtures/a/b/c/d"))
+      .build()
+      .unwrap();
+    assert_eq!(settings.get_string("name").unwrap(), "d");
+
+    let settings = Config::builder()
+      .add_source(FindUpSource::new("app.json").required(false))
+      .build()
+      .unwrap();
+    assert!(settings.get_string("name").is_err());
+
+    let error = Config::builder()
+      .add_source(
+        FindUpSource::new("app.json")
+          .with_cwd("fixtures/a/b")
+          .with_max_depth(1),
+      )
+      .build()
+      .unwrap_err();
+    assert_eq!(error.to_string(), "no `app.json` in fixtures/a/b or above");
+  }
+
+  #[test]
+  fn should_read_toml_files() {
+    let settings = Config::builder()
+      .add_source(FindUpSource::new("app.toml").with_cwd("fixtures/a/b/c/d"))
+      .build()
+      .unwrap();
+
+    assert_eq!(settings.get_string("name").unwrap(), "b");
+    assert_eq!(settings.get_int("server.port").unwrap(), 8080);
+  }
+}
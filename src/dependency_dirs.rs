@@ -0,0 +1,27 @@
+use std::path::{Component, Path};
+
+/// The directory names skipped with
+/// [`exclude_dependency_dirs`](crate::UpFinderBuilder::exclude_dependency_dirs):
+/// installed dependencies, virtual environments and build outputs, which hold
+/// vendored copies of configuration files that don't apply to the project.
+pub const DEPENDENCY_DIRS: &[&str] = &[
+  "node_modules",
+  "bower_components",
+  "jspm_packages",
+  "target",
+  ".venv",
+  "venv",
+  "__pycache__",
+  ".tox",
+  "vendor",
+];
+
+/// Whether `dir` is or is inside one of the [`DEPENDENCY_DIRS`].
+pub(crate) fn is_in_dependency_dir(dir: &Path) -> bool {
+  dir.components().any(|component| match component {
+    Component::Normal(name) => DEPENDENCY_DIRS
+      .iter()
+      .any(|dependency_dir| name == *dependency_dir),
+    _ => false,
+  })
+}
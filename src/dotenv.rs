@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+
+use crate::{FindUpResult, UpFinder};
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the dotenv files that apply to the current working directory, in the
+  /// order they should be loaded so later files override earlier ones.
+  ///
+  /// Every level can have `.env`, `.env.local` and, for a `mode` such as
+  /// `development`, `.env.<mode>` and `.env.<mode>.local`, loaded in that
+  /// order. Levels nearer to the current working directory are loaded after,
+  /// and so override, the levels above them.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// for path in find_up.find_up_dotenv(Some("development")) {
+  ///   println!("loading {}", path.display());
+  /// }
+  /// ```
+  pub fn find_up_dotenv(&self, mode: Option<&str>) -> Vec<PathBuf> {
+    let mut names = vec![".env".to_owned(), ".env.local".to_owned()];
+
+    if let Some(mode) = mode {
+      names.push(format!(".env.{mode}"));
+      names.push(format!(".env.{mode}.local"));
+    }
+
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+    let Ok(mut matches) = self.find_up_with_impl(
+      self.cwd.as_ref().to_path_buf(),
+      &names,
+      FindUpResult::Saved,
+      false,
+    ) else {
+      return vec![];
+    };
+
+    let mut files: Vec<_> = names
+      .iter()
+      .enumerate()
+      .flat_map(|(order, name)| {
+        matches
+          .remove(name)
+          .unwrap_or_default()
+          .into_iter()
+          .map(move |found| (found.depth(), order, found.into_path()))
+      })
+      .collect();
+
+    // Farthest level first, then the order within a level.
+    files.sort_by(|(a_depth, a_order, _), (b_depth, b_order, _)| {
+      b_depth.cmp(a_depth).then(a_order.cmp(b_order))
+    });
+
+    files.into_iter().map(|(_, _, path)| path).collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  #[test]
+  fn should_return_dotenv_files_in_load_order() {
+    let fs = MemoryFs::new()
+      .with_file("/.env", "")
+      .with_file("/.env.production", "")
+      .with_file("/app/.env.development.local", "")
+      .with_file("/app/.env.local", "")
+      .with_file("/app/.env.development", "")
+      .with_file("/app/.env", "")
+      .with_dir("/app/src");
+
+    let up_finder = UpFinder::builder().cwd("/app/src").fs(Arc::new(fs)).build();
+
+    assert_eq!(
+      up_finder.find_up_dotenv(Some("development")),
+      vec![
+        PathBuf::from("/.env"),
+        PathBuf::from("/app/.env"),
+        PathBuf::from("/app/.env.local"),
+        PathBuf::from("/app/.env.development"),
+        PathBuf::from("/app/.env.development.local"),
+      ]
+    );
+    assert_eq!(
+      up_finder.find_up_dotenv(None),
+      vec![
+        PathBuf::from("/.env"),
+        PathBuf::from("/app/.env"),
+        PathBuf::from("/app/.env.local"),
+      ]
+    );
+  }
+}
@@ -0,0 +1,254 @@
+use std::{
+  collections::VecDeque,
+  path::{Path, PathBuf},
+};
+
+use crate::{FindUpResult, UpFinder, hash::HashSet, is_in_dependency_dir};
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find a file in the current working directory and its subdirectories, at
+  /// most `max_depth` levels below it, nearest first, `0` for only the current
+  /// working directory itself.
+  ///
+  /// The subdirectories of a level are searched in the order of their names.
+  /// `exclude_dependency_dirs` applies, and then dependency directories
+  /// aren't entered at all. Of the other settings of the upward walk only
+  /// `kind` and `restrict_to` apply. Directories reached twice, e.g. through
+  /// a symlink, are only searched once if the [`Fs`](crate::Fs) can tell.
+  ///
+  /// Nothing is found if the current working directory is outside of
+  /// `restrict_to`, and below it symlinks and other reparse points are neither
+  /// matched nor entered.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder()
+  ///   .cwd(".")
+  ///   .exclude_dependency_dirs(true)
+  ///   .build();
+  ///
+  /// // The packages of a workspace.
+  /// let paths = find_up.find_down("package.json", 3);
+  ///
+  /// println!("{:#?}", paths);
+  /// ```
+  pub fn find_down(&self, name: &str, max_depth: usize) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    let Ok((cwd, root)) = self.restricted_cwd() else {
+      return paths;
+    };
+    // Below a root, reparse points could lead out of it.
+    let escapes = |path: &Path| root.is_some() && !matches!(self.fs.reparse_point(path), Ok(None));
+    let mut visited_dirs: HashSet<(u64, u64)> = HashSet::default();
+    let mut queue = VecDeque::from([(cwd, 0)]);
+
+    while let Some((dir, depth)) = queue.pop_front() {
+      if matches!(self.fs.file_id(&dir), Ok(Some(id)) if !visited_dirs.insert(id)) {
+        continue;
+      }
+
+      let excluded = self.exclude_dependency_dirs && is_in_dependency_dir(&dir);
+      let file = dir.join(name);
+      if !excluded
+        && self
+          .fs
+          .metadata(&file)
+          .is_ok_and(|metadata| self.is_kind(&metadata))
+        && !escapes(&file)
+      {
+        paths.push(file);
+      }
+
+      if depth == max_depth {
+        continue;
+      }
+
+      let mut entries = self.fs.read_dir(&dir).unwrap_or_default();
+      entries.sort();
+      for entry in entries {
+        let subdir = dir.join(entry);
+        if self.exclude_dependency_dirs && is_in_dependency_dir(&subdir) {
+          continue;
+        }
+        if self
+          .fs
+          .metadata(&subdir)
+          .is_ok_and(|metadata| metadata.is_dir())
+          && !escapes(&subdir)
+        {
+          queue.push_back((subdir, depth + 1));
+        }
+      }
+    }
+
+    paths
+  }
+
+  /// The nearest match in either direction: in the current working
+  /// directory, then in its direct subdirectories, see
+  /// [`UpFinder::find_down`], then in the parent directories.
+  ///
+  /// Without a match below it, the current working directory is looked in
+  /// twice: once by `find_down` and once more by the upward walk.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if let Some(path) = find_up.find_nearest(".editorconfig") {
+  ///   println!("{}", path.display());
+  /// }
+  /// ```
+  pub fn find_nearest(&self, name: &str) -> Option<PathBuf> {
+    self.find_down(name, 1).into_iter().next().or_else(|| {
+      self
+        .find_up_with(name, FindUpResult::Last)
+        .into_iter()
+        .next()
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::{Fs, MemoryFs, Metadata, ReparsePoint};
+
+  fn fs() -> Arc<MemoryFs> {
+    Arc::new(
+      MemoryFs::new()
+        .with_file("/repo/.editorconfig", "")
+        .with_file("/repo/app/packages/b/.editorconfig", "")
+        .with_file("/repo/app/packages/a/.editorconfig", "")
+        .with_file("/repo/app/node_modules/x/.editorconfig", "")
+        .with_file("/repo/app/docs/.editorconfig", "")
+        .with_dir("/repo/app/src"),
+    )
+  }
+
+  #[test]
+  fn should_search_the_subtree_breadth_first() {
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/app")
+      .exclude_dependency_dirs(true)
+      .fs(fs())
+      .build();
+
+    assert_eq!(
+      up_finder.find_down(".editorconfig", 2),
+      [
+        PathBuf::from("/repo/app/docs/.editorconfig"),
+        PathBuf::from("/repo/app/packages/a/.editorconfig"),
+        PathBuf::from("/repo/app/packages/b/.editorconfig"),
+      ]
+    );
+    assert_eq!(
+      up_finder.find_down(".editorconfig", 0),
+      Vec::<PathBuf>::new()
+    );
+  }
+
+  /// Panics on every access to a `node_modules` directory or below it.
+  #[derive(Debug)]
+  struct NoNodeModulesFs(MemoryFs);
+
+  impl NoNodeModulesFs {
+    fn check(path: &Path) {
+      assert!(
+        !path.iter().any(|name| name == "node_modules"),
+        "{} was accessed",
+        path.display()
+      );
+    }
+  }
+
+  impl Fs for NoNodeModulesFs {
+    fn metadata(&self, path: &Path) -> std::io::Result<Metadata> {
+      Self::check(path);
+      self.0.metadata(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> std::io::Result<Vec<std::ffi::OsString>> {
+      Self::check(path);
+      self.0.read_dir(path)
+    }
+
+    fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+      Self::check(path);
+      self.0.read(path)
+    }
+  }
+
+  #[test]
+  fn should_not_enter_excluded_dependency_dirs() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/app/node_modules/x/.editorconfig", "")
+      .with_file("/repo/app/.editorconfig", "");
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/app")
+      .exclude_dependency_dirs(true)
+      .fs(Arc::new(NoNodeModulesFs(fs)))
+      .build();
+
+    assert_eq!(
+      up_finder.find_down(".editorconfig", 3),
+      [PathBuf::from("/repo/app/.editorconfig")]
+    );
+  }
+
+  #[test]
+  fn should_find_the_nearest_match_in_either_direction() {
+    let up_finder = UpFinder::builder().cwd("/repo/app").fs(fs()).build();
+    assert_eq!(
+      up_finder.find_nearest(".editorconfig"),
+      Some(PathBuf::from("/repo/app/docs/.editorconfig"))
+    );
+
+    let up_finder = UpFinder::builder().cwd("/repo/app/src").fs(fs()).build();
+    assert_eq!(
+      up_finder.find_nearest(".editorconfig"),
+      Some(PathBuf::from("/repo/.editorconfig"))
+    );
+    assert_eq!(up_finder.find_nearest("Cargo.toml"), None);
+  }
+
+  #[test]
+  fn should_not_leave_the_restricted_root() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/app/.editorconfig", "")
+      .with_file("/repo/app/link/.editorconfig", "")
+      .with_reparse_point("/repo/app/link", ReparsePoint::Symlink)
+      .with_file("/repo/app/docs/.editorconfig", "");
+    let fs = Arc::new(fs);
+
+    let inside = UpFinder::builder()
+      .cwd("/repo/app")
+      .restrict_to("/repo")
+      .fs(fs.clone())
+      .build();
+
+    assert_eq!(
+      inside.find_down(".editorconfig", 1),
+      [
+        PathBuf::from("/repo/app/.editorconfig"),
+        PathBuf::from("/repo/app/docs/.editorconfig"),
+      ]
+    );
+
+    let outside = UpFinder::builder()
+      .cwd("/repo/app")
+      .restrict_to("/repo/app/docs")
+      .fs(fs)
+      .build();
+
+    assert!(outside.find_down(".editorconfig", 1).is_empty());
+  }
+}
@@ -0,0 +1,61 @@
+use std::{error::Error, fmt, io, path::PathBuf};
+
+/// Why a search could not be run, returned by the `try_` methods of
+/// [`UpFinder`](crate::UpFinder).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FindUpError {
+  /// The start directory is not inside the root the search is restricted to.
+  OutsideRoot {
+    path: PathBuf,
+    root: PathBuf,
+  },
+  /// A symlink, junction or other reparse point below the root the search is
+  /// restricted to, which could lead outside of it.
+  Escape(PathBuf),
+  /// The format of the file can't be told from its name.
+  UnknownFormat(String),
+  /// The file at `path` could not be deserialized.
+  Parse {
+    path: PathBuf,
+    source: Box<dyn Error + Send + Sync>,
+  },
+  /// The matcher of [`UpFinder::try_find_up_with`](crate::UpFinder::try_find_up_with)
+  /// failed on the candidate at `path`.
+  Matcher {
+    path: PathBuf,
+    source: Box<dyn Error + Send + Sync>,
+  },
+  Io(io::Error),
+}
+
+impl fmt::Display for FindUpError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::OutsideRoot { path, root } => {
+        write!(f, "{} is outside of {}", path.display(), root.display())
+      }
+      Self::Escape(path) => write!(f, "{} could lead outside of the root", path.display()),
+      Self::UnknownFormat(name) => write!(f, "the format of {name} is not known"),
+      Self::Parse { path, source } => write!(f, "failed to parse {}: {source}", path.display()),
+      Self::Matcher { path, source } => write!(f, "failed to match {}: {source}", path.display()),
+      Self::Io(error) => error.fmt(f),
+    }
+  }
+}
+
+impl Error for FindUpError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    match self {
+      Self::Parse { source, .. } | Self::Matcher { source, .. } => Some(&**source),
+      Self::Io(error) => Some(error),
+      _ => None,
+    }
+  }
+}
+
+impl From<io::Error> for FindUpError {
+  fn from(error: io::Error) -> Self {
+    Self::Io(error)
+  }
+}
@@ -0,0 +1,139 @@
+use std::{borrow::Cow, ops::ControlFlow, path::Path};
+
+use crate::{ConfigFallback, SearchReport, UpFinder, hash::HashMap, levels::Levels};
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Whether [`UpFinder::find_up`] would find `name`, probing the levels one
+  /// at a time and ending on the first entry found, without collecting any
+  /// matches.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if find_up.exists_up("package.json") {
+  ///   println!("inside a Node project");
+  /// }
+  /// ```
+  pub fn exists_up(&self, name: &str) -> bool {
+    self.exists_up_impl(name, self.max_depth)
+  }
+
+  /// Like [`UpFinder::exists_up`], looking at most `max_depth` levels above
+  /// the start directory, or fewer if the [`UpFinder`] has a lower
+  /// `max_depth`.
+  pub fn exists_up_within(&self, name: &str, max_depth: usize) -> bool {
+    let max_depth = self
+      .max_depth
+      .map_or(max_depth, |limit| limit.min(max_depth));
+
+    self.exists_up_impl(name, Some(max_depth))
+  }
+
+  /// Probe every level for `name` until the first entry of the right kind,
+  /// through the same checks as the walk of [`UpFinder::find_up`].
+  fn exists_up_impl(&self, name: &str, max_depth: Option<usize>) -> bool {
+    let mut report = SearchReport::default();
+
+    if self.env_override_match(&mut report).is_some() {
+      return true;
+    }
+
+    let Ok(levels) = Levels::new(self, self.cwd.as_ref().to_path_buf(), false) else {
+      return false;
+    };
+    let mut levels = levels.with_max_depth(max_depth);
+    while let Some((dir, depth)) = levels.next_dir() {
+      report.level(dir, depth);
+
+      let Ok(Some(mut level)) = levels.enter(&mut report) else {
+        return false;
+      };
+
+      let cwd = level.dir.as_path();
+      let dirs = std::iter::once(Cow::Borrowed(cwd))
+        .chain(self.probes().map(|probe| Cow::Owned(cwd.join(probe))));
+      for dir in dirs {
+        let mut listings = HashMap::default();
+        match self.lookup(
+          &mut level.access,
+          cwd,
+          &dir,
+          name,
+          &mut listings,
+          &mut report,
+        ) {
+          ControlFlow::Continue(Some(_)) => return true,
+          ControlFlow::Continue(None) => {}
+          ControlFlow::Break(()) => break,
+        }
+      }
+
+      levels.leave(&level, &mut report);
+    }
+
+    self
+      .config_fallback
+      .iter()
+      .flat_map(ConfigFallback::dirs)
+      .any(|(_, dir)| {
+        self
+          .fs
+          .metadata(&dir.join(name))
+          .is_ok_and(|metadata| self.is_kind(&metadata))
+      })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use crate::{ConfigFallback, HookResult, MemoryFs, UpFinder};
+
+  #[test]
+  fn should_tell_whether_a_search_would_find_the_name() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/package.json", "{}")
+      .with_file("/home/.npmrc", "")
+      .with_dir("/repo/app/src");
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/app/src")
+      .config_fallback(ConfigFallback::new().with_user_dir("/home"))
+      .fs(Arc::new(fs))
+      .build();
+
+    assert!(up_finder.exists_up("package.json"));
+    assert!(up_finder.exists_up(".npmrc"));
+    assert!(!up_finder.exists_up("Cargo.toml"));
+
+    assert!(up_finder.exists_up_within("package.json", 2));
+    assert!(!up_finder.exists_up_within("package.json", 1));
+  }
+
+  #[test]
+  fn should_go_through_the_level_checks_of_the_walk() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/node_modules/lib/.babelrc", "")
+      .with_file("/repo/vendor/.babelrc", "")
+      .with_dir("/repo/node_modules/lib/src");
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/node_modules/lib/src")
+      .exclude_dependency_dirs(true)
+      .fs(Arc::new(fs.clone()))
+      .build();
+
+    assert!(!up_finder.exists_up(".babelrc"));
+
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/vendor")
+      .on_enter_dir(|_, _| HookResult::Skip)
+      .fs(Arc::new(fs))
+      .build();
+
+    assert!(!up_finder.exists_up(".babelrc"));
+  }
+}
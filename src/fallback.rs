@@ -0,0 +1,209 @@
+use std::{
+  ffi::OsString,
+  path::{Path, PathBuf},
+};
+
+/// Where a match came from, see [`ConfigFallback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, PartialOrd, Ord, Hash)]
+pub enum ConfigTier {
+  /// The file named by the
+  /// [`env_override`](crate::UpFinderBuilder::env_override) variable.
+  Override,
+  /// The walk from the start directory upwards.
+  #[default]
+  Project,
+  /// A per-user configuration directory.
+  User,
+  /// A system-wide configuration directory.
+  System,
+}
+
+/// The directories searched for a name the walk didn't find, user directories
+/// before system directories, in order. The first match is reported, with
+/// its [`ConfigTier`].
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{ConfigFallback, UpFinder};
+///
+/// // ./.myapprc, else ~/.config/myapp/.myapprc, else /etc/xdg/myapp/.myapprc,
+/// // else /etc/myapp/.myapprc
+/// let find_up = UpFinder::builder()
+///   .cwd(".")
+///   .config_fallback(ConfigFallback::xdg("myapp").with_system_dir("/etc/myapp"))
+///   .build();
+///
+/// for found in find_up.find_up_matches(".myapprc") {
+///   println!("{:?} {}", found.tier(), found.path().display());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConfigFallback {
+  user_dirs: Vec<PathBuf>,
+  system_dirs: Vec<PathBuf>,
+}
+
+impl ConfigFallback {
+  /// No fallback directories.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// The XDG base directories of `app`: `$XDG_CONFIG_HOME/{app}`, or
+  /// `$HOME/.config/{app}` without it, for the user, and `{dir}/{app}` for
+  /// every directory of `$XDG_CONFIG_DIRS`, `/etc/xdg` without it, for the
+  /// system. Relative paths in the variables are ignored, as the
+  /// specification asks.
+  pub fn xdg(app: &str) -> Self {
+    Self::xdg_from(app, |name| std::env::var_os(name))
+  }
+
+  fn xdg_from(app: &str, var: impl Fn(&str) -> Option<OsString>) -> Self {
+    let absolute = |value: OsString| Some(PathBuf::from(value)).filter(|path| path.is_absolute());
+
+    let config_home = var("XDG_CONFIG_HOME").and_then(absolute).or_else(|| {
+      var("HOME")
+        .and_then(absolute)
+        .map(|home| home.join(".config"))
+    });
+
+    let config_dirs: Vec<PathBuf> = match var("XDG_CONFIG_DIRS").filter(|dirs| !dirs.is_empty()) {
+      Some(dirs) => std::env::split_paths(&dirs)
+        .filter(|path| path.is_absolute())
+        .collect(),
+      None => vec![PathBuf::from("/etc/xdg")],
+    };
+
+    Self {
+      user_dirs: config_home.into_iter().map(|dir| dir.join(app)).collect(),
+      system_dirs: config_dirs.into_iter().map(|dir| dir.join(app)).collect(),
+    }
+  }
+
+  /// The configuration directories of `app` where the platform keeps them:
+  /// `~/Library/Application Support/{app}` and `/Library/Application
+  /// Support/{app}` on macOS, `%APPDATA%\{app}` and `%ProgramData%\{app}` on
+  /// Windows and the [XDG](ConfigFallback::xdg) ones elsewhere.
+  #[cfg(feature = "dirs")]
+  pub fn platform(app: &str) -> Self {
+    let system_dirs = if cfg!(target_os = "macos") {
+      vec![Path::new("/Library/Application Support").join(app)]
+    } else if cfg!(windows) {
+      std::env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .map(|dir| dir.join(app))
+        .into_iter()
+        .collect()
+    } else {
+      Self::xdg(app).system_dirs
+    };
+
+    Self {
+      user_dirs: dirs::config_dir()
+        .map(|dir| dir.join(app))
+        .into_iter()
+        .collect(),
+      system_dirs,
+    }
+  }
+
+  /// Add a user configuration directory after the existing ones.
+  pub fn with_user_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+    self.user_dirs.push(dir.into());
+    self
+  }
+
+  /// Add a system configuration directory after the existing ones, e.g. the
+  /// `/etc/{app}` a distribution package installs its defaults to.
+  pub fn with_system_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+    self.system_dirs.push(dir.into());
+    self
+  }
+
+  pub fn user_dirs(&self) -> &[PathBuf] {
+    &self.user_dirs
+  }
+
+  pub fn system_dirs(&self) -> &[PathBuf] {
+    &self.system_dirs
+  }
+
+  /// Every directory with its tier, in search order.
+  pub(crate) fn dirs(&self) -> impl Iterator<Item = (ConfigTier, &Path)> {
+    let user = self
+      .user_dirs
+      .iter()
+      .map(|dir| (ConfigTier::User, dir.as_path()));
+    let system = self
+      .system_dirs
+      .iter()
+      .map(|dir| (ConfigTier::System, dir.as_path()));
+
+    user.chain(system)
+  }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+  use super::*;
+
+  fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<OsString> + 'a {
+    |name| {
+      vars
+        .iter()
+        .find(|(var, _)| *var == name)
+        .map(|(_, value)| value.into())
+    }
+  }
+
+  #[test]
+  fn should_read_the_xdg_base_directories() {
+    let fallback = ConfigFallback::xdg_from(
+      "app",
+      env(&[
+        ("HOME", "/home/me"),
+        ("XDG_CONFIG_DIRS", "/etc/site:relative:/etc/xdg"),
+      ]),
+    );
+
+    assert_eq!(
+      fallback.user_dirs(),
+      [PathBuf::from("/home/me/.config/app")]
+    );
+    assert_eq!(
+      fallback.system_dirs(),
+      [
+        PathBuf::from("/etc/site/app"),
+        PathBuf::from("/etc/xdg/app")
+      ]
+    );
+
+    let fallback = ConfigFallback::xdg_from(
+      "app",
+      env(&[("HOME", "/home/me"), ("XDG_CONFIG_HOME", "/cfg")]),
+    );
+
+    assert_eq!(fallback.user_dirs(), [PathBuf::from("/cfg/app")]);
+    assert_eq!(fallback.system_dirs(), [PathBuf::from("/etc/xdg/app")]);
+
+    let fallback = fallback.with_system_dir("/etc/app");
+    let tiers: Vec<_> = fallback.dirs().collect();
+
+    assert_eq!(
+      tiers,
+      [
+        (ConfigTier::User, Path::new("/cfg/app")),
+        (ConfigTier::System, Path::new("/etc/xdg/app")),
+        (ConfigTier::System, Path::new("/etc/app")),
+      ]
+    );
+  }
+
+  #[cfg(all(feature = "dirs", target_os = "linux"))]
+  #[test]
+  fn should_use_the_xdg_directories_on_linux() {
+    assert_eq!(ConfigFallback::platform("app"), ConfigFallback::xdg("app"));
+  }
+}
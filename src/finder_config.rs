@@ -0,0 +1,263 @@
+use std::{
+  error::Error,
+  fmt,
+  path::{Path, PathBuf},
+  str::FromStr,
+  sync::Arc,
+};
+
+#[cfg(feature = "serde")]
+use serde::{
+  Deserialize, Deserializer,
+  de::{self, MapAccess, Visitor},
+};
+
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use crate::StdFs;
+use crate::{FindUpKind, Fs, UpFinder};
+
+/// Search behavior loaded from a configuration file with the `serde`
+/// feature, or parsed from a [`Query`](crate::Query).
+///
+/// The keys are kebab-case, all of them optional: `kind` (`"file"`, `"dir"`
+/// or `"any"`), `stop-at`, `restrict-to`, `max-depth`, `config-dir`,
+/// `exclude-dependency-dirs` and `markers`, the names to search for. Unknown
+/// keys are an error.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "toml")]
+/// # {
+/// use up_finder::UpFinderConfig;
+///
+/// let config: UpFinderConfig = toml::from_str(
+///   r#"
+///   kind = "dir"
+///   max-depth = 3
+///   markers = [".git", ".hg"]
+///   "#,
+/// )
+/// .unwrap();
+///
+/// let find_up = config.up_finder(".");
+/// let markers: Vec<&str> = config.markers().iter().map(String::as_str).collect();
+///
+/// println!("{:#?}", find_up.find_up_multi(&markers));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UpFinderConfig {
+  pub(crate) kind: Option<FindUpKind>,
+  pub(crate) stop_at: Option<PathBuf>,
+  pub(crate) restrict_to: Option<PathBuf>,
+  pub(crate) max_depth: Option<usize>,
+  pub(crate) config_dir: bool,
+  pub(crate) exclude_dependency_dirs: bool,
+  pub(crate) markers: Vec<String>,
+}
+
+impl UpFinderConfig {
+  /// The kind of entry to search for, files by default.
+  pub fn kind(&self) -> FindUpKind {
+    self.kind.clone().unwrap_or(FindUpKind::File)
+  }
+
+  /// The `stop_at` directory.
+  pub fn stop_at(&self) -> Option<&Path> {
+    self.stop_at.as_deref()
+  }
+
+  /// The `restrict_to` root.
+  pub fn restrict_to(&self) -> Option<&Path> {
+    self.restrict_to.as_deref()
+  }
+
+  /// The `max_depth` limit.
+  pub fn max_depth(&self) -> Option<usize> {
+    self.max_depth
+  }
+
+  /// Whether `.config` subdirectories are searched as well.
+  pub fn config_dir(&self) -> bool {
+    self.config_dir
+  }
+
+  /// Whether matches in dependency directories are left out.
+  pub fn exclude_dependency_dirs(&self) -> bool {
+    self.exclude_dependency_dirs
+  }
+
+  /// The names to search for, in order, empty if the file gives none.
+  pub fn markers(&self) -> &[String] {
+    &self.markers
+  }
+
+  /// An [`UpFinder`] starting at `cwd` with this behavior, over the real
+  /// filesystem.
+  #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+  pub fn up_finder<P: AsRef<Path>>(&self, cwd: P) -> UpFinder<P> {
+    self.up_finder_with_fs(cwd, Arc::new(StdFs))
+  }
+
+  /// Like [`UpFinderConfig::up_finder`], searching `fs`.
+  pub fn up_finder_with_fs<P: AsRef<Path>>(&self, cwd: P, fs: Arc<dyn Fs>) -> UpFinder<P> {
+    UpFinder::builder()
+      .cwd(cwd)
+      .kind(self.kind())
+      .stop_at_opt(self.stop_at.clone())
+      .restrict_to_opt(self.restrict_to.clone())
+      .max_depth_opt(self.max_depth)
+      .config_dir(self.config_dir)
+      .exclude_dependency_dirs(self.exclude_dependency_dirs)
+      .fs(fs)
+      .build()
+  }
+}
+
+#[cfg(feature = "serde")]
+const FIELDS: &[&str] = &[
+  "kind",
+  "stop-at",
+  "restrict-to",
+  "max-depth",
+  "config-dir",
+  "exclude-dependency-dirs",
+  "markers",
+];
+
+/// The names of the kinds, see [`parse_kind`].
+pub(crate) const KINDS: &[&str] = &["file", "dir", "any"];
+
+/// The kind named `name`, one of [`KINDS`].
+pub(crate) fn parse_kind(name: &str) -> Option<FindUpKind> {
+  match name {
+    "file" => Some(FindUpKind::File),
+    "dir" => Some(FindUpKind::Dir),
+    "any" => Some(FindUpKind::Any),
+    _ => None,
+  }
+}
+
+impl FromStr for FindUpKind {
+  type Err = ParseKindError;
+
+  fn from_str(kind: &str) -> Result<Self, Self::Err> {
+    parse_kind(kind).ok_or_else(|| ParseKindError(kind.to_owned()))
+  }
+}
+
+/// Why a [`FindUpKind`] could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKindError(String);
+
+impl fmt::Display for ParseKindError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "unknown kind `{}`, expected one of {}",
+      self.0,
+      KINDS.join(", ")
+    )
+  }
+}
+
+impl Error for ParseKindError {}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for UpFinderConfig {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_struct("UpFinderConfig", FIELDS, ConfigVisitor)
+  }
+}
+
+#[cfg(feature = "serde")]
+struct ConfigVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for ConfigVisitor {
+  type Value = UpFinderConfig;
+
+  fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("an up_finder configuration")
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+    let mut config = UpFinderConfig::default();
+
+    while let Some(key) = map.next_key::<String>()? {
+      match key.as_str() {
+        "kind" => {
+          let kind = map.next_value::<String>()?;
+          config.kind =
+            Some(parse_kind(&kind).ok_or_else(|| de::Error::unknown_variant(&kind, KINDS))?);
+        }
+        "stop-at" => config.stop_at = Some(map.next_value()?),
+        "restrict-to" => config.restrict_to = Some(map.next_value()?),
+        "max-depth" => config.max_depth = Some(map.next_value()?),
+        "config-dir" => config.config_dir = map.next_value()?,
+        "exclude-dependency-dirs" => config.exclude_dependency_dirs = map.next_value()?,
+        "markers" => config.markers = map.next_value()?,
+        other => return Err(de::Error::unknown_field(other, FIELDS)),
+      }
+    }
+
+    Ok(config)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  #[cfg(feature = "json")]
+  use crate::MemoryFs;
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn should_build_the_configured_up_finder() {
+    let config: UpFinderConfig = serde_json::from_str(
+      r#"{
+        "kind": "dir",
+        "stop-at": "/repo",
+        "config-dir": true,
+        "markers": [".git", "hooks"]
+      }"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.kind(), FindUpKind::Dir);
+    assert_eq!(config.markers(), [".git", "hooks"]);
+    assert_eq!(config.max_depth(), None);
+
+    let fs = MemoryFs::new()
+      .with_dir("/.git")
+      .with_dir("/repo/.config/hooks")
+      .with_dir("/repo/app");
+    let up_finder = config.up_finder_with_fs("/repo/app", Arc::new(fs));
+
+    assert_eq!(
+      up_finder.find_up_multi(&[".git", "hooks"])["hooks"],
+      [PathBuf::from("/repo/.config/hooks")]
+    );
+    assert!(up_finder.find_up(".git").is_empty());
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn should_reject_unknown_keys_and_kinds() {
+    let error = serde_json::from_str::<UpFinderConfig>(r#"{ "max_depth": 2 }"#).unwrap_err();
+    assert!(error.to_string().starts_with("unknown field `max_depth`"));
+
+    let error = serde_json::from_str::<UpFinderConfig>(r#"{ "kind": "link" }"#).unwrap_err();
+    assert!(error.to_string().starts_with("unknown variant `link`"));
+  }
+
+  #[test]
+  fn should_parse_kinds_from_strings() {
+    assert_eq!("any".parse(), Ok(FindUpKind::Any));
+    assert_eq!(
+      "symlink".parse::<FindUpKind>().unwrap_err().to_string(),
+      "unknown kind `symlink`, expected one of file, dir, any"
+    );
+  }
+}
@@ -0,0 +1,434 @@
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  ffi::OsString,
+  fmt::{self, Debug},
+  fs, io,
+  ops::Deref,
+  path::{Component, Path, PathBuf},
+  sync::Arc,
+};
+
+use crate::ReparsePoint;
+
+#[cfg(feature = "cap-std")]
+mod cap_std;
+mod overlay;
+#[cfg(feature = "vfs")]
+mod vfs;
+
+#[cfg(feature = "cap-std")]
+pub use self::cap_std::CapStdFs;
+pub use self::overlay::OverlayFs;
+#[cfg(feature = "vfs")]
+pub use self::vfs::VfsFs;
+
+/// The filesystem operations a search performs.
+///
+/// [`StdFs`] is used unless another implementation is passed to the builder,
+/// [`MemoryFs`] allows running searches against a tree that only exists in memory.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use up_finder::{MemoryFs, UpFinder};
+///
+/// let fs = MemoryFs::new()
+///   .with_file("/project/package.json", "{}")
+///   .with_dir("/project/src");
+///
+/// let find_up = UpFinder::builder()
+///   .cwd("/project/src")
+///   .fs(Arc::new(fs))
+///   .build();
+/// let paths = find_up.find_up("package.json");
+///
+/// println!("{:#?}", paths);
+/// ```
+pub trait Fs: Debug + Send + Sync {
+  /// Whether anything exists at `path`.
+  fn exists(&self, path: &Path) -> bool {
+    self.metadata(path).is_ok()
+  }
+
+  /// The metadata of the entry at `path`, following symlinks.
+  fn metadata(&self, path: &Path) -> io::Result<Metadata>;
+
+  /// The names of the entries in the directory at `path`.
+  fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>>;
+
+  /// The contents of the file at `path`.
+  fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+  /// Whether `path` lives on a network filesystem, see [`NetworkMountPolicy`](crate::NetworkMountPolicy).
+  fn is_network_mount(&self, _path: &Path) -> io::Result<bool> {
+    Ok(false)
+  }
+
+  /// Whether the entry at `path` itself is a junction, symlink or other
+  /// [`ReparsePoint`], without following it.
+  fn reparse_point(&self, _path: &Path) -> io::Result<Option<ReparsePoint>> {
+    Ok(None)
+  }
+
+  /// Whether the walk must stop at the directory `path` instead of going on
+  /// to its parent, e.g. because nothing above it is reachable.
+  fn is_boundary(&self, _path: &Path) -> bool {
+    false
+  }
+
+  /// An identifier that is the same for every path leading to the file or
+  /// directory at `path`, following symlinks, e.g. its device and inode numbers.
+  /// `None` if the filesystem can't tell.
+  fn file_id(&self, _path: &Path) -> io::Result<Option<(u64, u64)>> {
+    Ok(None)
+  }
+
+  /// The id of the user owning the entry at `path`, see
+  /// [`OwnershipPolicy`](crate::OwnershipPolicy). `None` if the filesystem has
+  /// no notion of owners.
+  fn owner(&self, _path: &Path) -> io::Result<Option<u32>> {
+    Ok(None)
+  }
+}
+
+/// The filesystem of an [`UpFinder`](crate::UpFinder), equal to another one
+/// if both are the same instance, so that searches sharing it compare equal.
+#[derive(Clone)]
+pub(crate) struct SharedFs(pub(crate) Arc<dyn Fs>);
+
+impl Deref for SharedFs {
+  type Target = dyn Fs;
+
+  fn deref(&self) -> &Self::Target {
+    &*self.0
+  }
+}
+
+impl Debug for SharedFs {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.0.fmt(f)
+  }
+}
+
+impl PartialEq for SharedFs {
+  fn eq(&self, other: &Self) -> bool {
+    Arc::ptr_eq(&self.0, &other.0)
+  }
+}
+
+/// The kind of an entry reported by [`Fs::metadata`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+  File,
+  Dir,
+  Other,
+}
+
+/// The metadata of an entry reported by [`Fs::metadata`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+  file_type: FileType,
+  len: u64,
+}
+
+impl Metadata {
+  pub fn new(file_type: FileType, len: u64) -> Self {
+    Self { file_type, len }
+  }
+
+  pub fn file_type(&self) -> FileType {
+    self.file_type
+  }
+
+  pub fn is_file(&self) -> bool {
+    self.file_type == FileType::File
+  }
+
+  pub fn is_dir(&self) -> bool {
+    self.file_type == FileType::Dir
+  }
+
+  pub fn len(&self) -> u64 {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+}
+
+impl From<fs::Metadata> for Metadata {
+  fn from(metadata: fs::Metadata) -> Self {
+    let file_type = if metadata.is_file() {
+      FileType::File
+    } else if metadata.is_dir() {
+      FileType::Dir
+    } else {
+      FileType::Other
+    };
+
+    Self::new(file_type, metadata.len())
+  }
+}
+
+/// The real filesystem, backed by [`std::fs`].
+///
+/// Not available on `wasm32-unknown-unknown`, which has no filesystem; searches
+/// there run over whatever [`Fs`] is passed to the builder.
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StdFs;
+
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+impl Fs for StdFs {
+  fn exists(&self, path: &Path) -> bool {
+    path.exists()
+  }
+
+  fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+    fs::metadata(path).map(Metadata::from)
+  }
+
+  fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+    fs::read_dir(path)?
+      .map(|entry| entry.map(|entry| entry.file_name()))
+      .collect()
+  }
+
+  fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+    fs::read(path)
+  }
+
+  fn is_network_mount(&self, path: &Path) -> io::Result<bool> {
+    crate::is_network_mount(path)
+  }
+
+  fn reparse_point(&self, path: &Path) -> io::Result<Option<ReparsePoint>> {
+    crate::reparse::reparse_point(path)
+  }
+
+  fn file_id(&self, path: &Path) -> io::Result<Option<(u64, u64)>> {
+    crate::bind_mount::file_id(path)
+  }
+
+  fn owner(&self, path: &Path) -> io::Result<Option<u32>> {
+    crate::ownership::owner(path)
+  }
+
+  /// On WASI, preopened directories are boundaries: nothing above them can be
+  /// accessed.
+  #[cfg(target_os = "wasi")]
+  fn is_boundary(&self, path: &Path) -> bool {
+    let path = normalize(path);
+
+    crate::wasi::preopened_dirs()
+      .iter()
+      .any(|preopen| normalize(preopen) == path)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MemoryEntry {
+  File(Vec<u8>),
+  Dir,
+}
+
+/// An in-memory filesystem for deterministic tests.
+///
+/// Parent directories of inserted entries are created implicitly. Paths are
+/// stored as given, after dropping `.` components, so relative and absolute
+/// trees can be modelled alike.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemoryFs {
+  entries: BTreeMap<PathBuf, MemoryEntry>,
+  reparse_points: BTreeMap<PathBuf, ReparsePoint>,
+  boundaries: BTreeSet<PathBuf>,
+  owners: BTreeMap<PathBuf, u32>,
+}
+
+impl MemoryFs {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Add a file with the given contents.
+  pub fn with_file(mut self, path: impl AsRef<Path>, contents: impl Into<Vec<u8>>) -> Self {
+    self.insert_file(path, contents);
+    self
+  }
+
+  /// Add an empty directory.
+  pub fn with_dir(mut self, path: impl AsRef<Path>) -> Self {
+    self.insert_dir(path);
+    self
+  }
+
+  /// Mark `path` as a reparse point, e.g. to model a junction.
+  pub fn with_reparse_point(mut self, path: impl AsRef<Path>, reparse_point: ReparsePoint) -> Self {
+    self
+      .reparse_points
+      .insert(normalize(path.as_ref()), reparse_point);
+    self
+  }
+
+  /// Mark the directory `path` as a boundary the walk must not go above, see
+  /// [`Fs::is_boundary`].
+  pub fn with_boundary(mut self, path: impl AsRef<Path>) -> Self {
+    self.boundaries.insert(normalize(path.as_ref()));
+    self
+  }
+
+  /// Mark `path` as owned by the user `owner`, see [`Fs::owner`].
+  pub fn with_owner(mut self, path: impl AsRef<Path>, owner: u32) -> Self {
+    self.owners.insert(normalize(path.as_ref()), owner);
+    self
+  }
+
+  /// Add a file with the given contents, replacing any existing entry.
+  pub fn insert_file(&mut self, path: impl AsRef<Path>, contents: impl Into<Vec<u8>>) {
+    let path = normalize(path.as_ref());
+    self.insert_parents(&path);
+    self
+      .entries
+      .insert(path, MemoryEntry::File(contents.into()));
+  }
+
+  /// Add an empty directory, replacing any existing entry.
+  pub fn insert_dir(&mut self, path: impl AsRef<Path>) {
+    let path = normalize(path.as_ref());
+    self.insert_parents(&path);
+    self.entries.insert(path, MemoryEntry::Dir);
+  }
+
+  fn insert_parents(&mut self, path: &Path) {
+    for parent in path.ancestors().skip(1) {
+      if is_root(parent) {
+        break;
+      }
+      self.entries.insert(parent.to_path_buf(), MemoryEntry::Dir);
+    }
+  }
+
+  fn entry(&self, path: &Path) -> io::Result<Option<&MemoryEntry>> {
+    let path = normalize(path);
+
+    if is_root(&path) {
+      return Ok(None);
+    }
+
+    match self.entries.get(&path) {
+      Some(entry) => Ok(Some(entry)),
+      None => Err(not_found(&path)),
+    }
+  }
+}
+
+impl Fs for MemoryFs {
+  fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+    Ok(match self.entry(path)? {
+      Some(MemoryEntry::File(contents)) => Metadata::new(FileType::File, contents.len() as u64),
+      Some(MemoryEntry::Dir) | None => Metadata::new(FileType::Dir, 0),
+    })
+  }
+
+  fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+    if let Some(MemoryEntry::File(_)) = self.entry(path)? {
+      return Err(io::Error::new(
+        io::ErrorKind::NotADirectory,
+        format!("{} is not a directory", path.display()),
+      ));
+    }
+
+    let path = normalize(path);
+
+    Ok(
+      self
+        .entries
+        .keys()
+        .filter(|entry| entry.parent() == Some(path.as_path()))
+        .filter_map(|entry| entry.file_name().map(|name| name.to_os_string()))
+        .collect(),
+    )
+  }
+
+  fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+    match self.entry(path)? {
+      Some(MemoryEntry::File(contents)) => Ok(contents.clone()),
+      _ => Err(io::Error::new(
+        io::ErrorKind::IsADirectory,
+        format!("{} is a directory", path.display()),
+      )),
+    }
+  }
+
+  fn reparse_point(&self, path: &Path) -> io::Result<Option<ReparsePoint>> {
+    self.entry(path)?;
+
+    Ok(self.reparse_points.get(&normalize(path)).copied())
+  }
+
+  fn is_boundary(&self, path: &Path) -> bool {
+    self.boundaries.contains(&normalize(path))
+  }
+
+  fn owner(&self, path: &Path) -> io::Result<Option<u32>> {
+    self.entry(path)?;
+
+    Ok(self.owners.get(&normalize(path)).copied())
+  }
+}
+
+/// Drop `.` components so `./a/b` and `a/b` refer to the same entry.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+  path
+    .components()
+    .filter(|component| !matches!(component, Component::CurDir))
+    .collect()
+}
+
+/// The empty path and filesystem roots always exist as directories.
+fn is_root(path: &Path) -> bool {
+  path.parent().is_none()
+}
+
+pub(crate) fn not_found(path: &Path) -> io::Error {
+  io::Error::new(
+    io::ErrorKind::NotFound,
+    format!("{} does not exist", path.display()),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_create_parent_directories_implicitly() {
+    let fs = MemoryFs::new().with_file("a/b/package.json", "{}");
+
+    assert!(fs.metadata(Path::new("a")).unwrap().is_dir());
+    assert!(fs.metadata(Path::new("./a/b")).unwrap().is_dir());
+    assert!(
+      fs.metadata(Path::new("a/b/package.json"))
+        .unwrap()
+        .is_file()
+    );
+    assert!(!fs.exists(Path::new("a/package.json")));
+  }
+
+  #[test]
+  fn should_list_and_read_memory_entries() {
+    let fs = MemoryFs::new()
+      .with_file("a/package.json", "{}")
+      .with_dir("a/b");
+
+    assert_eq!(
+      fs.read_dir(Path::new("a")).unwrap(),
+      vec![OsString::from("b"), OsString::from("package.json")]
+    );
+    assert_eq!(fs.read(Path::new("a/package.json")).unwrap(), b"{}");
+    assert!(fs.read(Path::new("a/b")).is_err());
+  }
+}
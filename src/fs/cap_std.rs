@@ -0,0 +1,124 @@
+use std::{ffi::OsString, io, path::Path, sync::Arc};
+
+use ::cap_std::fs::Dir;
+
+use super::{FileType, Fs, Metadata};
+use crate::UpFinder;
+
+/// A [`Fs`] confined to a [`cap_std::fs::Dir`] capability.
+///
+/// Every path is resolved relative to the directory handle, absolute paths and
+/// `..` components that would leave it are rejected by `cap-std`, so a search
+/// can never observe anything outside of the sandbox.
+#[derive(Debug)]
+pub struct CapStdFs {
+  dir: Dir,
+}
+
+impl CapStdFs {
+  pub fn new(dir: Dir) -> Self {
+    Self { dir }
+  }
+}
+
+impl Fs for CapStdFs {
+  fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+    let metadata = self.dir.metadata(relative(path))?;
+
+    let file_type = if metadata.is_file() {
+      FileType::File
+    } else if metadata.is_dir() {
+      FileType::Dir
+    } else {
+      FileType::Other
+    };
+
+    Ok(Metadata::new(file_type, metadata.len()))
+  }
+
+  fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+    self
+      .dir
+      .read_dir(relative(path))?
+      .map(|entry| entry.map(|entry| entry.file_name()))
+      .collect()
+  }
+
+  fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+    self.dir.read(relative(path))
+  }
+}
+
+/// The walk ends with the empty path, which is the sandbox root itself.
+fn relative(path: &Path) -> &Path {
+  if path.as_os_str().is_empty() {
+    Path::new(".")
+  } else {
+    path
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Create a finder whose whole walk happens inside `dir`.
+  ///
+  /// `cwd` is relative to `dir` and the walk stops at `dir` itself. Use the
+  /// builder with [`CapStdFs`] to combine the sandbox with other options.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use cap_std::{ambient_authority, fs::Dir};
+  /// use up_finder::UpFinder;
+  ///
+  /// let dir = Dir::open_ambient_dir(".", ambient_authority()).unwrap();
+  ///
+  /// let find_up = UpFinder::sandboxed(dir, "src");
+  /// let paths = find_up.find_up("Cargo.toml");
+  ///
+  /// println!("{:#?}", paths);
+  /// ```
+  pub fn sandboxed(dir: Dir, cwd: P) -> Self {
+    UpFinder::builder()
+      .cwd(cwd)
+      .fs(Arc::new(CapStdFs::new(dir)))
+      .build()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::PathBuf;
+
+  use ::cap_std::ambient_authority;
+
+  use super::*;
+
+  fn fixtures() -> Dir {
+    Dir::open_ambient_dir("fixtures/a/b", ambient_authority()).unwrap()
+  }
+
+  #[test]
+  fn should_find_files_inside_the_sandbox() {
+    let up_finder = UpFinder::sandboxed(fixtures(), "c/d");
+
+    let paths = up_finder.find_up("package.json");
+
+    assert_eq!(
+      paths,
+      vec![
+        PathBuf::from("c/d/package.json"),
+        PathBuf::from("c/package.json"),
+        PathBuf::from("package.json"),
+      ]
+    );
+  }
+
+  #[test]
+  fn should_not_escape_the_sandbox() {
+    let fs = CapStdFs::new(fixtures());
+
+    assert!(Path::new("fixtures/a/package.json").is_file());
+    assert!(fs.metadata(Path::new("../package.json")).is_err());
+    assert!(fs.metadata(Path::new("/")).is_err());
+  }
+}
@@ -0,0 +1,301 @@
+use std::{
+  collections::BTreeMap,
+  ffi::OsString,
+  io,
+  path::{Path, PathBuf},
+  sync::{Arc, PoisonError, RwLock},
+};
+
+use super::{FileType, Fs, Metadata, normalize, not_found};
+use crate::ReparsePoint;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OverlayEntry {
+  File(Vec<u8>),
+  Dir,
+  Removed,
+}
+
+/// A [`Fs`] layering virtual entries over another filesystem.
+///
+/// Virtual entries take precedence over the base filesystem and can be changed
+/// at any time through a shared reference, e.g. whenever an editor buffer is
+/// edited, saved or closed. Directories containing virtual entries exist even if
+/// the base filesystem does not have them.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use up_finder::{OverlayFs, StdFs, UpFinder};
+///
+/// let overlay = Arc::new(OverlayFs::new(Arc::new(StdFs)));
+///
+/// // An unsaved buffer that does not exist on disk yet.
+/// overlay.set_file("package.json", r#"{ "name": "unsaved" }"#);
+///
+/// let find_up = UpFinder::builder().cwd("src").fs(overlay.clone()).build();
+/// let paths = find_up.find_up("package.json");
+///
+/// assert_eq!(paths.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct OverlayFs {
+  base: Arc<dyn Fs>,
+  entries: RwLock<BTreeMap<PathBuf, OverlayEntry>>,
+}
+
+impl OverlayFs {
+  pub fn new(base: Arc<dyn Fs>) -> Self {
+    Self {
+      base,
+      entries: RwLock::default(),
+    }
+  }
+
+  /// Make `path` a file with the given contents.
+  pub fn set_file(&self, path: impl AsRef<Path>, contents: impl Into<Vec<u8>>) {
+    self.set(path.as_ref(), OverlayEntry::File(contents.into()));
+  }
+
+  /// Make `path` a directory.
+  pub fn set_dir(&self, path: impl AsRef<Path>) {
+    self.set(path.as_ref(), OverlayEntry::Dir);
+  }
+
+  /// Hide `path` and everything below it, even if it exists in the base
+  /// filesystem.
+  pub fn remove(&self, path: impl AsRef<Path>) {
+    self.set(path.as_ref(), OverlayEntry::Removed);
+  }
+
+  /// Drop the virtual entry at `path`, exposing the base filesystem again.
+  pub fn forget(&self, path: impl AsRef<Path>) {
+    self
+      .entries
+      .write()
+      .unwrap_or_else(PoisonError::into_inner)
+      .remove(&normalize(path.as_ref()));
+  }
+
+  fn set(&self, path: &Path, entry: OverlayEntry) {
+    self
+      .entries
+      .write()
+      .unwrap_or_else(PoisonError::into_inner)
+      .insert(normalize(path), entry);
+  }
+
+  fn lookup(&self, path: &Path) -> Option<OverlayEntry> {
+    let entries = self.entries.read().unwrap_or_else(PoisonError::into_inner);
+
+    if is_hidden(&entries, path) {
+      return Some(OverlayEntry::Removed);
+    }
+
+    if let Some(entry) = entries.get(path) {
+      return Some(entry.clone());
+    }
+
+    let contains_virtual_entries = entries.iter().any(|(entry, kind)| {
+      *kind != OverlayEntry::Removed && entry.starts_with(path) && !is_hidden(&entries, entry)
+    });
+
+    contains_virtual_entries.then_some(OverlayEntry::Dir)
+  }
+}
+
+/// Whether one of the ancestors of `path` was removed, hiding everything below
+/// it.
+fn is_hidden(entries: &BTreeMap<PathBuf, OverlayEntry>, path: &Path) -> bool {
+  path
+    .ancestors()
+    .skip(1)
+    .any(|ancestor| entries.get(ancestor) == Some(&OverlayEntry::Removed))
+}
+
+impl Fs for OverlayFs {
+  fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+    let normalized = normalize(path);
+
+    match self.lookup(&normalized) {
+      Some(OverlayEntry::File(contents)) => {
+        Ok(Metadata::new(FileType::File, contents.len() as u64))
+      }
+      Some(OverlayEntry::Dir) => Ok(Metadata::new(FileType::Dir, 0)),
+      Some(OverlayEntry::Removed) => Err(not_found(&normalized)),
+      None => self.base.metadata(path),
+    }
+  }
+
+  fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+    let normalized = normalize(path);
+
+    let mut names = match self.lookup(&normalized) {
+      Some(OverlayEntry::File(_)) => {
+        return Err(io::Error::new(
+          io::ErrorKind::NotADirectory,
+          format!("{} is not a directory", path.display()),
+        ));
+      }
+      Some(OverlayEntry::Removed) => return Err(not_found(&normalized)),
+      Some(OverlayEntry::Dir) => self.base.read_dir(path).unwrap_or_default(),
+      None => self.base.read_dir(path)?,
+    };
+
+    let entries = self.entries.read().unwrap_or_else(PoisonError::into_inner);
+
+    for (entry, kind) in entries.iter() {
+      let Some(name) = entry
+        .strip_prefix(&normalized)
+        .ok()
+        .and_then(|rest| rest.iter().next())
+      else {
+        continue;
+      };
+
+      if *kind != OverlayEntry::Removed && is_hidden(&entries, entry) {
+        continue;
+      }
+
+      let is_child = entry.parent() == Some(normalized.as_path());
+
+      if is_child && *kind == OverlayEntry::Removed {
+        names.retain(|existing| existing != name);
+      } else if *kind != OverlayEntry::Removed && !names.iter().any(|existing| existing == name) {
+        names.push(name.to_os_string());
+      }
+    }
+
+    Ok(names)
+  }
+
+  fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+    let normalized = normalize(path);
+
+    match self.lookup(&normalized) {
+      Some(OverlayEntry::File(contents)) => Ok(contents),
+      Some(OverlayEntry::Dir) => Err(io::Error::new(
+        io::ErrorKind::IsADirectory,
+        format!("{} is a directory", path.display()),
+      )),
+      Some(OverlayEntry::Removed) => Err(not_found(&normalized)),
+      None => self.base.read(path),
+    }
+  }
+
+  fn is_network_mount(&self, path: &Path) -> io::Result<bool> {
+    self.base.is_network_mount(path)
+  }
+
+  fn reparse_point(&self, path: &Path) -> io::Result<Option<ReparsePoint>> {
+    match self.lookup(&normalize(path)) {
+      Some(OverlayEntry::Removed) => Err(not_found(path)),
+      Some(_) => Ok(None),
+      None => self.base.reparse_point(path),
+    }
+  }
+
+  fn is_boundary(&self, path: &Path) -> bool {
+    self.base.is_boundary(path)
+  }
+
+  fn file_id(&self, path: &Path) -> io::Result<Option<(u64, u64)>> {
+    match self.lookup(&normalize(path)) {
+      Some(OverlayEntry::Removed) => Err(not_found(path)),
+      Some(_) => Ok(None),
+      None => self.base.file_id(path),
+    }
+  }
+
+  fn owner(&self, path: &Path) -> io::Result<Option<u32>> {
+    match self.lookup(&normalize(path)) {
+      Some(OverlayEntry::Removed) => Err(not_found(path)),
+      Some(_) => Ok(None),
+      None => self.base.owner(path),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{StdFs, UpFinder};
+
+  #[test]
+  fn should_prefer_virtual_entries_over_the_base() {
+    let overlay = Arc::new(OverlayFs::new(Arc::new(StdFs)));
+    overlay.set_file("fixtures/a/.node-version", "22");
+    overlay.remove("fixtures/a/b/c/.node-version");
+
+    let up_finder = UpFinder::builder()
+      .cwd("fixtures/a/b/c/d")
+      .fs(overlay.clone())
+      .build();
+
+    assert_eq!(
+      up_finder.find_up(".node-version"),
+      vec![PathBuf::from("fixtures/a/.node-version")]
+    );
+    assert_eq!(
+      overlay.read(Path::new("fixtures/a/.node-version")).unwrap(),
+      b"22"
+    );
+
+    overlay.forget("fixtures/a/b/c/.node-version");
+
+    assert_eq!(up_finder.find_up(".node-version").len(), 2);
+  }
+
+  #[test]
+  fn should_list_virtual_entries_with_the_base() {
+    let overlay = OverlayFs::new(Arc::new(StdFs));
+    overlay.set_file("fixtures/a/b/c/new/tsconfig.json", "{}");
+    overlay.remove("fixtures/a/b/c/package.json");
+
+    let mut names = overlay.read_dir(Path::new("fixtures/a/b/c")).unwrap();
+    names.sort();
+
+    assert_eq!(names, vec![".node-version", "d", "new"]);
+    assert!(
+      overlay
+        .metadata(Path::new("fixtures/a/b/c/new"))
+        .unwrap()
+        .is_dir()
+    );
+  }
+
+  #[test]
+  fn should_hide_everything_below_removed_directories() {
+    let overlay = OverlayFs::new(Arc::new(StdFs));
+    overlay.remove("fixtures/a/b/c");
+    overlay.set_file("fixtures/a/b/c/new/tsconfig.json", "{}");
+
+    let package_json = Path::new("fixtures/a/b/c/package.json");
+
+    assert_eq!(
+      overlay.metadata(package_json).unwrap_err().kind(),
+      io::ErrorKind::NotFound
+    );
+    assert_eq!(
+      overlay.read(package_json).unwrap_err().kind(),
+      io::ErrorKind::NotFound
+    );
+    assert_eq!(
+      overlay
+        .read_dir(Path::new("fixtures/a/b/c/d"))
+        .unwrap_err()
+        .kind(),
+      io::ErrorKind::NotFound
+    );
+    assert!(overlay.metadata(Path::new("fixtures/a/b/c/new")).is_err());
+    assert!(
+      !overlay
+        .read_dir(Path::new("fixtures/a/b"))
+        .unwrap()
+        .iter()
+        .any(|name| name == "c")
+    );
+  }
+}
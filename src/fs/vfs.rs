@@ -0,0 +1,166 @@
+use std::{
+  ffi::OsString,
+  io::{self, Read},
+  path::{Component, Path},
+};
+
+use ::vfs::{VfsError, VfsFileType, VfsPath, error::VfsErrorKind};
+
+use super::{FileType, Fs, Metadata};
+
+/// A [`Fs`] backed by a [`vfs`](::vfs) filesystem.
+///
+/// Absolute paths are resolved from the root of the filesystem, relative paths
+/// from the `VfsPath` the adapter was created with.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use up_finder::{UpFinder, VfsFs};
+/// use vfs::{MemoryFS, VfsPath};
+///
+/// let root = VfsPath::new(MemoryFS::new());
+/// root.join("project/src").unwrap().create_dir_all().unwrap();
+/// root.join("project/package.json").unwrap().create_file().unwrap();
+///
+/// let find_up = UpFinder::builder()
+///   .cwd("/project/src")
+///   .fs(Arc::new(VfsFs::new(root)))
+///   .build();
+/// let paths = find_up.find_up("package.json");
+///
+/// println!("{:#?}", paths);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VfsFs {
+  base: VfsPath,
+}
+
+impl VfsFs {
+  pub fn new(base: VfsPath) -> Self {
+    Self { base }
+  }
+
+  fn resolve(&self, path: &Path) -> io::Result<VfsPath> {
+    let mut resolved = String::new();
+
+    for component in path.components() {
+      match component {
+        Component::RootDir => resolved.push('/'),
+        Component::CurDir => {}
+        Component::ParentDir => push_segment(&mut resolved, ".."),
+        Component::Normal(name) => {
+          let name = name.to_str().ok_or_else(|| {
+            io::Error::new(
+              io::ErrorKind::InvalidInput,
+              format!("{} is not valid UTF-8", path.display()),
+            )
+          })?;
+          push_segment(&mut resolved, name);
+        }
+        Component::Prefix(_) => {
+          return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+              "{} has a prefix, which vfs does not support",
+              path.display()
+            ),
+          ));
+        }
+      }
+    }
+
+    self.base.join(resolved).map_err(into_io_error)
+  }
+}
+
+impl Fs for VfsFs {
+  fn exists(&self, path: &Path) -> bool {
+    self
+      .resolve(path)
+      .and_then(|path| path.exists().map_err(into_io_error))
+      .unwrap_or(false)
+  }
+
+  fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+    let metadata = self.resolve(path)?.metadata().map_err(into_io_error)?;
+
+    let file_type = match metadata.file_type {
+      VfsFileType::File => FileType::File,
+      VfsFileType::Directory => FileType::Dir,
+    };
+
+    Ok(Metadata::new(file_type, metadata.len))
+  }
+
+  fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+    Ok(
+      self
+        .resolve(path)?
+        .read_dir()
+        .map_err(into_io_error)?
+        .map(|entry| OsString::from(entry.filename()))
+        .collect(),
+    )
+  }
+
+  fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+    let mut contents = vec![];
+    self
+      .resolve(path)?
+      .open_file()
+      .map_err(into_io_error)?
+      .read_to_end(&mut contents)?;
+    Ok(contents)
+  }
+}
+
+fn push_segment(path: &mut String, segment: &str) {
+  if !path.is_empty() && !path.ends_with('/') {
+    path.push('/');
+  }
+  path.push_str(segment);
+}
+
+fn into_io_error(error: VfsError) -> io::Error {
+  let kind = match error.kind() {
+    VfsErrorKind::IoError(error) => error.kind(),
+    VfsErrorKind::FileNotFound => io::ErrorKind::NotFound,
+    VfsErrorKind::InvalidPath => io::ErrorKind::InvalidInput,
+    VfsErrorKind::DirectoryExists | VfsErrorKind::FileExists => io::ErrorKind::AlreadyExists,
+    VfsErrorKind::NotSupported => io::ErrorKind::Unsupported,
+    _ => io::ErrorKind::Other,
+  };
+
+  io::Error::new(kind, error)
+}
+
+#[cfg(test)]
+mod tests {
+  use ::vfs::MemoryFS;
+
+  use super::*;
+
+  #[test]
+  fn should_resolve_relative_paths_from_the_base() {
+    let root = VfsPath::new(MemoryFS::new());
+    root.join("workspace/a").unwrap().create_dir_all().unwrap();
+    root
+      .join("workspace/a/package.json")
+      .unwrap()
+      .create_file()
+      .unwrap();
+
+    let fs = VfsFs::new(root.join("workspace").unwrap());
+
+    assert!(fs.metadata(Path::new("a/package.json")).unwrap().is_file());
+    assert!(fs.metadata(Path::new("/workspace/a")).unwrap().is_dir());
+    assert!(!fs.exists(Path::new("package.json")));
+    assert_eq!(
+      fs.read_dir(Path::new("a")).unwrap(),
+      vec![OsString::from("package.json")]
+    );
+  }
+}
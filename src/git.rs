@@ -0,0 +1,251 @@
+use std::path::{Path, PathBuf};
+
+use crate::{Fs, UpFinder, restrict::clean};
+
+/// A git repository, see [`UpFinder::find_git_root`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitRepository {
+  work_tree: PathBuf,
+  git_dir: PathBuf,
+  common_dir: PathBuf,
+  superproject: Option<PathBuf>,
+}
+
+impl GitRepository {
+  /// The top-level directory of the working tree, the one containing `.git`.
+  pub fn work_tree(&self) -> &Path {
+    &self.work_tree
+  }
+
+  /// The git directory of the working tree: `.git` itself, or the directory a
+  /// `.git` file points to.
+  pub fn git_dir(&self) -> &Path {
+    &self.git_dir
+  }
+
+  /// The git directory shared by all worktrees, holding the objects and refs.
+  /// The same as [`GitRepository::git_dir`] outside linked worktrees.
+  pub fn common_dir(&self) -> &Path {
+    &self.common_dir
+  }
+
+  /// Whether this is a worktree added with `git worktree add`.
+  pub fn is_worktree(&self) -> bool {
+    self.git_dir != self.common_dir
+  }
+
+  /// The working tree of the main worktree, for linked worktrees of
+  /// non-bare repositories.
+  pub fn main_work_tree(&self) -> Option<&Path> {
+    if !self.is_worktree() {
+      return Some(&self.work_tree);
+    }
+    self
+      .common_dir
+      .ends_with(".git")
+      .then(|| self.common_dir.parent())
+      .flatten()
+  }
+
+  /// The working tree of the repository this one is a submodule of.
+  pub fn superproject(&self) -> Option<&Path> {
+    self.superproject.as_deref()
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the git repository the current working directory is in.
+  ///
+  /// `.git` may be a directory or, for linked worktrees and submodules, a
+  /// file containing `gitdir: <path>`. The shared git directory of a worktree
+  /// is read from its `commondir` file. A repository whose git directory is
+  /// inside the `modules` directory of the git directory of a repository
+  /// further up is a submodule of it. Neither is looked for above `stop_at`,
+  /// `max_depth` or `restrict_to`, and nothing is found if the current
+  /// working directory is outside of `restrict_to`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd("src").build();
+  ///
+  /// if let Some(repository) = find_up.find_git_root() {
+  ///   println!("{}", repository.work_tree().display());
+  /// }
+  /// ```
+  pub fn find_git_root(&self) -> Option<GitRepository> {
+    let (cwd, root) = self.restricted_cwd().ok()?;
+    let (work_tree, git_dir, depth) = self.find_git_dir(&cwd, 0, root.as_deref())?;
+    let common_dir = read_path_file(&*self.fs, &git_dir.join("commondir"), &git_dir)
+      .unwrap_or_else(|| git_dir.clone());
+
+    let superproject = work_tree
+      .parent()
+      .filter(|_| {
+        !self.fs.is_boundary(&work_tree)
+          && !self.ends_walk_at(&work_tree, depth)
+          && root.as_deref() != Some(&*work_tree)
+      })
+      .and_then(|parent| {
+        let (superproject, super_git_dir, _) =
+          self.find_git_dir(parent, depth + 1, root.as_deref())?;
+        let super_common_dir =
+          read_path_file(&*self.fs, &super_git_dir.join("commondir"), &super_git_dir)
+            .unwrap_or(super_git_dir);
+        git_dir
+          .starts_with(super_common_dir.join("modules"))
+          .then_some(superproject)
+      });
+
+    Some(GitRepository {
+      work_tree,
+      git_dir,
+      common_dir,
+      superproject,
+    })
+  }
+
+  /// The nearest directory at or above `start`, `depth` levels above the
+  /// current working directory, with a `.git` entry, the git directory it
+  /// stands for and its depth. `stop_at`, `max_depth` and the `restrict_to`
+  /// `root` apply.
+  fn find_git_dir(
+    &self,
+    start: &Path,
+    depth: usize,
+    root: Option<&Path>,
+  ) -> Option<(PathBuf, PathBuf, usize)> {
+    for (depth, dir) in (depth..).zip(start.ancestors()) {
+      let git = dir.join(".git");
+      let Ok(metadata) = self.fs.metadata(&git) else {
+        if self.fs.is_boundary(dir) || self.ends_walk_at(dir, depth) || root == Some(dir) {
+          break;
+        }
+        continue;
+      };
+
+      let git_dir = if metadata.is_dir() {
+        git
+      } else {
+        let contents = String::from_utf8(self.fs.read(&git).ok()?).ok()?;
+        let target = contents.trim().strip_prefix("gitdir:")?.trim();
+        clean(&dir.join(target))
+      };
+
+      return Some((dir.to_path_buf(), git_dir, depth));
+    }
+
+    None
+  }
+}
+
+/// Read a file holding a single path, relative to `base` unless absolute.
+fn read_path_file(fs: &dyn Fs, path: &Path, base: &Path) -> Option<PathBuf> {
+  let contents = String::from_utf8(fs.read(path).ok()?).ok()?;
+
+  Some(clean(&base.join(contents.trim())))
+}
+
+/// Find the git repository at or above `start` on the real filesystem, see
+/// [`UpFinder::find_git_root`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn find_git_root(start: impl AsRef<Path>) -> Option<GitRepository> {
+  UpFinder::builder()
+    .cwd(start.as_ref())
+    .build()
+    .find_git_root()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::{MemoryFs, UpFinderBuilder};
+
+  fn find(fs: &Arc<MemoryFs>, cwd: &'static str) -> GitRepository {
+    UpFinder::builder()
+      .cwd(cwd)
+      .fs(fs.clone())
+      .build()
+      .find_git_root()
+      .unwrap()
+  }
+
+  #[test]
+  fn should_find_plain_repositories_and_worktrees() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/repo/.git/HEAD", "ref: refs/heads/main")
+        .with_dir("/repo/src")
+        .with_file("/wt/.git", "gitdir: /repo/.git/worktrees/wt\n")
+        .with_file("/repo/.git/worktrees/wt/commondir", "../..\n")
+        .with_dir("/wt/src"),
+    );
+
+    let repo = find(&fs, "/repo/src");
+
+    assert_eq!(repo.work_tree(), Path::new("/repo"));
+    assert_eq!(repo.git_dir(), Path::new("/repo/.git"));
+    assert!(!repo.is_worktree());
+    assert_eq!(repo.superproject(), None);
+
+    let worktree = find(&fs, "/wt/src");
+
+    assert_eq!(worktree.work_tree(), Path::new("/wt"));
+    assert_eq!(worktree.git_dir(), Path::new("/repo/.git/worktrees/wt"));
+    assert_eq!(worktree.common_dir(), Path::new("/repo/.git"));
+    assert!(worktree.is_worktree());
+    assert_eq!(worktree.main_work_tree(), Some(Path::new("/repo")));
+  }
+
+  #[test]
+  fn should_resolve_the_superproject_of_submodules() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/repo/.git/HEAD", "ref: refs/heads/main")
+        .with_file(
+          "/repo/vendor/lib/.git",
+          "gitdir: ../../.git/modules/vendor/lib",
+        )
+        .with_file("/repo/.git/modules/vendor/lib/HEAD", "")
+        .with_dir("/repo/vendor/lib/src"),
+    );
+
+    let submodule = find(&fs, "/repo/vendor/lib/src");
+
+    assert_eq!(submodule.work_tree(), Path::new("/repo/vendor/lib"));
+    assert_eq!(
+      submodule.git_dir(),
+      Path::new("/repo/.git/modules/vendor/lib")
+    );
+    assert!(!submodule.is_worktree());
+    assert_eq!(submodule.superproject(), Some(Path::new("/repo")));
+
+    let bounded = |up_finder: UpFinderBuilder<&'static str>| {
+      up_finder
+        .cwd("/repo/vendor/lib/src")
+        .fs(fs.clone())
+        .build()
+        .find_git_root()
+    };
+
+    let below_superproject = bounded(UpFinder::builder().stop_at("/repo/vendor")).unwrap();
+    assert_eq!(
+      below_superproject.work_tree(),
+      Path::new("/repo/vendor/lib")
+    );
+    assert_eq!(below_superproject.superproject(), None);
+    assert_eq!(bounded(UpFinder::builder().max_depth(0)), None);
+
+    let below_root = bounded(UpFinder::builder().restrict_to("/repo/vendor")).unwrap();
+    assert_eq!(below_root.superproject(), None);
+    assert_eq!(
+      bounded(UpFinder::builder().restrict_to("/repo/vendor/lib/src")),
+      None
+    );
+    assert_eq!(bounded(UpFinder::builder().restrict_to("/other")), None);
+  }
+}
@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+
+use crate::UpFinder;
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the ignore files git applies to the current working directory, in
+  /// evaluation order: `.git/info/exclude` first, then every `.gitignore` from
+  /// the root of the repository down to the current working directory, so
+  /// later files take precedence.
+  ///
+  /// The walk ends at the first level with a `.git` entry. Empty if there is
+  /// none up to `stop_at`, `max_depth` or `restrict_to`, or if the current
+  /// working directory is outside of `restrict_to`, since outside a
+  /// repository nothing is ignored. `info/exclude` is
+  /// only looked for in `.git` directories, not in the git directory a `.git`
+  /// file of a worktree or submodule points to.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd("src").build();
+  ///
+  /// for path in find_up.find_up_gitignores() {
+  ///   println!("{}", path.display());
+  /// }
+  /// ```
+  pub fn find_up_gitignores(&self) -> Vec<PathBuf> {
+    let mut gitignores = vec![];
+    let Ok((cwd, root)) = self.restricted_cwd() else {
+      return gitignores;
+    };
+
+    for (depth, dir) in cwd.ancestors().enumerate() {
+      let gitignore = dir.join(".gitignore");
+      if self
+        .fs
+        .metadata(&gitignore)
+        .is_ok_and(|metadata| metadata.is_file())
+      {
+        gitignores.push(gitignore);
+      }
+
+      let git = dir.join(".git");
+      let Ok(metadata) = self.fs.metadata(&git) else {
+        if self.fs.is_boundary(dir) || self.ends_walk_at(dir, depth) || root.as_deref() == Some(dir)
+        {
+          break;
+        }
+        continue;
+      };
+
+      if metadata.is_dir() {
+        let exclude = git.join("info/exclude");
+        if self
+          .fs
+          .metadata(&exclude)
+          .is_ok_and(|metadata| metadata.is_file())
+        {
+          gitignores.push(exclude);
+        }
+      }
+
+      gitignores.reverse();
+      return gitignores;
+    }
+
+    vec![]
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  #[test]
+  fn should_return_ignore_files_up_to_the_repository_root() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/.gitignore", "")
+        .with_file("/repo/.git/info/exclude", "")
+        .with_file("/repo/.gitignore", "")
+        .with_file("/repo/app/.gitignore", "")
+        .with_dir("/repo/app/src/lib"),
+    );
+
+    let in_repo = UpFinder::builder()
+      .cwd("/repo/app/src/lib")
+      .fs(fs.clone())
+      .build();
+
+    assert_eq!(
+      in_repo.find_up_gitignores(),
+      vec![
+        PathBuf::from("/repo/.git/info/exclude"),
+        PathBuf::from("/repo/.gitignore"),
+        PathBuf::from("/repo/app/.gitignore"),
+      ]
+    );
+
+    let below_root = UpFinder::builder()
+      .cwd("/repo/app/src/lib")
+      .max_depth(2)
+      .fs(fs.clone())
+      .build();
+
+    assert!(below_root.find_up_gitignores().is_empty());
+
+    let below_root = UpFinder::builder()
+      .cwd("/repo/app/src/lib")
+      .restrict_to("/repo/app")
+      .fs(fs.clone())
+      .build();
+
+    assert!(below_root.find_up_gitignores().is_empty());
+
+    let outside = UpFinder::builder().cwd("/").fs(fs).build();
+
+    assert!(outside.find_up_gitignores().is_empty());
+  }
+}
@@ -0,0 +1,459 @@
+use std::path::{Path, PathBuf};
+
+use crate::{FindUpMatch, SearchReport, UpFinder, levels::Levels};
+
+/// A shell-style pattern for names, such as `.eslintrc.*` or `*.lock`.
+///
+/// `*` matches any run of characters, `?` any single character, `[abc]` and
+/// `[a-z]` one of the characters in the brackets and `[!abc]` one that isn't,
+/// and `\` makes the next character literal. Patterns apply to whole names,
+/// and `*` and `?` match a leading `.` too. An unclosed `[` is literal.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::Glob;
+///
+/// let glob = Glob::new(".eslintrc.*");
+///
+/// assert!(glob.matches(".eslintrc.json"));
+/// assert!(!glob.matches(".eslintrc"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+  pattern: String,
+  tokens: Vec<Token>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+  Char(char),
+  Any,
+  AnyRun,
+  Class {
+    negated: bool,
+    ranges: Vec<(char, char)>,
+  },
+}
+
+impl Glob {
+  pub fn new(pattern: impl Into<String>) -> Self {
+    let pattern = pattern.into();
+    let tokens = tokenize(&pattern);
+
+    Self { pattern, tokens }
+  }
+
+  pub fn as_str(&self) -> &str {
+    &self.pattern
+  }
+
+  /// Whether `name` matches the whole pattern.
+  pub fn matches(&self, name: &str) -> bool {
+    let name: Vec<char> = name.chars().collect();
+
+    matches(&self.tokens, &name)
+  }
+
+  /// Whether `pattern` uses any of the special characters, so callers taking
+  /// names and patterns alike can tell them apart.
+  pub fn is_pattern(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '\\'])
+  }
+}
+
+fn tokenize(pattern: &str) -> Vec<Token> {
+  let chars: Vec<char> = pattern.chars().collect();
+  let mut tokens = vec![];
+  let mut i = 0;
+
+  while i < chars.len() {
+    match chars[i] {
+      '*' => tokens.push(Token::AnyRun),
+      '?' => tokens.push(Token::Any),
+      '\\' if i + 1 < chars.len() => {
+        i += 1;
+        tokens.push(Token::Char(chars[i]));
+      }
+      '[' => match class(&chars[i + 1..]) {
+        Some((token, len)) => {
+          tokens.push(token);
+          i += len;
+        }
+        None => tokens.push(Token::Char('[')),
+      },
+      c => tokens.push(Token::Char(c)),
+    }
+    i += 1;
+  }
+
+  tokens
+}
+
+/// The class starting after a `[`, with the number of characters it takes up
+/// including the closing `]`.
+fn class(chars: &[char]) -> Option<(Token, usize)> {
+  let negated = matches!(chars.first(), Some('!' | '^'));
+  let mut i = usize::from(negated);
+  let mut ranges = vec![];
+
+  // A `]` right after the opening bracket is part of the class.
+  while let Some(&c) = chars.get(i) {
+    if c == ']' && !ranges.is_empty() {
+      return Some((Token::Class { negated, ranges }, i + 1));
+    }
+    match (chars.get(i + 1), chars.get(i + 2)) {
+      (Some('-'), Some(&end)) if end != ']' => {
+        ranges.push((c, end));
+        i += 3;
+      }
+      _ => {
+        ranges.push((c, c));
+        i += 1;
+      }
+    }
+  }
+
+  None
+}
+
+fn matches(tokens: &[Token], name: &[char]) -> bool {
+  // Backtracking only to the last `*`, which is enough for names.
+  let (mut t, mut n) = (0, 0);
+  let mut restart = None;
+
+  while n < name.len() {
+    match tokens.get(t) {
+      Some(Token::AnyRun) => {
+        restart = Some((t, n));
+        t += 1;
+        continue;
+      }
+      Some(token) if token_matches(token, name[n]) => {
+        t += 1;
+        n += 1;
+        continue;
+      }
+      _ => {}
+    }
+    match restart {
+      Some((star, from)) => {
+        t = star + 1;
+        n = from + 1;
+        restart = Some((star, from + 1));
+      }
+      None => return false,
+    }
+  }
+
+  tokens[t..].iter().all(|token| *token == Token::AnyRun)
+}
+
+fn token_matches(token: &Token, c: char) -> bool {
+  match token {
+    Token::Char(expected) => *expected == c,
+    Token::Any => true,
+    Token::AnyRun => false,
+    Token::Class { negated, ranges } => {
+      ranges
+        .iter()
+        .any(|&(start, end)| (start..=end).contains(&c))
+        != *negated
+    }
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the entries matching `glob` in the current working directory and all
+  /// parent directories, nearest first and sorted by name on every level.
+  ///
+  /// Every level is listed, so this costs more than looking for fixed names.
+  /// The levels are those [`UpFinder::find_up`] would search, but only the
+  /// levels themselves are listed, not their `.config` or other probe
+  /// directories, and the candidate hook, the fallback and the override
+  /// variable don't apply.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::{Glob, UpFinder};
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  /// let paths = find_up.find_up_glob(&Glob::new("Cargo.*"));
+  ///
+  /// println!("{:#?}", paths);
+  /// ```
+  pub fn find_up_glob(&self, glob: &Glob) -> Vec<PathBuf> {
+    self
+      .find_up_glob_matches(glob)
+      .into_iter()
+      .map(FindUpMatch::into_path)
+      .collect()
+  }
+
+  /// Like [`UpFinder::find_up_glob`], returning the depth of every match.
+  pub fn find_up_glob_matches(&self, glob: &Glob) -> Vec<FindUpMatch> {
+    self.find_up_listed(|name| glob.matches(name))
+  }
+
+  /// Find the entries whose names `regex` matches, like
+  /// [`UpFinder::find_up_glob`]. The regex is used as is, anchor it with `^`
+  /// and `$` to match whole names.
+  #[cfg(feature = "regex")]
+  pub fn find_up_regex(&self, regex: &regex::Regex) -> Vec<PathBuf> {
+    self
+      .find_up_regex_matches(regex)
+      .into_iter()
+      .map(FindUpMatch::into_path)
+      .collect()
+  }
+
+  /// Like [`UpFinder::find_up_regex`], returning the depth of every match.
+  #[cfg(feature = "regex")]
+  pub fn find_up_regex_matches(&self, regex: &regex::Regex) -> Vec<FindUpMatch> {
+    self.find_up_listed(|name| regex.is_match(name))
+  }
+
+  /// The entries of every level whose UTF-8 names satisfy `is_match`.
+  fn find_up_listed(&self, is_match: impl Fn(&str) -> bool) -> Vec<FindUpMatch> {
+    let mut found = vec![];
+    let mut report = SearchReport::default();
+
+    let Ok(mut levels) = Levels::new(self, self.cwd.as_ref().to_path_buf(), false) else {
+      return found;
+    };
+
+    while let Some((dir, depth)) = levels.next_dir() {
+      report.level(dir, depth);
+
+      // Like `find_up`, a search that could lead outside of `restrict_to`
+      // finds nothing.
+      let mut level = match levels.enter(&mut report) {
+        Ok(Some(level)) => level,
+        Ok(None) => break,
+        Err(_) => return vec![],
+      };
+      let dir = level.dir.as_path();
+
+      // The empty path is what walking up a relative path ends with.
+      let listed = if dir.as_os_str().is_empty() {
+        Path::new(".")
+      } else {
+        dir
+      };
+
+      let mut names: Vec<_> = level
+        .access
+        .run(&self.fs.0, dir, listed, |fs, dir| fs.read_dir(dir))
+        .and_then(Result::ok)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|name| name.to_str().is_some_and(&is_match))
+        .collect();
+      names.sort();
+
+      for path in names.into_iter().map(|name| dir.join(name)) {
+        match level
+          .access
+          .run(&self.fs.0, dir, &path, |fs, path| fs.metadata(path))
+        {
+          Some(Ok(metadata)) if self.is_kind(&metadata) => {
+            if level.restricted
+              && level
+                .access
+                .run(&self.fs.0, dir, &path, |fs, path| fs.reparse_point(path))
+                .is_some_and(|reparse_point| reparse_point.ok().flatten().is_some())
+            {
+              return vec![];
+            }
+            found.push(FindUpMatch::at_depth(path, level.depth));
+          }
+          Some(_) => {}
+          None => break,
+        }
+      }
+
+      levels.leave(&level, &mut report);
+    }
+
+    found
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::{MemoryFs, ReparsePoint};
+
+  #[test]
+  fn should_match_whole_names() {
+    let cases = [
+      (".eslintrc.*", ".eslintrc.json", true),
+      (".eslintrc.*", ".eslintrc", false),
+      ("*.lock", "Cargo.lock", true),
+      ("*.lock", "Cargo.lock.bak", false),
+      ("*rc*", ".npmrc", true),
+      ("file?.txt", "file1.txt", true),
+      ("file?.txt", "file10.txt", false),
+      ("[Mm]akefile", "makefile", true),
+      ("[!M]akefile", "Makefile", false),
+      ("v[0-9].json", "v7.json", true),
+      ("[]].txt", "].txt", true),
+      ("\\*.txt", "*.txt", true),
+      ("\\*.txt", "a.txt", false),
+      ("[unclosed", "[unclosed", true),
+      ("*", "", true),
+      ("a*b*c", "aXbYbZc", true),
+    ];
+
+    for (pattern, name, expected) in cases {
+      assert_eq!(
+        Glob::new(pattern).matches(name),
+        expected,
+        "{pattern} {name}"
+      );
+    }
+  }
+
+  fn fs() -> Arc<MemoryFs> {
+    Arc::new(
+      MemoryFs::new()
+        .with_file("/repo/.eslintrc.json", "")
+        .with_file("/repo/.eslintrc.yml", "")
+        .with_dir("/repo/.eslintrc.d")
+        .with_file("/repo/app/.eslintrc.cjs", "")
+        .with_dir("/repo/app/src"),
+    )
+  }
+
+  #[test]
+  fn should_find_matches_on_every_level() {
+    let find_up = UpFinder::builder().cwd("/repo/app/src").fs(fs()).build();
+
+    let found = find_up.find_up_glob_matches(&Glob::new(".eslintrc.*"));
+
+    assert_eq!(
+      found
+        .iter()
+        .map(|found| (found.depth(), found.path()))
+        .collect::<Vec<_>>(),
+      vec![
+        (1, Path::new("/repo/app/.eslintrc.cjs")),
+        (2, Path::new("/repo/.eslintrc.json")),
+        (2, Path::new("/repo/.eslintrc.yml")),
+      ]
+    );
+  }
+
+  #[test]
+  fn should_not_list_levels_above_stop_at_or_max_depth() {
+    let glob = Glob::new(".eslintrc.*");
+    let stopped = UpFinder::builder()
+      .cwd("/repo/app/src")
+      .stop_at("/repo/app")
+      .fs(fs())
+      .build();
+    let limited = UpFinder::builder()
+      .cwd("/repo/app/src")
+      .max_depth(1)
+      .fs(fs())
+      .build();
+
+    assert_eq!(
+      stopped.find_up_glob(&glob),
+      [PathBuf::from("/repo/app/.eslintrc.cjs")]
+    );
+    assert_eq!(
+      limited.find_up_glob(&glob),
+      [PathBuf::from("/repo/app/.eslintrc.cjs")]
+    );
+  }
+
+  #[cfg(feature = "regex")]
+  #[test]
+  fn should_find_regex_matches() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/yarn.lock", "")
+      .with_file("/repo/pnpm-lock.yaml", "")
+      .with_dir("/repo/app");
+    let find_up = UpFinder::builder()
+      .cwd("/repo/app")
+      .fs(Arc::new(fs))
+      .build();
+
+    assert_eq!(
+      find_up.find_up_regex(&regex::Regex::new(r"^[a-z]+(-lock\.yaml|\.lock)$").unwrap()),
+      vec![
+        PathBuf::from("/repo/pnpm-lock.yaml"),
+        PathBuf::from("/repo/yarn.lock")
+      ]
+    );
+  }
+
+  #[test]
+  fn should_list_the_levels_the_walk_searches() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/.eslintrc.json", "")
+      .with_file("/repo/node_modules/lib/.eslintrc.json", "")
+      .with_dir("/repo/node_modules/lib/src");
+    let find_up = UpFinder::builder()
+      .cwd("/repo/node_modules/lib/src")
+      .exclude_dependency_dirs(true)
+      .fs(Arc::new(fs))
+      .build();
+
+    assert_eq!(
+      find_up.find_up_glob(&Glob::new(".eslintrc.*")),
+      [PathBuf::from("/repo/.eslintrc.json")]
+    );
+  }
+
+  #[test]
+  fn should_list_the_current_directory_at_the_end_of_relative_paths() {
+    let find_up = UpFinder::builder().cwd("src").max_depth(1).build();
+
+    assert_eq!(
+      find_up.find_up_glob(&Glob::new("Cargo.toml")),
+      [PathBuf::from("Cargo.toml")]
+    );
+  }
+
+  #[test]
+  fn should_not_list_levels_above_the_restricted_root() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/srv/Cargo.toml", "")
+        .with_file("/srv/app/Cargo.toml", "")
+        .with_dir("/srv/app/src"),
+    );
+    let restricted = |root: &str| {
+      UpFinder::builder()
+        .cwd("/srv/app/src")
+        .restrict_to(root)
+        .fs(fs.clone())
+        .build()
+        .find_up_glob(&Glob::new("Cargo.*"))
+    };
+
+    assert_eq!(
+      restricted("/srv/app"),
+      [PathBuf::from("/srv/app/Cargo.toml")]
+    );
+    assert!(restricted("/other").is_empty());
+
+    let linked = UpFinder::builder()
+      .cwd("/srv/app/src")
+      .restrict_to("/srv")
+      .fs(Arc::new(
+        MemoryFs::new()
+          .with_file("/srv/app/Cargo.toml", "")
+          .with_reparse_point("/srv/app/Cargo.toml", ReparsePoint::Symlink)
+          .with_dir("/srv/app/src"),
+      ))
+      .build();
+
+    assert!(linked.find_up_glob(&Glob::new("Cargo.*")).is_empty());
+  }
+}
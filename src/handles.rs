@@ -0,0 +1,330 @@
+use std::{
+  ffi::{CStr, CString, OsStr},
+  fs::{self, File},
+  io,
+  mem::MaybeUninit,
+  os::{
+    fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+    unix::ffi::OsStrExt,
+  },
+  path::{Path, PathBuf},
+};
+
+use crate::{FindUpError, FindUpKind, UpFinder};
+
+/// A match opened during the walk, see [`UpFinder::find_up_handles`].
+#[derive(Debug)]
+pub struct FoundHandle {
+  path: PathBuf,
+  handle: OwnedFd,
+}
+
+impl FoundHandle {
+  /// The path of the match from the canonical start directory, for display
+  /// purposes only.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// The opened file or directory.
+  pub fn handle(&self) -> &OwnedFd {
+    &self.handle
+  }
+
+  pub fn into_file(self) -> File {
+    File::from(self.handle)
+  }
+
+  pub fn into_parts(self) -> (PathBuf, OwnedFd) {
+    (self.path, self.handle)
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find `name` by walking up with `openat` and return the opened matches.
+  ///
+  /// Every ancestor is reached through `..` of an already opened directory and
+  /// every candidate is opened relative to it before its type is checked, so
+  /// what was matched is exactly what is returned; nothing can be swapped in
+  /// between. It ends at the root, where `..` is the directory itself, and
+  /// `stop_at` and `restrict_to` are compared with the canonical path of each
+  /// level. A start outside of `restrict_to` is an error wrapping
+  /// [`FindUpError::OutsideRoot`](crate::FindUpError::OutsideRoot). The search
+  /// always runs against the real filesystem, the configured
+  /// [`Fs`](crate::Fs) and network mount policy are not consulted.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::io::Read;
+  ///
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// for found in find_up.find_up_handles("Cargo.toml").unwrap() {
+  ///   let mut contents = String::new();
+  ///   found.into_file().read_to_string(&mut contents).unwrap();
+  /// }
+  /// ```
+  pub fn find_up_handles(&self, name: &str) -> io::Result<Vec<FoundHandle>> {
+    let name = CString::new(name)?;
+
+    let mut dir = open_at(libc::AT_FDCWD, &c_path(self.cwd.as_ref())?, DIR_FLAGS)?;
+    let mut dir_id = file_id(dir.as_raw_fd())?;
+    // The paths are derived from the canonical start, so that every `..`
+    // walked up is a parent in them too.
+    let mut path = fs::canonicalize(self.cwd.as_ref())?;
+    let stop_at = self
+      .stop_at
+      .as_deref()
+      .and_then(|stop_at| fs::canonicalize(stop_at).ok());
+
+    let root = match &self.restrict_to {
+      Some(root) => Some(fs::canonicalize(root)?),
+      None => None,
+    };
+    if let Some(root) = root.as_ref().filter(|root| !path.starts_with(root)) {
+      return Err(io::Error::other(FindUpError::OutsideRoot {
+        path,
+        root: root.clone(),
+      }));
+    }
+
+    let mut found = vec![];
+    let mut depth = 0;
+
+    loop {
+      if let Some(handle) = self.open_candidate(dir.as_raw_fd(), &name)? {
+        found.push(FoundHandle {
+          path: path.join(OsStr::from_bytes(name.as_bytes())),
+          handle,
+        });
+      }
+
+      if stop_at.as_deref() == Some(&*path)
+        || root.as_deref() == Some(&*path)
+        || self.max_depth.is_some_and(|max_depth| depth >= max_depth)
+      {
+        break;
+      }
+
+      let parent = open_at(dir.as_raw_fd(), c"..", DIR_FLAGS)?;
+      let parent_id = file_id(parent.as_raw_fd())?;
+
+      // `..` of the root is the root itself.
+      if parent_id == dir_id {
+        break;
+      }
+
+      // A directory moved since the start may put more levels above it than
+      // the path has, they are all reported at its root.
+      if let Some(parent_path) = path.parent() {
+        path = parent_path.to_path_buf();
+      }
+      dir = parent;
+      dir_id = parent_id;
+      depth += 1;
+    }
+
+    Ok(found)
+  }
+
+  fn open_candidate(&self, dir: RawFd, name: &CString) -> io::Result<Option<OwnedFd>> {
+    let flags = match self.kind {
+      FindUpKind::File | FindUpKind::Any => FILE_FLAGS,
+      FindUpKind::Dir => DIR_FLAGS,
+    };
+
+    let handle = match open_at(dir, name, flags) {
+      Ok(handle) => handle,
+      Err(error) if is_missing(&error) => return Ok(None),
+      Err(error) => return Err(error),
+    };
+
+    let mode = file_mode(handle.as_raw_fd())?;
+
+    let matches_criteria = match self.kind {
+      FindUpKind::File => mode & libc::S_IFMT == libc::S_IFREG,
+      FindUpKind::Dir => mode & libc::S_IFMT == libc::S_IFDIR,
+      FindUpKind::Any => true,
+    };
+
+    if !matches_criteria {
+      return Ok(None);
+    }
+
+    if mode & libc::S_IFMT == libc::S_IFREG {
+      // `O_NONBLOCK` only guarded the open against FIFOs, drop it again.
+      clear_nonblocking(handle.as_raw_fd())?;
+    }
+
+    Ok(Some(handle))
+  }
+}
+
+const DIR_FLAGS: libc::c_int = libc::O_RDONLY | libc::O_DIRECTORY | libc::O_CLOEXEC;
+
+/// `O_NONBLOCK` keeps a FIFO named like the candidate from blocking the open.
+const FILE_FLAGS: libc::c_int =
+  libc::O_RDONLY | libc::O_CLOEXEC | libc::O_NOCTTY | libc::O_NONBLOCK;
+
+fn c_path(path: &Path) -> io::Result<CString> {
+  // The empty path is what walking up a relative path ends with.
+  if path.as_os_str().is_empty() {
+    return Ok(CString::from(c"."));
+  }
+
+  Ok(CString::new(path.as_os_str().as_bytes())?)
+}
+
+fn open_at(dir: RawFd, name: &CStr, flags: libc::c_int) -> io::Result<OwnedFd> {
+  // SAFETY: `name` is NUL terminated.
+  let fd = unsafe { libc::openat(dir, name.as_ptr(), flags) };
+
+  if fd == -1 {
+    return Err(io::Error::last_os_error());
+  }
+
+  // SAFETY: `openat` returned a new descriptor that nothing else owns.
+  Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+fn clear_nonblocking(fd: RawFd) -> io::Result<()> {
+  // SAFETY: `fd` is an open file descriptor.
+  let status = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+
+  // SAFETY: as above.
+  if status == -1 || unsafe { libc::fcntl(fd, libc::F_SETFL, status & !libc::O_NONBLOCK) } == -1 {
+    return Err(io::Error::last_os_error());
+  }
+
+  Ok(())
+}
+
+fn stat(fd: RawFd) -> io::Result<libc::stat> {
+  let mut stat = MaybeUninit::<libc::stat>::uninit();
+
+  // SAFETY: `stat` is valid for writes.
+  if unsafe { libc::fstat(fd, stat.as_mut_ptr()) } == -1 {
+    return Err(io::Error::last_os_error());
+  }
+
+  // SAFETY: `fstat` returned successfully, so `stat` is initialized.
+  Ok(unsafe { stat.assume_init() })
+}
+
+fn file_id(fd: RawFd) -> io::Result<(u64, u64)> {
+  let stat = stat(fd)?;
+  #[allow(clippy::unnecessary_cast)]
+  Ok((stat.st_dev as u64, stat.st_ino as u64))
+}
+
+fn file_mode(fd: RawFd) -> io::Result<libc::mode_t> {
+  Ok(stat(fd)?.st_mode)
+}
+
+/// Errors that mean "nothing usable here" rather than a failed search.
+fn is_missing(error: &io::Error) -> bool {
+  matches!(
+    error.raw_os_error(),
+    Some(libc::ENOENT | libc::ENOTDIR | libc::ELOOP | libc::EACCES | libc::ENXIO)
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Read;
+
+  use super::*;
+
+  #[test]
+  fn should_return_opened_files() {
+    let up_finder = UpFinder::builder().cwd("fixtures/a/b/c/d").build();
+
+    let found = up_finder.find_up_handles(".node-version").unwrap();
+
+    assert_eq!(found.len(), 1);
+    assert!(found[0].path().ends_with("fixtures/a/b/c/.node-version"));
+
+    let mut contents = String::new();
+    let mut expected = String::new();
+    found
+      .into_iter()
+      .next()
+      .unwrap()
+      .into_file()
+      .read_to_string(&mut contents)
+      .unwrap();
+    File::open("fixtures/a/b/c/.node-version")
+      .unwrap()
+      .read_to_string(&mut expected)
+      .unwrap();
+
+    assert_eq!(contents, expected);
+  }
+
+  #[test]
+  fn should_only_return_directories_for_dir_kind() {
+    let up_finder = UpFinder::builder()
+      .cwd("fixtures/a/b/c/d")
+      .kind(FindUpKind::Dir)
+      .build();
+
+    assert!(
+      up_finder
+        .find_up_handles("package.json")
+        .unwrap()
+        .is_empty()
+    );
+    assert_eq!(up_finder.find_up_handles("b").unwrap().len(), 1);
+  }
+
+  #[test]
+  fn should_report_paths_from_the_canonical_start() {
+    let up_finder = UpFinder::builder()
+      .cwd("fixtures/a/b/c/d/..")
+      .stop_at("fixtures/a/b")
+      .build();
+
+    let paths: Vec<PathBuf> = up_finder
+      .find_up_handles("package.json")
+      .unwrap()
+      .into_iter()
+      .map(|found| found.path().to_path_buf())
+      .collect();
+
+    let fixtures = fs::canonicalize("fixtures").unwrap();
+    assert_eq!(
+      paths,
+      [
+        fixtures.join("a/b/c/package.json"),
+        fixtures.join("a/b/package.json"),
+      ]
+    );
+  }
+
+  #[test]
+  fn should_not_walk_above_the_root() {
+    let up_finder = UpFinder::builder()
+      .cwd("fixtures/a/b/c/d")
+      .restrict_to("fixtures/a/b/c")
+      .build();
+
+    let found = up_finder.find_up_handles("package.json").unwrap();
+
+    assert_eq!(found.len(), 2);
+    assert!(found[1].path().ends_with("fixtures/a/b/c/package.json"));
+
+    let outside = UpFinder::builder()
+      .cwd("fixtures/a")
+      .restrict_to("fixtures/a/b")
+      .build();
+    let error = outside.find_up_handles("package.json").unwrap_err();
+
+    assert!(matches!(
+      error.get_ref().and_then(|error| error.downcast_ref()),
+      Some(FindUpError::OutsideRoot { .. })
+    ));
+  }
+}
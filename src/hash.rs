@@ -0,0 +1,84 @@
+//! The hash maps of the crate, the same type with or without the `rustc-hash`
+//! feature, which only changes the hash function behind [`BuildHasher`].
+
+use std::{fmt, hash};
+
+#[cfg(feature = "rustc-hash")]
+type Inner = rustc_hash::FxBuildHasher;
+#[cfg(not(feature = "rustc-hash"))]
+type Inner = std::collections::hash_map::RandomState;
+
+/// The [`BuildHasher`](hash::BuildHasher) of the maps the crate returns, e.g.
+/// by [`UpFinder::find_up_multi`](crate::UpFinder::find_up_multi). It builds
+/// the fast `FxHasher` of `rustc-hash` with the `rustc-hash` feature, and the
+/// `DefaultHasher` of `std` without it.
+///
+/// # Example
+///
+/// ```rust
+/// use std::{collections::HashMap, path::PathBuf};
+///
+/// use up_finder::{BuildHasher, UpFinder};
+///
+/// let find_up = UpFinder::builder().cwd(".").build();
+/// let paths: HashMap<&str, Vec<PathBuf>, BuildHasher> = find_up.find_up_multi(&["Cargo.toml"]);
+///
+/// println!("{:#?}", paths);
+/// ```
+#[derive(Clone, Default)]
+pub struct BuildHasher(Inner);
+
+impl fmt::Debug for BuildHasher {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("BuildHasher").finish_non_exhaustive()
+  }
+}
+
+impl hash::BuildHasher for BuildHasher {
+  type Hasher = Hasher;
+
+  fn build_hasher(&self) -> Hasher {
+    Hasher(self.0.build_hasher())
+  }
+}
+
+/// The [`Hasher`](hash::Hasher) built by [`BuildHasher`].
+pub struct Hasher(<Inner as hash::BuildHasher>::Hasher);
+
+impl fmt::Debug for Hasher {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Hasher").finish_non_exhaustive()
+  }
+}
+
+/// Forward the integer methods too, `FxHasher` has faster versions of them.
+macro_rules! forward {
+  ($($name:ident: $ty:ty,)*) => {
+    $(
+      fn $name(&mut self, value: $ty) {
+        self.0.$name(value);
+      }
+    )*
+  };
+}
+
+impl hash::Hasher for Hasher {
+  fn finish(&self) -> u64 {
+    self.0.finish()
+  }
+
+  fn write(&mut self, bytes: &[u8]) {
+    self.0.write(bytes);
+  }
+
+  forward! {
+    write_u8: u8,
+    write_u16: u16,
+    write_u32: u32,
+    write_u64: u64,
+    write_usize: usize,
+  }
+}
+
+pub(crate) type HashMap<K, V> = std::collections::HashMap<K, V, BuildHasher>;
+pub(crate) type HashSet<T> = std::collections::HashSet<T, BuildHasher>;
@@ -0,0 +1,73 @@
+use std::{fmt, path::Path, sync::Arc};
+
+/// What the walk does after an [`on_enter_dir`](crate::UpFinderBuilder::on_enter_dir)
+/// or [`on_candidate`](crate::UpFinderBuilder::on_candidate) hook.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{HookResult, UpFinder};
+///
+/// let find_up = UpFinder::builder()
+///   .cwd(".")
+///   .on_enter_dir(|dir, _| {
+///     if dir.ends_with("vendor") {
+///       HookResult::Skip
+///     } else {
+///       HookResult::Continue
+///     }
+///   })
+///   .on_candidate(|path| HookResult::Annotate(format!("seen {}", path.display())))
+///   .build();
+///
+/// for found in find_up.find_up_matches("package.json") {
+///   println!("{:?}", found.annotations());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookResult {
+  /// Go on as without the hook.
+  Continue,
+  /// Skip the directory or candidate, the walk goes on with the next one.
+  Skip,
+  /// Go on, attaching the note to the matches of the directory or to the
+  /// match of the candidate, see [`FindUpMatch::annotations`](crate::FindUpMatch::annotations).
+  Annotate(String),
+}
+
+type DirHookFn = dyn Fn(&Path, usize) -> HookResult + Send + Sync;
+type CandidateHookFn = dyn Fn(&Path) -> HookResult + Send + Sync;
+
+/// The [`on_enter_dir`](crate::UpFinderBuilder::on_enter_dir) hook.
+#[derive(Clone)]
+pub struct DirHook(pub(crate) Arc<DirHookFn>);
+
+impl fmt::Debug for DirHook {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("DirHook")
+  }
+}
+
+/// Hooks are equal if they are the same closure.
+impl PartialEq for DirHook {
+  fn eq(&self, other: &Self) -> bool {
+    Arc::ptr_eq(&self.0, &other.0)
+  }
+}
+
+/// The [`on_candidate`](crate::UpFinderBuilder::on_candidate) hook.
+#[derive(Clone)]
+pub struct CandidateHook(pub(crate) Arc<CandidateHookFn>);
+
+impl fmt::Debug for CandidateHook {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("CandidateHook")
+  }
+}
+
+/// Hooks are equal if they are the same closure.
+impl PartialEq for CandidateHook {
+  fn eq(&self, other: &Self) -> bool {
+    Arc::ptr_eq(&self.0, &other.0)
+  }
+}
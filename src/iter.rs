@@ -0,0 +1,592 @@
+use std::{
+  collections::VecDeque,
+  path::{Path, PathBuf},
+};
+
+use crate::{
+  ConfigTier, FindUpMatch, FindUpResult, SearchReport, UpFinder,
+  hash::HashMap,
+  levels::{Level, Levels, WalkMatches},
+  metrics, trace,
+};
+
+/// The levels a search with `up_finder` goes through, nearest first, entered
+/// but not searched yet. A walk that fails has no levels at all, like a
+/// search that fails has no matches.
+fn plan_levels(up_finder: &UpFinder<&Path>, report: &mut SearchReport) -> VecDeque<Level> {
+  let mut planned = VecDeque::new();
+  // The hook is left to the searches of the levels, which may never come.
+  let Ok(levels) = Levels::new(up_finder, up_finder.cwd.to_path_buf(), true) else {
+    return planned;
+  };
+  let mut levels = levels.defer_hook();
+
+  while let Some((dir, depth)) = levels.next_dir() {
+    report.level(dir, depth);
+
+    match levels.enter(report) {
+      Ok(Some(level)) => {
+        levels.leave(&level, report);
+        planned.push_back(level);
+      }
+      Ok(None) => break,
+      Err(_) => return VecDeque::new(),
+    }
+  }
+
+  planned
+}
+
+/// The matches of every name on the planned `level`.
+fn search_level<'a>(
+  up_finder: &UpFinder<&Path>,
+  names: &[&'a str],
+  mut level: Level,
+  report: &mut SearchReport,
+) -> HashMap<&'a str, Vec<FindUpMatch>> {
+  let mut matches = WalkMatches::new(names);
+
+  match up_finder.search_level(
+    &mut level,
+    names,
+    &mut FindUpResult::Saved,
+    &mut matches,
+    true,
+    report,
+  ) {
+    Ok(_) => matches.paths,
+    Err(_) => HashMap::default(),
+  }
+}
+
+/// [`UpFinder`] without the [`ConfigFallback`](crate::ConfigFallback), for
+/// searches of single levels.
+pub(crate) fn without_fallback<P: AsRef<Path>>(up_finder: &UpFinder<P>) -> UpFinder<&Path> {
+  UpFinder {
+    config_fallback: None,
+    ..up_finder.borrowed()
+  }
+}
+
+/// A search done a level at a time, with one span for all of its parts and
+/// recorded as one search once it is over.
+#[derive(Debug)]
+pub(crate) struct PartedSearch {
+  pub(crate) span: trace::PartedSearch,
+  pub(crate) report: SearchReport,
+}
+
+impl PartedSearch {
+  /// The search of `names` with `up_finder`, the match named by its
+  /// `env_override` if there is one, and its planned levels otherwise.
+  pub(crate) fn plan(
+    up_finder: &UpFinder<&Path>,
+    names: &[&str],
+  ) -> (Self, Option<FindUpMatch>, VecDeque<Level>) {
+    let mut search = Self {
+      span: trace::PartedSearch::new(up_finder.cwd, names),
+      report: SearchReport::default(),
+    };
+
+    let (overridden, levels) = search.part(|report| match up_finder.env_override_match(report) {
+      Some(path) => (
+        Some(FindUpMatch::outside_walk(path, ConfigTier::Override)),
+        VecDeque::new(),
+      ),
+      None => (None, plan_levels(up_finder, report)),
+    });
+
+    (search, overridden, levels)
+  }
+
+  /// The matches of every name on the planned `level`.
+  pub(crate) fn search<'a>(
+    &mut self,
+    up_finder: &UpFinder<&Path>,
+    names: &[&'a str],
+    mut level: Level,
+  ) -> HashMap<&'a str, Vec<FindUpMatch>> {
+    self.part(|report| {
+      level.call_hook(up_finder);
+      search_level(up_finder, names, level, report)
+    })
+  }
+
+  /// Run a part of the search in its span, adding the time it took to that
+  /// of the search.
+  fn part<T>(&mut self, part: impl FnOnce(&mut SearchReport) -> T) -> T {
+    let _span = self.span.enter();
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let start = std::time::Instant::now();
+
+    let result = part(&mut self.report);
+
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    {
+      self.report.elapsed = Some(self.report.elapsed.unwrap_or_default() + start.elapsed());
+    }
+
+    result
+  }
+}
+
+impl Drop for PartedSearch {
+  fn drop(&mut self) {
+    metrics::record(&self.report);
+  }
+}
+
+/// The matches of a search, found one level at a time from either end, see
+/// [`UpFinder::find_up_iter`].
+#[derive(Debug)]
+pub struct FindUpIter<'a> {
+  up_finder: UpFinder<&'a Path>,
+  name: String,
+  levels: VecDeque<Level>,
+  front: VecDeque<FindUpMatch>,
+  back: VecDeque<FindUpMatch>,
+  search: PartedSearch,
+}
+
+impl FindUpIter<'_> {
+  /// The matches on `level`.
+  fn search(&mut self, level: Level) -> VecDeque<FindUpMatch> {
+    let name = self.name.as_str();
+
+    self
+      .search
+      .search(&self.up_finder, &[name], level)
+      .remove(name)
+      .unwrap_or_default()
+      .into()
+  }
+}
+
+impl Iterator for FindUpIter<'_> {
+  type Item = FindUpMatch;
+
+  fn next(&mut self) -> Option<FindUpMatch> {
+    while self.front.is_empty() {
+      let Some(level) = self.levels.pop_front() else {
+        return self.back.pop_front();
+      };
+      self.front = self.search(level);
+    }
+
+    self.front.pop_front()
+  }
+
+  fn count(mut self) -> usize {
+    let levels = std::mem::take(&mut self.levels);
+
+    levels
+      .into_iter()
+      .fold(self.front.len() + self.back.len(), |count, level| {
+        count + self.search(level).len()
+      })
+  }
+}
+
+impl DoubleEndedIterator for FindUpIter<'_> {
+  fn next_back(&mut self) -> Option<FindUpMatch> {
+    while self.back.is_empty() {
+      let Some(level) = self.levels.pop_back() else {
+        return self.front.pop_back();
+      };
+      self.back = self.search(level);
+    }
+
+    self.back.pop_back()
+  }
+}
+
+/// A level of the walk and what matched there, see [`UpFinder::iter_levels`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FindUpLevel {
+  dir: PathBuf,
+  depth: usize,
+  matches: Vec<(String, FindUpMatch)>,
+}
+
+impl FindUpLevel {
+  /// The directory of the level.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// How many levels above the start directory the level is.
+  pub fn depth(&self) -> usize {
+    self.depth
+  }
+
+  /// The matches on the level with the names they matched, in the order of
+  /// the names.
+  pub fn matches(&self) -> &[(String, FindUpMatch)] {
+    &self.matches
+  }
+
+  /// The matches of `name` on the level.
+  pub fn matches_of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a FindUpMatch> {
+    self
+      .matches
+      .iter()
+      .filter(move |(matched, _)| matched == name)
+      .map(|(_, found)| found)
+  }
+}
+
+/// The levels of a search, searched one at a time from either end, see
+/// [`UpFinder::iter_levels`].
+#[derive(Debug)]
+pub struct LevelIter<'a> {
+  up_finder: UpFinder<&'a Path>,
+  names: Vec<String>,
+  levels: VecDeque<Level>,
+  /// The start level with the match named by the `env_override`, the only
+  /// level if there is one.
+  overridden: Option<FindUpLevel>,
+  search: PartedSearch,
+}
+
+impl LevelIter<'_> {
+  fn search(&mut self, level: Level) -> FindUpLevel {
+    let names: Vec<&str> = self.names.iter().map(String::as_str).collect();
+    let dir = level.dir.clone();
+    let depth = level.depth;
+    let mut found = self.search.search(&self.up_finder, &names, level);
+
+    FindUpLevel {
+      matches: names
+        .iter()
+        .flat_map(|&name| {
+          found
+            .remove(name)
+            .unwrap_or_default()
+            .into_iter()
+            .map(move |found| (name.to_owned(), found))
+        })
+        .collect(),
+      dir,
+      depth,
+    }
+  }
+}
+
+impl Iterator for LevelIter<'_> {
+  type Item = FindUpLevel;
+
+  fn next(&mut self) -> Option<FindUpLevel> {
+    if let Some(level) = self.overridden.take() {
+      return Some(level);
+    }
+
+    let level = self.levels.pop_front()?;
+    Some(self.search(level))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.levels.len() + usize::from(self.overridden.is_some());
+    (len, Some(len))
+  }
+}
+
+impl DoubleEndedIterator for LevelIter<'_> {
+  fn next_back(&mut self) -> Option<FindUpLevel> {
+    if let Some(level) = self.overridden.take() {
+      return Some(level);
+    }
+
+    let level = self.levels.pop_back()?;
+    Some(self.search(level))
+  }
+}
+
+impl ExactSizeIterator for LevelIter<'_> {}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find a file in the current working directory and parent directories
+  /// lazily, searching a level whenever the matches found so far run out.
+  ///
+  /// The levels are worked out up front, so the iterator is double-ended and
+  /// the farthest match, e.g. the outermost `Cargo.toml`, is found without
+  /// searching the levels below it. Unlike [`UpFinder::find_up_matches`], the
+  /// iterator leaves out the [`ConfigFallback`](crate::ConfigFallback)
+  /// directories, and it reports hard links as matches of their own.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if let Some(workspace) = find_up.find_up_iter("Cargo.toml").next_back() {
+  ///   println!("{}", workspace.path().display());
+  /// }
+  /// ```
+  pub fn find_up_iter(&self, name: &str) -> FindUpIter<'_> {
+    let up_finder = without_fallback(self);
+    let (search, overridden, levels) = PartedSearch::plan(&up_finder, &[name]);
+
+    FindUpIter {
+      levels,
+      up_finder,
+      name: name.to_owned(),
+      front: overridden.into_iter().collect(),
+      back: VecDeque::new(),
+      search,
+    }
+  }
+
+  /// How many matches [`UpFinder::find_up_iter`] would yield for `name`,
+  /// counted a level at a time, e.g. to tell how deeply a package is nested in
+  /// a monorepo.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// println!("{} packages up to the root", find_up.count_up("package.json"));
+  /// ```
+  pub fn count_up(&self, name: &str) -> usize {
+    self.find_up_iter(name).count()
+  }
+
+  /// The levels of a search for `names`, one at a time from either end, each
+  /// with whatever matched there, e.g. to process a cascade level by level.
+  /// Every level is reported, the ones without matches included.
+  ///
+  /// Like [`UpFinder::find_up_iter`], the levels are worked out up front and
+  /// leave out the [`ConfigFallback`](crate::ConfigFallback) directories.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// for level in find_up.iter_levels(&[".editorconfig", ".gitattributes"]) {
+  ///   println!("{}: {:?}", level.dir().display(), level.matches());
+  /// }
+  /// ```
+  pub fn iter_levels(&self, names: &[&str]) -> LevelIter<'_> {
+    let up_finder = without_fallback(self);
+    let (search, overridden, levels) = PartedSearch::plan(&up_finder, names);
+
+    LevelIter {
+      overridden: overridden.map(|found| FindUpLevel {
+        dir: up_finder.cwd.to_path_buf(),
+        depth: 0,
+        matches: names
+          .iter()
+          .map(|&name| (name.to_owned(), found.clone()))
+          .collect(),
+      }),
+      levels,
+      up_finder,
+      names: names.iter().map(|&name| name.to_owned()).collect(),
+      search,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::{MemoryFs, ReparsePoint, ReparsePointPolicy};
+
+  #[test]
+  fn should_iterate_from_both_ends() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/crates/app/Cargo.toml", "")
+      .with_file("/repo/crates/app/.config/Cargo.toml", "")
+      .with_file("/repo/Cargo.toml", "")
+      .with_dir("/repo/crates/app/src");
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/crates/app/src")
+      .config_dir(true)
+      .fs(Arc::new(fs))
+      .build();
+
+    let all: Vec<FindUpMatch> = up_finder.find_up_iter("Cargo.toml").collect();
+    assert_eq!(all, up_finder.find_up_matches("Cargo.toml"));
+
+    let mut reversed: Vec<FindUpMatch> = up_finder.find_up_iter("Cargo.toml").rev().collect();
+    reversed.reverse();
+    assert_eq!(reversed, all);
+
+    let mut iter = up_finder.find_up_iter("Cargo.toml");
+    assert_eq!(
+      iter.next_back().unwrap().path(),
+      Path::new("/repo/Cargo.toml")
+    );
+    assert_eq!(
+      iter.next().unwrap().path(),
+      Path::new("/repo/crates/app/Cargo.toml")
+    );
+    assert_eq!(
+      iter.next_back().unwrap().path(),
+      Path::new("/repo/crates/app/.config/Cargo.toml")
+    );
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+  }
+
+  #[test]
+  fn should_end_at_the_levels_that_end_the_walk() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/crates/app/Cargo.toml", "")
+      .with_file("/repo/Cargo.toml", "")
+      .with_file("/Cargo.toml", "")
+      .with_dir("/repo/crates/app/src")
+      .with_reparse_point("/repo/crates", ReparsePoint::MountPoint);
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/crates/app/src")
+      .reparse_points(ReparsePointPolicy::StopBelow)
+      .fs(Arc::new(fs))
+      .build();
+
+    let paths: Vec<PathBuf> = up_finder
+      .find_up_iter("Cargo.toml")
+      .rev()
+      .map(FindUpMatch::into_path)
+      .collect();
+    assert_eq!(paths, [PathBuf::from("/repo/crates/app/Cargo.toml")]);
+  }
+
+  #[test]
+  fn should_yield_every_level_with_its_matches() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/app/.editorconfig", "")
+      .with_file("/repo/.editorconfig", "")
+      .with_file("/repo/.gitattributes", "")
+      .with_dir("/repo/app");
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/app")
+      .fs(Arc::new(fs))
+      .build();
+
+    let levels = up_finder.iter_levels(&[".gitattributes", ".editorconfig"]);
+    assert_eq!(levels.len(), 3);
+
+    let levels: Vec<(PathBuf, usize, Vec<String>)> = levels
+      .map(|level| {
+        let names = level
+          .matches()
+          .iter()
+          .map(|(name, _)| name.clone())
+          .collect();
+        (level.dir().to_path_buf(), level.depth(), names)
+      })
+      .collect();
+    assert_eq!(
+      levels,
+      [
+        (
+          PathBuf::from("/repo/app"),
+          0,
+          vec![".editorconfig".to_owned()]
+        ),
+        (
+          PathBuf::from("/repo"),
+          1,
+          vec![".gitattributes".to_owned(), ".editorconfig".to_owned()]
+        ),
+        (PathBuf::from("/"), 2, vec![]),
+      ]
+    );
+
+    let mut levels = up_finder.iter_levels(&[".editorconfig"]);
+    assert_eq!(levels.next_back().unwrap().dir(), Path::new("/"));
+    let repo = levels.next_back().unwrap();
+    assert_eq!(
+      repo.matches_of(".editorconfig").collect::<Vec<_>>(),
+      [&FindUpMatch::at_depth("/repo/.editorconfig".into(), 1)]
+    );
+  }
+
+  #[test]
+  fn should_count_the_matches_the_iterator_yields() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/packages/app/package.json", "{}")
+      .with_file("/repo/packages/app/.config/package.json", "{}")
+      .with_file("/repo/package.json", "{}")
+      .with_dir("/repo/packages/app/src");
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/packages/app/src")
+      .config_dir(true)
+      .fs(Arc::new(fs))
+      .build();
+
+    assert_eq!(up_finder.count_up("package.json"), 3);
+    assert_eq!(up_finder.count_up("Cargo.toml"), 0);
+
+    let mut iter = up_finder.find_up_iter("package.json");
+    iter.next();
+    assert_eq!(iter.count(), 2);
+  }
+
+  #[test]
+  fn should_yield_the_file_named_by_the_override_variable() {
+    let fs = MemoryFs::new()
+      .with_file("/app/.toolrc", "")
+      .with_file("/elsewhere/toolrc", "")
+      .with_dir("/app/src");
+    let up_finder = UpFinder::builder()
+      .cwd("/app/src")
+      .env_override("UP_FINDER_TEST_ITER_OVERRIDE")
+      .fs(Arc::new(fs))
+      .build();
+
+    // SAFETY: no other test reads or writes this variable.
+    unsafe {
+      std::env::set_var("UP_FINDER_TEST_ITER_OVERRIDE", "/elsewhere/toolrc");
+    }
+
+    let found: Vec<_> = up_finder.find_up_iter(".toolrc").collect();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].path(), Path::new("/elsewhere/toolrc"));
+    assert_eq!(up_finder.count_up(".toolrc"), 1);
+
+    let levels: Vec<_> = up_finder.iter_levels(&[".toolrc"]).collect();
+    assert_eq!(levels.len(), 1);
+    assert_eq!(levels[0].dir(), Path::new("/app/src"));
+    assert_eq!(
+      levels[0]
+        .matches_of(".toolrc")
+        .next()
+        .map(FindUpMatch::path),
+      Some(Path::new("/elsewhere/toolrc"))
+    );
+  }
+
+  #[test]
+  #[cfg(unix)]
+  fn should_count_hard_links_like_the_iterator() {
+    let base = std::env::temp_dir().join(format!("up_finder-count-{}", std::process::id()));
+    std::fs::create_dir_all(base.join("app/.config")).unwrap();
+    std::fs::write(base.join("app/.npmrc"), "").unwrap();
+    std::fs::hard_link(base.join("app/.npmrc"), base.join("app/.config/.npmrc")).unwrap();
+
+    let up_finder = UpFinder::builder()
+      .cwd(base.join("app"))
+      .config_dir(true)
+      .dedupe_hard_links(true)
+      .max_depth(0)
+      .build();
+    let found: Vec<FindUpMatch> = up_finder.find_up_iter(".npmrc").collect();
+    let count = up_finder.count_up(".npmrc");
+
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].hard_links(), [base.join("app/.config/.npmrc")]);
+    assert_eq!(count, 1);
+  }
+}
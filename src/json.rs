@@ -0,0 +1,73 @@
+use serde_json::Value;
+
+/// A filter on the JSON contents of candidates, for use with
+/// [`UpFinder::find_up_where`](crate::UpFinder::find_up_where).
+///
+/// Candidates that are not valid JSON never match.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{JsonMatcher, UpFinder};
+///
+/// let find_up = UpFinder::builder().cwd(".").build();
+///
+/// let workspace_root = JsonMatcher::has_key("workspaces");
+/// let paths = find_up.find_up_where("package.json", |_, contents| {
+///   workspace_root.matches(contents)
+/// });
+///
+/// println!("{:#?}", paths);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonMatcher(Condition);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+  HasKey(String),
+  PathEquals(String, Value),
+}
+
+impl JsonMatcher {
+  /// Match top-level objects with the given key, whatever its value.
+  pub fn has_key(key: impl Into<String>) -> Self {
+    Self(Condition::HasKey(key.into()))
+  }
+
+  /// Match documents where the value at the JSON Pointer `pointer`, e.g.
+  /// `/type` or `/engines/node`, equals `value`.
+  pub fn path_equals(pointer: impl Into<String>, value: impl Into<Value>) -> Self {
+    Self(Condition::PathEquals(pointer.into(), value.into()))
+  }
+
+  /// Whether `contents` satisfy the condition.
+  pub fn matches(&self, contents: &[u8]) -> bool {
+    let Ok(document) = serde_json::from_slice::<Value>(contents) else {
+      return false;
+    };
+
+    match &self.0 {
+      Condition::HasKey(key) => document
+        .as_object()
+        .is_some_and(|object| object.contains_key(key)),
+      Condition::PathEquals(pointer, value) => document.pointer(pointer) == Some(value),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_match_keys_and_values() {
+    let package_json = br#"{ "type": "module", "engines": { "node": ">=22" } }"#;
+
+    assert!(JsonMatcher::has_key("type").matches(package_json));
+    assert!(!JsonMatcher::has_key("workspaces").matches(package_json));
+    assert!(JsonMatcher::path_equals("/type", "module").matches(package_json));
+    assert!(JsonMatcher::path_equals("/engines/node", ">=22").matches(package_json));
+    assert!(!JsonMatcher::path_equals("/type", "commonjs").matches(package_json));
+    assert!(!JsonMatcher::has_key("type").matches(b"not json"));
+  }
+}
@@ -0,0 +1,341 @@
+//! The levels of a walk up from a directory, and the checks that skip a level
+//! or end the walk, shared by every search going up.
+use std::path::{Path, PathBuf};
+
+use crate::{
+  BindMountPolicy, FindUpError, FindUpMatch, HookResult, NetworkMountPolicy, OwnershipPolicy,
+  ReparsePoint, ReparsePointPolicy, SearchReport, StopReason, UpFinder,
+  dependency_dirs::is_in_dependency_dir,
+  hash::{HashMap, HashSet},
+  msys_to_windows,
+  network::LevelAccess,
+  ownership::foreign_owner,
+  partial::WalkPosition,
+  restrict::restrict,
+  share_root, trace,
+};
+
+/// The levels of a walk, entered one at a time with [`Levels::enter`] and
+/// left with [`Levels::leave`], which decides whether the walk goes on.
+pub(crate) struct Levels<'f, P> {
+  up_finder: &'f UpFinder<P>,
+  /// The level to enter next, `None` once the walk is over.
+  dir: Option<PathBuf>,
+  depth: usize,
+  /// The `max_depth` of the walk, the one of the [`UpFinder`] unless set
+  /// with [`Levels::with_max_depth`].
+  max_depth: Option<usize>,
+  detect_reparse_points: bool,
+  /// The `restrict_to` root, in the form of the levels.
+  root: Option<PathBuf>,
+  share_root: Option<PathBuf>,
+  crossed_reparse_points: Vec<(PathBuf, ReparsePoint)>,
+  visited_dirs: HashSet<(u64, u64)>,
+  /// Whether [`Levels::enter`] leaves the `on_enter_dir` hook to the caller.
+  defer_hook: bool,
+}
+
+/// A level the walk entered, with what the checks found out about it.
+#[derive(Debug, Clone)]
+pub(crate) struct Level {
+  pub(crate) dir: PathBuf,
+  pub(crate) depth: usize,
+  /// How the candidates of the level are probed, [`LevelAccess::Skip`] if
+  /// the level isn't searched.
+  pub(crate) access: LevelAccess,
+  pub(crate) reparse_point: Option<ReparsePoint>,
+  pub(crate) foreign_owner: Option<u32>,
+  /// The note of the `on_enter_dir` hook for the matches of the level.
+  pub(crate) annotation: Option<String>,
+  /// The reparse points crossed up to and including the level.
+  pub(crate) crossed_reparse_points: Vec<(PathBuf, ReparsePoint)>,
+  pub(crate) share_root: Option<PathBuf>,
+  /// Whether the walk is restricted to a root, so that matches must not be
+  /// reparse points.
+  pub(crate) restricted: bool,
+}
+
+impl Level {
+  /// Call the `on_enter_dir` hook of `up_finder` with the level, which skips
+  /// it or notes an annotation for its matches.
+  pub(crate) fn call_hook<P>(&mut self, up_finder: &UpFinder<P>) {
+    let Some(hook) = &up_finder.on_enter_dir else {
+      return;
+    };
+
+    match (hook.0)(&self.dir, self.depth) {
+      HookResult::Continue => {}
+      HookResult::Skip => {
+        trace::level_skipped(&self.dir, &"skipped by on_enter_dir");
+        self.access = LevelAccess::Skip;
+      }
+      HookResult::Annotate(annotation) => self.annotation = Some(annotation),
+    }
+  }
+}
+
+/// The matches of a walk so far.
+pub(crate) struct WalkMatches<'a> {
+  pub(crate) paths: HashMap<&'a str, Vec<FindUpMatch>>,
+  /// Where the nearest match of a name with a file id is in `paths`, see
+  /// [`FindUpMatch::hard_links`].
+  pub(crate) hard_links: HashMap<(&'a str, (u64, u64)), usize>,
+}
+
+impl<'a> WalkMatches<'a> {
+  /// No matches of `names` yet.
+  pub(crate) fn new(names: &[&'a str]) -> Self {
+    Self {
+      paths: names.iter().map(|&name| (name, vec![])).collect(),
+      hard_links: HashMap::default(),
+    }
+  }
+}
+
+/// How the search of a level ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LevelEnd {
+  /// The walk goes on to the next level.
+  Searched,
+  /// The matcher returned [`FindUpResult::Last`](crate::FindUpResult::Last).
+  Last,
+  /// The matcher returned [`FindUpResult::Stop`](crate::FindUpResult::Stop),
+  /// the walk ended like when it is cancelled.
+  Stopped,
+}
+
+impl<'f, P: AsRef<Path>> Levels<'f, P> {
+  /// The levels of a walk with `up_finder` from `cwd`. `detailed` also checks
+  /// every level for reparse points, for [`FindUpMatch::crossed_reparse_points`].
+  pub(crate) fn new(
+    up_finder: &'f UpFinder<P>,
+    cwd: PathBuf,
+    detailed: bool,
+  ) -> Result<Self, FindUpError> {
+    Self::resume(
+      up_finder,
+      WalkPosition {
+        dir: cwd,
+        depth: 0,
+        crossed_reparse_points: vec![],
+        visited_dirs: HashSet::default(),
+        found: vec![],
+      },
+      detailed,
+    )
+  }
+
+  /// The rest of the levels of a walk that stopped at `position`.
+  pub(crate) fn resume(
+    up_finder: &'f UpFinder<P>,
+    position: WalkPosition,
+    detailed: bool,
+  ) -> Result<Self, FindUpError> {
+    let cwd = if cfg!(windows) && up_finder.msys_paths {
+      msys_to_windows(&position.dir).unwrap_or(position.dir)
+    } else {
+      position.dir
+    };
+
+    let (cwd, root) = match &up_finder.restrict_to {
+      Some(root) => {
+        let (cwd, root) = restrict(&cwd, root)?;
+        (cwd, Some(root))
+      }
+      None => (cwd, None),
+    };
+
+    Ok(Self {
+      up_finder,
+      share_root: share_root(&cwd),
+      dir: Some(cwd),
+      depth: position.depth,
+      max_depth: up_finder.max_depth,
+      detect_reparse_points: detailed
+        || up_finder.reparse_points != ReparsePointPolicy::Follow
+        || root.is_some(),
+      root,
+      crossed_reparse_points: position.crossed_reparse_points,
+      visited_dirs: position.visited_dirs,
+      defer_hook: false,
+    })
+  }
+
+  /// End the walk `max_depth` levels above the start directory instead.
+  pub(crate) fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+    self.max_depth = max_depth;
+    self
+  }
+
+  /// Leave the `on_enter_dir` hook to the searches of the levels, see
+  /// [`Level::call_hook`].
+  pub(crate) fn defer_hook(mut self) -> Self {
+    self.defer_hook = true;
+    self
+  }
+
+  /// The level to enter next and its depth, `None` once the walk is over.
+  pub(crate) fn next_dir(&self) -> Option<(&Path, usize)> {
+    self.dir.as_deref().map(|dir| (dir, self.depth))
+  }
+
+  /// Where a walk stopped before entering the next level goes on from.
+  pub(crate) fn into_position(self, found: Vec<String>) -> Option<WalkPosition> {
+    Some(WalkPosition {
+      dir: self.dir?,
+      depth: self.depth,
+      crossed_reparse_points: self.crossed_reparse_points,
+      visited_dirs: self.visited_dirs,
+      found,
+    })
+  }
+
+  /// Check the next level, `None` if the checks end the walk before it.
+  pub(crate) fn enter(&mut self, report: &mut SearchReport) -> Result<Option<Level>, FindUpError> {
+    let Some(dir) = self.dir.take() else {
+      return Ok(None);
+    };
+    let up_finder = self.up_finder;
+    let fs = &up_finder.fs.0;
+
+    // Every operation on the level goes through its network access, so that
+    // a dead mount can't hang the walk before the candidates are probed.
+    if up_finder.network_mounts != NetworkMountPolicy::Follow {
+      report.fs_operation();
+    }
+    let mut network = up_finder.network_mounts.level_access(fs, &dir);
+    if network == LevelAccess::Skip {
+      trace::level_skipped(&dir, &"on a network mount");
+    }
+
+    let reparse_point = if self.detect_reparse_points {
+      report.fs_operation();
+      match network.run(fs, &dir, &dir, |fs, dir| fs.reparse_point(dir)) {
+        Some(Ok(reparse_point)) => reparse_point,
+        // A directory that can't be checked could lead out of the root.
+        Some(Err(error)) if self.root.is_some() => return Err(FindUpError::Io(error)),
+        Some(Err(_)) | None => None,
+      }
+    } else {
+      None
+    };
+
+    if reparse_point.is_some() && self.root.as_ref().is_some_and(|root| *root != dir) {
+      return Err(FindUpError::Escape(dir));
+    }
+
+    if let Some(reparse_point) = reparse_point {
+      if self.depth > 0 && up_finder.reparse_points == ReparsePointPolicy::StopBelow {
+        report.stop(&dir, StopReason::ReparsePoint);
+        return Ok(None);
+      }
+
+      self
+        .crossed_reparse_points
+        .push((dir.clone(), reparse_point));
+    }
+
+    let foreign_owner = if up_finder.ownership == OwnershipPolicy::Ignore {
+      None
+    } else {
+      report.fs_operation();
+      network
+        .run(fs, &dir, &dir, |fs, dir| fs.owner(dir))
+        .and_then(foreign_owner)
+    };
+
+    if foreign_owner.is_some() && up_finder.ownership == OwnershipPolicy::Stop {
+      report.stop(&dir, StopReason::ForeignOwner);
+      return Ok(None);
+    }
+
+    let aliased = up_finder.bind_mounts != BindMountPolicy::Follow && {
+      report.fs_operation();
+      matches!(
+        network.run(fs, &dir, &dir, |fs, dir| fs.file_id(dir)),
+        Some(Ok(Some(id))) if !self.visited_dirs.insert(id)
+      )
+    };
+
+    if aliased && up_finder.bind_mounts == BindMountPolicy::Stop {
+      report.stop(&dir, StopReason::BindMount);
+      return Ok(None);
+    }
+
+    let access = if aliased {
+      trace::level_skipped(&dir, &"already searched under another path");
+      LevelAccess::Skip
+    } else if up_finder.exclude_dependency_dirs && is_in_dependency_dir(&dir) {
+      trace::level_skipped(&dir, &"in a dependency directory");
+      LevelAccess::Skip
+    } else {
+      network
+    };
+
+    let mut level = Level {
+      dir,
+      depth: self.depth,
+      access,
+      reparse_point,
+      foreign_owner,
+      annotation: None,
+      crossed_reparse_points: self.crossed_reparse_points.clone(),
+      share_root: self.share_root.clone(),
+      restricted: self.root.is_some(),
+    };
+
+    if !self.defer_hook {
+      level.call_hook(up_finder);
+    }
+
+    Ok(Some(level))
+  }
+
+  /// Finish `level`, going on to its parent unless the walk ends there.
+  pub(crate) fn leave(&mut self, level: &Level, report: &mut SearchReport) {
+    let dir = level.dir.as_path();
+
+    if level.reparse_point.is_some() && self.up_finder.reparse_points == ReparsePointPolicy::StopAt
+    {
+      report.stop(dir, StopReason::ReparsePoint);
+      return;
+    }
+
+    if self.up_finder.fs.is_boundary(dir) {
+      report.stop(dir, StopReason::Boundary);
+      return;
+    }
+
+    if self.up_finder.stop_at.as_deref() == Some(dir) {
+      report.stop(dir, StopReason::StopAt);
+      return;
+    }
+
+    if self
+      .max_depth
+      .is_some_and(|max_depth| level.depth >= max_depth)
+    {
+      report.stop(dir, StopReason::MaxDepth);
+      return;
+    }
+
+    if self.root.as_deref() == Some(dir) {
+      report.stop(dir, StopReason::RestrictTo);
+      return;
+    }
+
+    // Never go from `\\server\share\` on to `\\server`.
+    if self.share_root.as_deref() == Some(dir) {
+      report.stop(dir, StopReason::ShareRoot);
+      return;
+    }
+
+    let Some(parent) = dir.parent() else {
+      report.stop(dir, StopReason::Root);
+      return;
+    };
+
+    self.dir = Some(parent.to_path_buf());
+    self.depth = level.depth + 1;
+  }
+}
@@ -1,16 +1,190 @@
-use rustc_hash::FxHashMap;
-use std::path::{Path, PathBuf};
-use typed_builder::TypedBuilder;
+use std::{
+  collections::hash_map::Entry,
+  ffi::OsString,
+  ops::ControlFlow,
+  path::{Path, PathBuf},
+};
+
+#[cfg(all(
+  feature = "clap",
+  not(all(target_family = "wasm", target_os = "unknown"))
+))]
+mod args;
+mod bind_mount;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub mod build_support;
+mod builder;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+mod cascade;
+mod casing;
+pub mod compat;
+mod config_finder;
+#[cfg(all(
+  feature = "config",
+  not(all(target_family = "wasm", target_os = "unknown"))
+))]
+mod config_source;
+mod dependency_dirs;
+mod dotenv;
+mod down;
+mod error;
+mod exists;
+mod fallback;
+mod finder_config;
+mod fs;
+mod git;
+mod gitignore;
+mod glob;
+#[cfg(unix)]
+mod handles;
+mod hash;
+mod hooks;
+mod iter;
+#[cfg(feature = "json")]
+mod json;
+mod levels;
+mod matches;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(not(feature = "metrics"))]
+mod metrics;
+mod msys;
+mod multi_start;
+mod name_query;
+mod network;
+mod node;
+mod normalize;
+mod ownership;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+mod parse;
+mod partial;
+mod plan;
+mod query;
+mod remote;
+mod reparse;
+mod report;
+mod restrict;
+mod start;
+#[cfg(all(
+  feature = "testing",
+  not(all(target_family = "wasm", target_os = "unknown"))
+))]
+pub mod testing;
+#[cfg(feature = "toml")]
+mod toml;
+mod trace;
+mod unc;
+#[cfg(feature = "camino")]
+mod utf8;
+mod verbatim;
+mod versions;
+#[cfg(target_os = "wasi")]
+mod wasi;
+#[cfg(feature = "notify")]
+mod watch;
+pub mod workspace;
+mod wsl;
+#[cfg(feature = "yaml")]
+mod yaml;
+
+#[cfg(all(
+  feature = "clap",
+  not(all(target_family = "wasm", target_os = "unknown"))
+))]
+pub use args::FindUpArgs;
+pub use bind_mount::BindMountPolicy;
+pub use builder::UpFinderBuilder;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+pub use cascade::{Cascade, CascadeOrder, DeepMerge, MergeStrategy};
+use casing::find_case_insensitive;
+pub use config_finder::ConfigFinder;
+#[cfg(all(
+  feature = "config",
+  not(all(target_family = "wasm", target_os = "unknown"))
+))]
+pub use config_source::FindUpSource;
+pub use dependency_dirs::DEPENDENCY_DIRS;
+use dependency_dirs::is_in_dependency_dir;
+pub use error::FindUpError;
+pub use fallback::{ConfigFallback, ConfigTier};
+pub use finder_config::{ParseKindError, UpFinderConfig};
+#[cfg(feature = "cap-std")]
+pub use fs::CapStdFs;
+use fs::SharedFs;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub use fs::StdFs;
+#[cfg(feature = "vfs")]
+pub use fs::VfsFs;
+pub use fs::{FileType, Fs, MemoryFs, Metadata, OverlayFs};
+pub use git::GitRepository;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub use git::find_git_root;
+pub use glob::Glob;
+#[cfg(unix)]
+pub use handles::FoundHandle;
+use hash::HashMap;
+pub use hash::{BuildHasher, Hasher};
+pub use hooks::HookResult;
+use hooks::{CandidateHook, DirHook};
+pub use iter::{FindUpIter, FindUpLevel, LevelIter};
+#[cfg(feature = "json")]
+pub use json::JsonMatcher;
+use levels::{Level, LevelEnd, Levels, WalkMatches};
+pub use matches::FindUpMatch;
+pub use msys::msys_to_windows;
+pub use multi_start::StartMatches;
+pub use name_query::NameQuery;
+use network::LevelAccess;
+pub use network::{NetworkMountPolicy, is_network_mount};
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub use node::{find_node_bin, resolve_node_package};
+use normalize::find_normalized;
+pub use ownership::OwnershipPolicy;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+pub use parse::ConfigFormat;
+pub use partial::{SearchBudget, SearchCursor};
+use partial::{WalkPosition, WalkState};
+pub use query::{Query, QueryError, QueryTarget};
+pub use remote::RemoteFs;
+pub use reparse::{ReparsePoint, ReparsePointPolicy};
+pub use report::{SearchReport, StopReason, Visit};
+pub use restrict::clean;
+pub use start::StartFrom;
+#[cfg(feature = "toml")]
+pub use toml::TomlMatcher;
+pub use unc::share_root;
+#[cfg(feature = "macros")]
+pub use up_finder_macros::find_up_path;
+#[cfg(feature = "camino")]
+pub use utf8::Utf8UpFinder;
+pub use verbatim::to_verbatim;
+pub use versions::{ToolVersion, VersionFile};
+#[cfg(feature = "notify")]
+pub use watch::{Invalidation, InvalidationCause, Invalidations, NearestWatch};
+pub use wsl::{windows_to_wsl, wsl_to_windows};
+#[cfg(feature = "yaml")]
+pub use yaml::YamlMatcher;
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[non_exhaustive]
 pub enum FindUpKind {
   File,
   Dir,
+  /// Files, directories and anything else.
+  Any,
 }
 
+/// What [`UpFinder::find_up_with`] does with a candidate.
 pub enum FindUpResult {
+  /// Report the given path as a match and keep searching.
   Saved(PathBuf),
+  /// Report the given path as a match, finish the current level and end the
+  /// search, e.g. for an `.editorconfig` declaring `root = true`.
+  Last(PathBuf),
+  /// Skip the candidate and keep searching.
   Continue,
+  /// Skip the candidate and end the search.
   Stop,
 }
 
@@ -26,13 +200,107 @@ pub enum FindUpResult {
 ///
 /// println!("{:#?}", paths);
 /// ```
-#[derive(Debug, PartialEq, TypedBuilder)]
-pub struct UpFinder<P: AsRef<Path>> {
+#[derive(Debug, PartialEq)]
+pub struct UpFinder<P> {
   /// The current working directory.
   cwd: P,
   /// The kind of file to search for.
-  #[builder(default = FindUpKind::File)]
   kind: FindUpKind,
+  /// How ancestors on network filesystems are probed.
+  network_mounts: NetworkMountPolicy,
+  /// What to do on reaching a junction, mount point or symlinked ancestor.
+  reparse_points: ReparsePointPolicy,
+  /// What to do on reaching a directory that was already searched under
+  /// another path.
+  bind_mounts: BindMountPolicy,
+  /// What to do on reaching an ancestor owned by another user.
+  ownership: OwnershipPolicy,
+  /// Report matches as extended-length `\\?\` paths on Windows, see [`to_verbatim`].
+  verbatim: bool,
+  /// Accept MSYS2 and Git Bash style `/c/...` start directories on Windows, see
+  /// [`msys_to_windows`].
+  msys_paths: bool,
+  /// Also match names stored in another Unicode normalization form, such as the
+  /// NFD names on HFS+ and APFS volumes. Costs a directory listing on levels
+  /// where a non-ASCII name isn't found as given. Has no effect without the
+  /// `unicode-normalization` feature.
+  normalize_unicode: bool,
+  /// Report matches with the name as it is stored, e.g. `Package.JSON` when
+  /// searching for `package.json` on a case-insensitive filesystem. Costs a
+  /// directory listing on levels with a match, only the matched name itself is
+  /// resolved, not its ancestors.
+  preserve_case: bool,
+  /// Report files that are hard links to an earlier match once, as an alias of
+  /// the nearest one, see [`FindUpMatch::hard_links`].
+  dedupe_hard_links: bool,
+  /// Also look for every name in the `.config` subdirectory of every level,
+  /// right after looking in the level itself.
+  config_dir: bool,
+  /// More subdirectories to look for every name in on every level, relative
+  /// to the level, e.g. `config` and `etc`, after `.config`. The one a match
+  /// was found in is its [`FindUpMatch::probe`].
+  probe_dirs: Vec<PathBuf>,
+  /// Don't report matches inside `node_modules`, `target` and the other
+  /// [`DEPENDENCY_DIRS`], even when the walk starts inside one. Any ancestor
+  /// of the start directory with such a name counts.
+  exclude_dependency_dirs: bool,
+  /// Never search above this directory, see [`UpFinder::try_find_up`].
+  restrict_to: Option<PathBuf>,
+  /// The last directory to search, compared with the ancestors of `cwd` as
+  /// given, without resolving either.
+  stop_at: Option<PathBuf>,
+  /// How many levels above `cwd` to search at most, `0` for only `cwd` itself.
+  max_depth: Option<usize>,
+  /// Record the directories and candidates of every search for its
+  /// [`SearchReport`], see [`SearchReport::visits`].
+  record_visits: bool,
+  /// Called with every directory and its depth before searching it, e.g. to
+  /// log the walk or to skip directories, see [`HookResult`].
+  on_enter_dir: Option<DirHook>,
+  /// Called with every candidate of the right kind before the matcher, e.g.
+  /// to collect data about it or to skip it, see [`HookResult`].
+  on_candidate: Option<CandidateHook>,
+  /// Where to look for names the walk didn't find, see [`ConfigFallback`].
+  /// Not subject to `restrict_to`.
+  config_fallback: Option<ConfigFallback>,
+  /// An environment variable such as `MYTOOL_CONFIG` naming the file to use
+  /// instead of searching. If it is set and names an entry of the right kind,
+  /// that is the only match, for every name, with [`ConfigTier::Override`].
+  env_override: Option<String>,
+  /// The filesystem to search, the real one by default. Required on
+  /// `wasm32-unknown-unknown`, where there is no real filesystem.
+  fs: SharedFs,
+}
+
+impl<P> UpFinder<P> {
+  /// These settings, searching from `cwd`.
+  pub(crate) fn with_cwd<Q>(&self, cwd: Q) -> UpFinder<Q> {
+    UpFinder {
+      cwd,
+      kind: self.kind.clone(),
+      network_mounts: self.network_mounts,
+      reparse_points: self.reparse_points,
+      bind_mounts: self.bind_mounts,
+      ownership: self.ownership,
+      verbatim: self.verbatim,
+      msys_paths: self.msys_paths,
+      normalize_unicode: self.normalize_unicode,
+      preserve_case: self.preserve_case,
+      dedupe_hard_links: self.dedupe_hard_links,
+      config_dir: self.config_dir,
+      probe_dirs: self.probe_dirs.clone(),
+      exclude_dependency_dirs: self.exclude_dependency_dirs,
+      restrict_to: self.restrict_to.clone(),
+      stop_at: self.stop_at.clone(),
+      max_depth: self.max_depth,
+      record_visits: self.record_visits,
+      on_enter_dir: self.on_enter_dir.clone(),
+      on_candidate: self.on_candidate.clone(),
+      config_fallback: self.config_fallback.clone(),
+      env_override: self.env_override.clone(),
+      fs: self.fs.clone(),
+    }
+  }
 }
 
 impl<P: AsRef<Path>> UpFinder<P> {
@@ -49,17 +317,16 @@ impl<P: AsRef<Path>> UpFinder<P> {
   /// println!("{:#?}", paths);
   /// ```
   pub fn find_up(&self, name: &str) -> Vec<PathBuf> {
-    let paths = self.find_up_multi(&[name]);
+    let mut paths = self.find_up_multi(&[name]);
 
-    if let Some(paths) = paths.get(name) {
-      paths.clone()
-    } else {
-      vec![]
-    }
+    paths.remove(name).unwrap_or_default()
   }
 
   /// Find multiple files in the current working directory and all parent directories.
   ///
+  /// The result is keyed by the names that were passed in, so it can be looked up
+  /// with the same `&str` without allocating.
+  ///
   /// # Example
   ///
   /// ```rust
@@ -70,71 +337,893 @@ impl<P: AsRef<Path>> UpFinder<P> {
   ///
   /// println!("{:#?}", paths);
   /// ```
-  pub fn find_up_multi(&self, names: &[&str]) -> FxHashMap<String, Vec<PathBuf>> {
-    self.find_up_with_impl(self.cwd.as_ref().to_path_buf(), names, FindUpResult::Saved)
+  pub fn find_up_multi<'a>(&self, names: &[&'a str]) -> HashMap<&'a str, Vec<PathBuf>> {
+    self
+      .try_find_up_multi(names)
+      .unwrap_or_else(|_| names.iter().map(|&name| (name, vec![])).collect())
+  }
+
+  /// Like [`UpFinder::find_up`], returning the directories the matches were
+  /// found for instead of the matches, see [`FindUpMatch::dir`]. A directory
+  /// with several matches, e.g. in itself and in its `.config` subdirectory,
+  /// is returned once.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::{FindUpKind, UpFinder};
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").kind(FindUpKind::Dir).build();
+  ///
+  /// // The repositories containing the current directory.
+  /// let repos = find_up.find_up_dir_of(".git");
+  ///
+  /// println!("{:#?}", repos);
+  /// ```
+  pub fn find_up_dir_of(&self, name: &str) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = self
+      .find_up_with_impl(
+        self.cwd.as_ref().to_path_buf(),
+        &[name],
+        FindUpResult::Saved,
+        false,
+      )
+      .ok()
+      .and_then(|mut matches| matches.remove(name))
+      .unwrap_or_default()
+      .into_iter()
+      .map(|found| found.dir)
+      .collect();
+    dirs.dedup();
+
+    dirs
+  }
+
+  /// Like [`UpFinder::find_up`], but reports why the search could not be run
+  /// instead of returning no matches.
+  ///
+  /// With [`restrict_to`](UpFinderBuilder::restrict_to) set, nothing above the
+  /// given root is searched, and it is an error if the start directory is
+  /// outside of it or if a symlink or junction below it could lead the search
+  /// elsewhere.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::{FindUpError, UpFinder};
+  ///
+  /// let find_up = UpFinder::builder()
+  ///   .cwd("/srv/projects/../../etc")
+  ///   .restrict_to("/srv/projects")
+  ///   .build();
+  ///
+  /// assert!(matches!(
+  ///   find_up.try_find_up("package.json"),
+  ///   Err(FindUpError::OutsideRoot { .. })
+  /// ));
+  /// ```
+  pub fn try_find_up(&self, name: &str) -> Result<Vec<PathBuf>, FindUpError> {
+    let mut paths = self.try_find_up_multi(&[name])?;
+
+    Ok(paths.remove(name).unwrap_or_default())
+  }
+
+  /// Like [`UpFinder::find_up_multi`], but reports why the search could not be
+  /// run, see [`UpFinder::try_find_up`].
+  pub fn try_find_up_multi<'a>(
+    &self,
+    names: &[&'a str],
+  ) -> Result<HashMap<&'a str, Vec<PathBuf>>, FindUpError> {
+    let matches = self.find_up_with_impl(
+      self.cwd.as_ref().to_path_buf(),
+      names,
+      FindUpResult::Saved,
+      false,
+    )?;
+
+    Ok(
+      matches
+        .into_iter()
+        .map(|(name, matches)| {
+          let paths = matches.into_iter().map(FindUpMatch::into_path).collect();
+          (name, paths)
+        })
+        .collect(),
+    )
+  }
+
+  /// Like [`UpFinder::find_up`], returning what the walk learned about every match.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// for found in find_up.find_up_matches("package.json") {
+  ///   if !found.crossed_reparse_points().is_empty() {
+  ///     println!("{} was found through a junction", found.path().display());
+  ///   }
+  /// }
+  /// ```
+  pub fn find_up_matches(&self, name: &str) -> Vec<FindUpMatch> {
+    let mut matches = self.find_up_multi_matches(&[name]);
+
+    matches.remove(name).unwrap_or_default()
+  }
+
+  /// Like [`UpFinder::find_up_multi`], returning what the walk learned about every match.
+  pub fn find_up_multi_matches<'a>(&self, names: &[&'a str]) -> HashMap<&'a str, Vec<FindUpMatch>> {
+    self
+      .try_find_up_multi_matches(names)
+      .unwrap_or_else(|_| names.iter().map(|&name| (name, vec![])).collect())
+  }
+
+  /// Like [`UpFinder::find_up_multi_matches`], but reports why the search
+  /// could not be run, see [`UpFinder::try_find_up`].
+  pub fn try_find_up_multi_matches<'a>(
+    &self,
+    names: &[&'a str],
+  ) -> Result<HashMap<&'a str, Vec<FindUpMatch>>, FindUpError> {
+    self.find_up_with_impl(
+      self.cwd.as_ref().to_path_buf(),
+      names,
+      FindUpResult::Saved,
+      true,
+    )
+  }
+
+  /// Like [`UpFinder::find_up_matches`], also reporting what the search did,
+  /// see [`SearchReport`].
+  pub fn find_up_report(&self, name: &str) -> (Vec<FindUpMatch>, SearchReport) {
+    let (mut matches, report) = self.find_up_multi_report(&[name]);
+
+    (matches.remove(name).unwrap_or_default(), report)
+  }
+
+  /// Like [`UpFinder::find_up_multi_matches`], also reporting what the search
+  /// did, see [`SearchReport`].
+  pub fn find_up_multi_report<'a>(
+    &self,
+    names: &[&'a str],
+  ) -> (HashMap<&'a str, Vec<FindUpMatch>>, SearchReport) {
+    let (matches, report) = self.find_up_reported(
+      self.cwd.as_ref().to_path_buf(),
+      names,
+      FindUpResult::Saved,
+      true,
+      &mut |_, _| ControlFlow::Continue(()),
+      &mut None,
+    );
+    let matches = matches.unwrap_or_else(|_| names.iter().map(|&name| (name, vec![])).collect());
+
+    (matches, report)
+  }
+
+  /// Find a file in the current working directory and all parent directories,
+  /// letting `matcher` decide what happens with every candidate of the right
+  /// kind.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::{FindUpResult, UpFinder};
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// // Only the nearest match.
+  /// let mut found = false;
+  /// let paths = find_up.find_up_with("package.json", |path| {
+  ///   if std::mem::replace(&mut found, true) {
+  ///     FindUpResult::Stop
+  ///   } else {
+  ///     FindUpResult::Saved(path)
+  ///   }
+  /// });
+  ///
+  /// println!("{:#?}", paths);
+  /// ```
+  pub fn find_up_with<F>(&self, name: &str, matcher: F) -> Vec<PathBuf>
+  where
+    F: FnMut(PathBuf) -> FindUpResult,
+  {
+    self
+      .find_up_with_impl(self.cwd.as_ref().to_path_buf(), &[name], matcher, false)
+      .ok()
+      .and_then(|mut matches| matches.remove(name))
+      .unwrap_or_default()
+      .into_iter()
+      .map(FindUpMatch::into_path)
+      .collect()
   }
 
-  fn find_up_with_impl<F>(
+  /// Like [`UpFinder::find_up_with`], with a matcher that can fail, e.g. on
+  /// reading a candidate. The first error ends the search and is returned as
+  /// [`FindUpError::Matcher`].
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::{FindUpResult, UpFinder};
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// let paths = find_up.try_find_up_with("package.json", |path| {
+  ///   let contents = std::fs::read_to_string(&path)?;
+  ///   Ok::<_, std::io::Error>(if contents.contains("\"workspaces\"") {
+  ///     FindUpResult::Last(path)
+  ///   } else {
+  ///     FindUpResult::Continue
+  ///   })
+  /// });
+  ///
+  /// println!("{:#?}", paths);
+  /// ```
+  pub fn try_find_up_with<F, E>(
+    &self,
+    name: &str,
+    mut matcher: F,
+  ) -> Result<Vec<PathBuf>, FindUpError>
+  where
+    F: FnMut(PathBuf) -> Result<FindUpResult, E>,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+  {
+    let mut failure = None;
+    let matches = self.find_up_with_impl(
+      self.cwd.as_ref().to_path_buf(),
+      &[name],
+      |path| match matcher(path.clone()) {
+        Ok(result) => result,
+        Err(error) => {
+          failure = Some(FindUpError::Matcher {
+            path,
+            source: error.into(),
+          });
+          FindUpResult::Stop
+        }
+      },
+      false,
+    );
+
+    if let Some(failure) = failure {
+      return Err(failure);
+    }
+
+    Ok(
+      matches?
+        .remove(name)
+        .unwrap_or_default()
+        .into_iter()
+        .map(FindUpMatch::into_path)
+        .collect(),
+    )
+  }
+
+  /// Find a file in the current working directory and all parent directories
+  /// whose contents satisfy `predicate`.
+  ///
+  /// Every candidate is read through the configured [`Fs`], candidates that
+  /// can't be read are skipped.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// // The nearest workspace root.
+  /// let paths = find_up.find_up_where("package.json", |_, contents| {
+  ///   String::from_utf8_lossy(contents).contains("\"workspaces\"")
+  /// });
+  ///
+  /// println!("{:#?}", paths);
+  /// ```
+  pub fn find_up_where<F>(&self, name: &str, predicate: F) -> Vec<PathBuf>
+  where
+    F: Fn(&Path, &[u8]) -> bool,
+  {
+    self.find_up_with(name, |path| match self.fs.read(&path) {
+      Ok(contents) if predicate(&path, &contents) => FindUpResult::Saved(path),
+      _ => FindUpResult::Continue,
+    })
+  }
+
+  /// Find a file in the current working directory and all parent directories,
+  /// up to and including the first one whose contents satisfy `is_root`.
+  ///
+  /// Every candidate is read through the configured [`Fs`], candidates that
+  /// can't be read are skipped.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// let paths = find_up.find_up_until(".editorconfig", |_, contents| {
+  ///   String::from_utf8_lossy(contents)
+  ///     .lines()
+  ///     .any(|line| line.split_whitespace().collect::<String>() == "root=true")
+  /// });
+  ///
+  /// println!("{:#?}", paths);
+  /// ```
+  pub fn find_up_until<F>(&self, name: &str, is_root: F) -> Vec<PathBuf>
+  where
+    F: Fn(&Path, &[u8]) -> bool,
+  {
+    self.find_up_with(name, |path| match self.fs.read(&path) {
+      Ok(contents) if is_root(&path, &contents) => FindUpResult::Last(path),
+      Ok(_) => FindUpResult::Saved(path),
+      Err(_) => FindUpResult::Continue,
+    })
+  }
+
+  /// Find a file in the current working directory and all parent directories,
+  /// turning every candidate into a `T` during the walk, e.g. by parsing a
+  /// version out of it. Candidates `transform` returns `None` for are skipped.
+  ///
+  /// With [`dedupe_hard_links`](UpFinderBuilder::dedupe_hard_links), hard links
+  /// to an earlier match are transformed as well.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// let versions: Vec<String> = find_up.find_up_map(".nvmrc", |path| {
+  ///   let version = std::fs::read_to_string(path).ok()?;
+  ///   Some(version.trim().to_owned()).filter(|version| !version.is_empty())
+  /// });
+  ///
+  /// println!("{:#?}", versions);
+  /// ```
+  pub fn find_up_map<T, F>(&self, name: &str, mut transform: F) -> Vec<T>
+  where
+    F: FnMut(&Path) -> Option<T>,
+  {
+    let mut outputs = vec![];
+    let matches = self.find_up_with_impl(
+      self.cwd.as_ref().to_path_buf(),
+      &[name],
+      |path| match transform(&path) {
+        Some(output) => {
+          outputs.push(output);
+          FindUpResult::Saved(path)
+        }
+        None => FindUpResult::Continue,
+      },
+      false,
+    );
+
+    // A match named by `env_override` doesn't go through the matcher.
+    let overridden = matches
+      .ok()
+      .and_then(|mut matches| matches.remove(name))
+      .unwrap_or_default()
+      .into_iter()
+      .find(|found| found.tier == ConfigTier::Override);
+    match overridden {
+      Some(found) => transform(&found.path).into_iter().collect(),
+      None => outputs,
+    }
+  }
+
+  /// Like [`UpFinder::find_up`], calling `progress` with every directory and
+  /// its depth before searching it. Breaking from `progress` cancels the
+  /// search, leaving the matches found so far.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::{ops::ControlFlow, time::{Duration, Instant}};
+  ///
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  /// let deadline = Instant::now() + Duration::from_secs(5);
+  ///
+  /// let paths = find_up.find_up_with_progress("package.json", |dir, depth| {
+  ///   eprint!("\r{depth}: {}", dir.display());
+  ///   if Instant::now() < deadline {
+  ///     ControlFlow::Continue(())
+  ///   } else {
+  ///     ControlFlow::Break(())
+  ///   }
+  /// });
+  ///
+  /// println!("{:#?}", paths);
+  /// ```
+  pub fn find_up_with_progress<F>(&self, name: &str, mut progress: F) -> Vec<PathBuf>
+  where
+    F: FnMut(&Path, usize) -> ControlFlow<()>,
+  {
+    let (matches, _) = self.find_up_reported(
+      self.cwd.as_ref().to_path_buf(),
+      &[name],
+      FindUpResult::Saved,
+      false,
+      &mut progress,
+      &mut None,
+    );
+
+    matches
+      .ok()
+      .and_then(|mut matches| matches.remove(name))
+      .unwrap_or_default()
+      .into_iter()
+      .map(FindUpMatch::into_path)
+      .collect()
+  }
+
+  /// Walk up from `cwd`, `detailed` controls whether match metadata that costs
+  /// extra filesystem operations is collected.
+  fn find_up_with_impl<'a, F>(
     &self,
     cwd: PathBuf,
-    names: &[&str],
+    names: &[&'a str],
     matcher: F,
-  ) -> FxHashMap<String, Vec<PathBuf>>
+    detailed: bool,
+  ) -> Result<HashMap<&'a str, Vec<FindUpMatch>>, FindUpError>
   where
-    F: Fn(PathBuf) -> FindUpResult,
+    F: FnMut(PathBuf) -> FindUpResult,
   {
-    let mut paths: FxHashMap<&str, Vec<PathBuf>> = FxHashMap::default();
+    self
+      .find_up_reported(
+        cwd,
+        names,
+        matcher,
+        detailed,
+        &mut |_, _| ControlFlow::Continue(()),
+        &mut None,
+      )
+      .0
+  }
 
-    let mut cwd = cwd;
+  /// [`UpFinder::find_up_with_impl`], also reporting what the search did.
+  /// The walk goes on from `position` if it is set, and sets it to where it
+  /// stopped if `progress` cancels it, see [`WalkPosition`].
+  fn find_up_reported<'a, F>(
+    &self,
+    cwd: PathBuf,
+    names: &[&'a str],
+    matcher: F,
+    detailed: bool,
+    progress: &mut dyn FnMut(&Path, usize) -> ControlFlow<()>,
+    position: &mut Option<WalkPosition>,
+  ) -> (
+    Result<HashMap<&'a str, Vec<FindUpMatch>>, FindUpError>,
+    SearchReport,
+  )
+  where
+    F: FnMut(PathBuf) -> FindUpResult,
+  {
+    let _span = trace::search(&cwd, names);
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let start = std::time::Instant::now();
 
-    loop {
-      for &name in names {
-        let vecs = paths.entry(name).or_default();
+    let mut report = SearchReport {
+      record_visits: self.record_visits,
+      ..SearchReport::default()
+    };
+    let result = self.walk(
+      cwd,
+      names,
+      matcher,
+      WalkState {
+        detailed,
+        progress,
+        position,
+        report: &mut report,
+      },
+    );
 
-        let file = cwd.join(name);
+    if result.is_err() {
+      report.stop_reason = StopReason::Error;
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    {
+      report.elapsed = Some(start.elapsed());
+    }
+    metrics::record(&report);
 
-        if !file.exists() {
-          continue;
+    (result, report)
+  }
+
+  fn walk<'a, F>(
+    &self,
+    cwd: PathBuf,
+    names: &[&'a str],
+    mut matcher: F,
+    state: WalkState<'_>,
+  ) -> Result<HashMap<&'a str, Vec<FindUpMatch>>, FindUpError>
+  where
+    F: FnMut(PathBuf) -> FindUpResult,
+  {
+    let WalkState {
+      detailed,
+      progress,
+      position,
+      report,
+    } = state;
+    let mut matches = WalkMatches::new(names);
+
+    let resumed = position.take();
+
+    if let Some(path) = self
+      .env_override_match(report)
+      .filter(|_| resumed.is_none())
+    {
+      for vecs in matches.paths.values_mut() {
+        vecs.push(FindUpMatch::outside_walk(
+          path.clone(),
+          ConfigTier::Override,
+        ));
+      }
+      report.stop_reason = StopReason::EnvOverride;
+      return Ok(matches.paths);
+    }
+
+    let (mut levels, found) = match resumed {
+      Some(mut resumed) => {
+        let found = std::mem::take(&mut resumed.found);
+        (Levels::resume(self, resumed, detailed)?, found)
+      }
+      None => (Levels::new(self, cwd, detailed)?, vec![]),
+    };
+
+    let mut stopped = false;
+
+    while let Some((dir, depth)) = levels.next_dir() {
+      report.level(dir, depth);
+
+      if progress(dir, depth).is_break() {
+        report.stop(dir, StopReason::Cancelled);
+        stopped = true;
+        let found = names
+          .iter()
+          .filter(|&&name| {
+            found.iter().any(|found| found == name) || !matches.paths[name].is_empty()
+          })
+          .map(|&name| name.to_owned())
+          .collect();
+        *position = levels.into_position(found);
+        break;
+      }
+
+      let Some(mut level) = levels.enter(report)? else {
+        break;
+      };
+
+      match self.search_level(
+        &mut level,
+        names,
+        &mut matcher,
+        &mut matches,
+        detailed,
+        report,
+      )? {
+        LevelEnd::Searched => levels.leave(&level, report),
+        LevelEnd::Last => {
+          report.stop(&level.dir, StopReason::Matcher);
+          break;
         }
+        LevelEnd::Stopped => {
+          stopped = true;
+          break;
+        }
+      }
+    }
 
-        let matches_criteria = match self.kind {
-          FindUpKind::File => file.is_file(),
-          FindUpKind::Dir => file.is_dir(),
-        };
+    let mut paths = matches.paths;
 
-        if !matches_criteria {
+    if let Some(fallback) = self.config_fallback.as_ref().filter(|_| !stopped) {
+      'names: for &name in names {
+        let vecs = paths.entry(name).or_default();
+        if !vecs.is_empty() || found.iter().any(|found| found == name) {
           continue;
         }
 
-        match matcher(file) {
-          FindUpResult::Saved(path) => {
-            vecs.push(path);
-          }
-          FindUpResult::Continue => {
+        for (tier, dir) in fallback.dirs() {
+          let file = dir.join(name);
+
+          report.fs_operation();
+          if !self
+            .fs
+            .metadata(&file)
+            .is_ok_and(|metadata| self.is_kind(&metadata))
+          {
             continue;
           }
-          FindUpResult::Stop => {
-            break;
+
+          let path = match matcher(file) {
+            FindUpResult::Saved(path) | FindUpResult::Last(path) => path,
+            FindUpResult::Continue => continue,
+            FindUpResult::Stop => continue 'names,
+          };
+
+          trace::matched(&path, 0);
+          vecs.push(FindUpMatch::outside_walk(path, tier));
+          continue 'names;
+        }
+      }
+    }
+
+    Ok(paths)
+  }
+
+  /// The file named by `env_override`, if it is set and names an entry of the
+  /// right kind.
+  fn env_override_match(&self, report: &mut SearchReport) -> Option<PathBuf> {
+    let path = self
+      .env_override
+      .as_deref()
+      .and_then(std::env::var_os)
+      .filter(|path| !path.is_empty())
+      .map(PathBuf::from)
+      .filter(|path| {
+        report.fs_operation();
+        self
+          .fs
+          .metadata(path)
+          .is_ok_and(|metadata| self.is_kind(&metadata))
+      })?;
+
+    trace::matched(&path, 0);
+    Some(path)
+  }
+
+  /// Search the entered `level` for `names`, adding what `matcher` accepts to
+  /// `matches`.
+  fn search_level<'a, F>(
+    &self,
+    level: &mut Level,
+    names: &[&'a str],
+    matcher: &mut F,
+    matches: &mut WalkMatches<'a>,
+    detailed: bool,
+    report: &mut SearchReport,
+  ) -> Result<LevelEnd, FindUpError>
+  where
+    F: FnMut(PathBuf) -> FindUpResult,
+  {
+    let cwd = level.dir.as_path();
+    let access = &mut level.access;
+
+    let probes: Vec<(&Path, PathBuf)> = self
+      .probes()
+      .map(|probe| (probe, cwd.join(probe)))
+      .collect();
+    let candidates = names.iter().flat_map(|&name| {
+      std::iter::once((name, None, cwd)).chain(
+        probes
+          .iter()
+          .map(move |(probe, dir)| (name, Some(*probe), dir.as_path())),
+      )
+    });
+
+    let mut listings: HashMap<&Path, Vec<OsString>> = HashMap::default();
+    let mut last_level = false;
+
+    for (name, probe, dir) in candidates {
+      let vecs = matches.paths.entry(name).or_default();
+
+      let (file, annotation) = match self.lookup(access, cwd, dir, name, &mut listings, report) {
+        ControlFlow::Continue(Some(found)) => found,
+        ControlFlow::Continue(None) => continue,
+        ControlFlow::Break(()) => break,
+      };
+
+      let file = if self.preserve_case && *access == LevelAccess::Direct {
+        let entries = listings.entry(dir).or_insert_with(|| {
+          report.fs_operation();
+          self.fs.read_dir(dir).unwrap_or_default()
+        });
+
+        match find_case_insensitive(entries, name) {
+          Some(entry) => dir.join(entry),
+          None => file,
+        }
+      } else {
+        file
+      };
+
+      let path = match matcher(file) {
+        FindUpResult::Saved(path) => path,
+        FindUpResult::Last(path) => {
+          last_level = true;
+          path
+        }
+        FindUpResult::Continue => {
+          trace::candidate_skipped(&dir.join(name), &"rejected by the matcher");
+          continue;
+        }
+        FindUpResult::Stop => {
+          report.stop(dir, StopReason::Matcher);
+          return Ok(LevelEnd::Stopped);
+        }
+      };
+
+      if self.dedupe_hard_links {
+        report.fs_operation();
+        if let Some(Ok(Some(id))) = access.run(&self.fs.0, cwd, &path, |fs, path| fs.file_id(path))
+        {
+          match matches.hard_links.entry((name, id)) {
+            Entry::Occupied(canonical) => {
+              vecs[*canonical.get()].hard_links.push(path);
+              continue;
+            }
+            Entry::Vacant(canonical) => {
+              canonical.insert(vecs.len());
+            }
           }
         }
       }
 
-      let Some(parent) = cwd.parent() else {
-        break;
+      let path = if self.verbatim {
+        to_verbatim(&path)
+      } else {
+        path
+      };
+
+      let reparse_point = if detailed || level.restricted {
+        report.fs_operation();
+        access
+          .run(&self.fs.0, cwd, &path, |fs, path| fs.reparse_point(path))
+          .and_then(|reparse_point| reparse_point.ok().flatten())
+      } else {
+        None
       };
 
-      cwd = parent.to_path_buf();
+      if reparse_point.is_some() && level.restricted {
+        return Err(FindUpError::Escape(path));
+      }
+
+      trace::matched(&path, level.depth);
+      vecs.push(FindUpMatch {
+        path,
+        dir: if self.verbatim {
+          to_verbatim(cwd)
+        } else {
+          cwd.to_path_buf()
+        },
+        probe: probe.map(Path::to_path_buf),
+        depth: level.depth,
+        reparse_point,
+        crossed_reparse_points: level.crossed_reparse_points.clone(),
+        share_root: if detailed {
+          level.share_root.clone()
+        } else {
+          None
+        },
+        hard_links: vec![],
+        foreign_owner: level.foreign_owner,
+        tier: ConfigTier::Project,
+        annotations: level.annotation.iter().cloned().chain(annotation).collect(),
+      });
     }
 
-    paths
+    Ok(if last_level {
+      LevelEnd::Last
+    } else {
+      LevelEnd::Searched
+    })
+  }
+
+  /// Look up `name` in `dir`, the level `cwd` or one of its probes, as
+  /// `access` allows: the entry of the right kind that the `on_candidate` hook
+  /// doesn't skip, with the note of the hook. Breaks if the rest of the level
+  /// can't be probed.
+  fn lookup<'d>(
+    &self,
+    access: &mut LevelAccess,
+    cwd: &Path,
+    dir: &'d Path,
+    name: &str,
+    listings: &mut HashMap<&'d Path, Vec<OsString>>,
+    report: &mut SearchReport,
+  ) -> ControlFlow<(), Option<(PathBuf, Option<String>)>> {
+    let file = dir.join(name);
+    report.candidate(&file);
+    report.fs_operation();
+
+    let Some(metadata) = access.run(&self.fs.0, cwd, &file, |fs, file| fs.metadata(file)) else {
+      return ControlFlow::Break(());
+    };
+
+    let (file, metadata) = match metadata {
+      Ok(metadata) => (file, metadata),
+      // Listings aren't bounded like single probes, so only fall back to them
+      // on directly accessible levels.
+      Err(_)
+        if cfg!(feature = "unicode-normalization")
+          && self.normalize_unicode
+          && !name.is_ascii()
+          && *access == LevelAccess::Direct =>
+      {
+        let entries = listings.entry(dir).or_insert_with(|| {
+          report.fs_operation();
+          self.fs.read_dir(dir).unwrap_or_default()
+        });
+
+        let Some(entry) = find_normalized(entries, name) else {
+          return ControlFlow::Continue(None);
+        };
+
+        let file = dir.join(entry);
+
+        report.candidate(&file);
+        report.fs_operation();
+        match self.fs.metadata(&file) {
+          Ok(metadata) => (file, metadata),
+          Err(error) => {
+            trace::candidate_skipped(&file, &error);
+            return ControlFlow::Continue(None);
+          }
+        }
+      }
+      Err(error) => {
+        trace::candidate_skipped(&file, &error);
+        return ControlFlow::Continue(None);
+      }
+    };
+
+    if !self.is_kind(&metadata) {
+      trace::candidate_skipped(&file, &"not of the kind searched for");
+      return ControlFlow::Continue(None);
+    }
+
+    match self.on_candidate.as_ref().map(|hook| (hook.0)(&file)) {
+      Some(HookResult::Skip) => {
+        trace::candidate_skipped(&file, &"skipped by on_candidate");
+        ControlFlow::Continue(None)
+      }
+      Some(HookResult::Annotate(annotation)) => {
+        ControlFlow::Continue(Some((file, Some(annotation))))
+      }
+      Some(HookResult::Continue) | None => ControlFlow::Continue(Some((file, None))),
+    }
+  }
+
+  /// This search, borrowing everything but its settings.
+  pub(crate) fn borrowed(&self) -> UpFinder<&Path> {
+    self.with_cwd(self.cwd.as_ref())
+  }
+
+  /// The subdirectories searched on every level besides the level itself, in
+  /// order.
+  fn probes(&self) -> impl Iterator<Item = &Path> {
+    self
+      .config_dir
+      .then(|| Path::new(".config"))
       .into_iter()
-      .map(|(name, paths)| (name.to_string(), paths))
-      .collect()
+      .chain(self.probe_dirs.iter().map(PathBuf::as_path))
+  }
+
+  fn is_kind(&self, metadata: &Metadata) -> bool {
+    match self.kind {
+      FindUpKind::File => metadata.is_file(),
+      FindUpKind::Dir => metadata.is_dir(),
+      FindUpKind::Any => true,
+    }
+  }
+
+  /// Whether `stop_at` or `max_depth` end the walk after the level `dir`.
+  fn ends_walk_at(&self, dir: &Path, depth: usize) -> bool {
+    self.stop_at.as_deref() == Some(dir)
+      || self.max_depth.is_some_and(|max_depth| depth >= max_depth)
   }
 }
 
 #[cfg(test)]
 mod tests {
+  use std::{collections::BTreeMap, sync::Arc};
+
   use insta::assert_debug_snapshot;
 
   use super::*;
@@ -177,6 +1266,8 @@ mod tests {
       assert_eq!(paths.len(), 1);
     }
 
+    // In a stable order, which maps of `std` don't have.
+    let paths: BTreeMap<_, _> = paths.into_iter().collect();
     assert_debug_snapshot!(paths);
   }
 
@@ -204,6 +1295,8 @@ mod tests {
       assert_eq!(paths.len(), 0);
     }
 
+    // In a stable order, which maps of `std` don't have.
+    let paths: BTreeMap<_, _> = paths.into_iter().collect();
     assert_debug_snapshot!(paths);
   }
 
@@ -228,4 +1321,941 @@ mod tests {
 
     assert_debug_snapshot!(paths);
   }
+
+  #[test]
+  fn should_search_local_ancestors_when_skipping_network_mounts() {
+    let up_finder = UpFinder::builder()
+      .cwd("fixtures/a/b/c/d")
+      .network_mounts(NetworkMountPolicy::Skip)
+      .build();
+
+    let paths = up_finder.find_up("package.json");
+
+    assert_eq!(paths.len(), 4);
+  }
+
+  /// A tree with a network mount at `/mnt` that hangs on everything but
+  /// telling that it is one.
+  #[derive(Debug)]
+  struct StuckMountFs(MemoryFs);
+
+  impl StuckMountFs {
+    fn hang(&self, path: &Path) {
+      if path == Path::new("/mnt") {
+        std::thread::sleep(std::time::Duration::from_secs(5));
+      }
+    }
+  }
+
+  impl Fs for StuckMountFs {
+    fn metadata(&self, path: &Path) -> std::io::Result<Metadata> {
+      self.hang(path.parent().unwrap_or(path));
+      self.0.metadata(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> std::io::Result<Vec<std::ffi::OsString>> {
+      self.0.read_dir(path)
+    }
+
+    fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+      self.0.read(path)
+    }
+
+    fn is_network_mount(&self, path: &Path) -> std::io::Result<bool> {
+      Ok(path == Path::new("/mnt"))
+    }
+
+    fn reparse_point(&self, path: &Path) -> std::io::Result<Option<ReparsePoint>> {
+      self.hang(path);
+      Ok(None)
+    }
+
+    fn file_id(&self, path: &Path) -> std::io::Result<Option<(u64, u64)>> {
+      self.hang(path);
+      Ok(None)
+    }
+
+    fn owner(&self, path: &Path) -> std::io::Result<Option<u32>> {
+      self.hang(path);
+      Ok(None)
+    }
+  }
+
+  #[test]
+  #[cfg(not(target_family = "wasm"))]
+  fn should_bound_every_operation_on_network_mounts() {
+    let fs = Arc::new(StuckMountFs(
+      MemoryFs::new()
+        .with_file("/.toolrc", "")
+        .with_file("/mnt/.toolrc", "")
+        .with_file("/mnt/app/.toolrc", ""),
+    ));
+
+    for policy in [
+      NetworkMountPolicy::Timeout(std::time::Duration::from_millis(50)),
+      NetworkMountPolicy::Skip,
+    ] {
+      let up_finder = UpFinder::builder()
+        .cwd("/mnt/app")
+        .network_mounts(policy)
+        .reparse_points(ReparsePointPolicy::StopBelow)
+        .bind_mounts(BindMountPolicy::Skip)
+        .ownership(OwnershipPolicy::Flag)
+        .dedupe_hard_links(true)
+        .fs(fs.clone())
+        .build();
+
+      let start = std::time::Instant::now();
+      let paths = up_finder.find_up(".toolrc");
+
+      assert!(start.elapsed() < std::time::Duration::from_secs(3));
+      assert_eq!(
+        paths,
+        vec![PathBuf::from("/mnt/app/.toolrc"), PathBuf::from("/.toolrc")]
+      );
+    }
+  }
+
+  #[test]
+  fn should_find_files_in_memory_fs() {
+    let fs = MemoryFs::new()
+      .with_file("/project/package.json", "{}")
+      .with_file("/project/packages/app/package.json", "{}")
+      .with_dir("/project/packages/app/src");
+
+    let up_finder = UpFinder::builder()
+      .cwd("/project/packages/app/src")
+      .fs(Arc::new(fs))
+      .build();
+
+    let paths = up_finder.find_up("package.json");
+
+    assert_debug_snapshot!(paths);
+  }
+
+  #[test]
+  fn should_stop_at_filesystem_boundaries() {
+    let fs = MemoryFs::new()
+      .with_file("/package.json", "{}")
+      .with_file("/project/package.json", "{}")
+      .with_dir("/project/src")
+      .with_boundary("/project");
+
+    let up_finder = UpFinder::builder()
+      .cwd("/project/src")
+      .fs(Arc::new(fs))
+      .build();
+
+    let paths = up_finder.find_up("package.json");
+
+    assert_eq!(paths, vec![PathBuf::from("/project/package.json")]);
+  }
+
+  #[cfg(feature = "unicode-normalization")]
+  #[test]
+  fn should_match_names_in_other_normalization_forms() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/cafe\u{301}/r\u{e9}sum\u{e9}.md", "")
+        .with_file("/re\u{301}sume\u{301}.md", "")
+        .with_dir("/cafe\u{301}/src"),
+    );
+
+    let literal = UpFinder::builder()
+      .cwd("/cafe\u{301}/src")
+      .fs(fs.clone())
+      .build();
+
+    assert_eq!(
+      literal.find_up("r\u{e9}sum\u{e9}.md"),
+      vec![PathBuf::from("/cafe\u{301}/r\u{e9}sum\u{e9}.md")]
+    );
+
+    let normalized = UpFinder::builder()
+      .cwd("/cafe\u{301}/src")
+      .normalize_unicode(true)
+      .fs(fs)
+      .build();
+
+    assert_eq!(
+      normalized.find_up("r\u{e9}sum\u{e9}.md"),
+      vec![
+        PathBuf::from("/cafe\u{301}/r\u{e9}sum\u{e9}.md"),
+        PathBuf::from("/re\u{301}sume\u{301}.md")
+      ]
+    );
+  }
+
+  /// Looks up the last component of a path regardless of its casing.
+  #[derive(Debug)]
+  struct CaseInsensitiveFs(MemoryFs);
+
+  impl Fs for CaseInsensitiveFs {
+    fn metadata(&self, path: &Path) -> std::io::Result<Metadata> {
+      let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return self.0.metadata(path);
+      };
+
+      match find_case_insensitive(&self.0.read_dir(dir)?, name.to_str().unwrap()) {
+        Some(entry) => self.0.metadata(&dir.join(entry)),
+        None => self.0.metadata(path),
+      }
+    }
+
+    fn read_dir(&self, path: &Path) -> std::io::Result<Vec<std::ffi::OsString>> {
+      self.0.read_dir(path)
+    }
+
+    fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+      self.0.read(path)
+    }
+  }
+
+  #[test]
+  fn should_report_names_as_stored() {
+    let fs = Arc::new(CaseInsensitiveFs(
+      MemoryFs::new()
+        .with_file("/Package.JSON", "{}")
+        .with_file("/project/package.json", "{}")
+        .with_dir("/project/src"),
+    ));
+
+    let as_queried = UpFinder::builder()
+      .cwd("/project/src")
+      .fs(fs.clone())
+      .build();
+
+    assert_eq!(
+      as_queried.find_up("package.json"),
+      vec![
+        PathBuf::from("/project/package.json"),
+        PathBuf::from("/package.json")
+      ]
+    );
+
+    let as_stored = UpFinder::builder()
+      .cwd("/project/src")
+      .preserve_case(true)
+      .fs(fs)
+      .build();
+
+    assert_eq!(
+      as_stored.find_up("package.json"),
+      vec![
+        PathBuf::from("/project/package.json"),
+        PathBuf::from("/Package.JSON")
+      ]
+    );
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn should_not_search_directories_twice_through_aliases() {
+    let base = std::env::temp_dir().join(format!("up_finder-bind-{}", std::process::id()));
+    let root = base.join("root");
+    std::fs::create_dir_all(root.join("project")).unwrap();
+    for dir in [&base, &root, &root.join("project")] {
+      std::fs::write(dir.join("package.json"), "{}").unwrap();
+    }
+    // The tree shows up inside itself, like a bind mount of `root`.
+    std::os::unix::fs::symlink("..", root.join("project/mirror")).unwrap();
+
+    let find = |bind_mounts| {
+      UpFinder::builder()
+        .cwd(root.join("project/mirror/project"))
+        .bind_mounts(bind_mounts)
+        .build()
+        .find_up("package.json")
+        .into_iter()
+        .filter(|path| path.starts_with(&base))
+        .map(|path| path.strip_prefix(&base).unwrap().to_path_buf())
+        .collect::<Vec<_>>()
+    };
+
+    let follow = find(BindMountPolicy::Follow);
+    let skip = find(BindMountPolicy::Skip);
+    let stop = find(BindMountPolicy::Stop);
+
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert_eq!(
+      follow,
+      vec![
+        PathBuf::from("root/project/mirror/project/package.json"),
+        PathBuf::from("root/project/mirror/package.json"),
+        PathBuf::from("root/project/package.json"),
+        PathBuf::from("root/package.json"),
+        PathBuf::from("package.json"),
+      ]
+    );
+    assert_eq!(
+      skip,
+      vec![
+        PathBuf::from("root/project/mirror/project/package.json"),
+        PathBuf::from("root/project/mirror/package.json"),
+        PathBuf::from("package.json"),
+      ]
+    );
+    assert_eq!(
+      stop,
+      vec![
+        PathBuf::from("root/project/mirror/project/package.json"),
+        PathBuf::from("root/project/mirror/package.json"),
+      ]
+    );
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn should_report_hard_links_as_aliases() {
+    let base = std::env::temp_dir().join(format!("up_finder-links-{}", std::process::id()));
+    std::fs::create_dir_all(base.join("a/b")).unwrap();
+    std::fs::write(base.join("pnpm-lock.yaml"), "").unwrap();
+    std::fs::write(base.join("a/pnpm-lock.yaml"), "").unwrap();
+    std::fs::hard_link(
+      base.join("a/pnpm-lock.yaml"),
+      base.join("a/b/pnpm-lock.yaml"),
+    )
+    .unwrap();
+
+    let matches: Vec<_> = UpFinder::builder()
+      .cwd(base.join("a/b"))
+      .dedupe_hard_links(true)
+      .build()
+      .find_up_matches("pnpm-lock.yaml")
+      .into_iter()
+      .filter(|found| found.path().starts_with(&base))
+      .collect();
+
+    std::fs::remove_dir_all(&base).unwrap();
+
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].path(), base.join("a/b/pnpm-lock.yaml"));
+    assert_eq!(matches[0].hard_links(), [base.join("a/pnpm-lock.yaml")]);
+    assert_eq!(matches[1].path(), base.join("pnpm-lock.yaml"));
+    assert!(matches[1].hard_links().is_empty());
+  }
+
+  fn junction_fs() -> Arc<MemoryFs> {
+    Arc::new(
+      MemoryFs::new()
+        .with_file("/package.json", "{}")
+        .with_file("/links/package.json", "{}")
+        .with_file("/links/pkg/package.json", "{}")
+        .with_dir("/links/pkg/src")
+        .with_reparse_point("/links/pkg", ReparsePoint::MountPoint),
+    )
+  }
+
+  #[test]
+  fn should_report_reparse_points_crossed_by_matches() {
+    let up_finder = UpFinder::builder()
+      .cwd("/links/pkg/src")
+      .fs(junction_fs())
+      .build();
+
+    let matches = up_finder.find_up_matches("package.json");
+
+    assert_debug_snapshot!(matches);
+  }
+
+  #[test]
+  fn should_not_search_above_the_restricted_root() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/package.json", "{}")
+        .with_file("/srv/package.json", "{}")
+        .with_file("/srv/app/package.json", "{}")
+        .with_dir("/srv/app/src")
+        .with_dir("/other"),
+    );
+
+    let inside = UpFinder::builder()
+      .cwd("/srv/app/src")
+      .restrict_to("/srv")
+      .fs(fs.clone())
+      .build();
+
+    assert_eq!(
+      inside.try_find_up("package.json").unwrap(),
+      vec![
+        PathBuf::from("/srv/app/package.json"),
+        PathBuf::from("/srv/package.json")
+      ]
+    );
+
+    let outside = UpFinder::builder()
+      .cwd("/srv/app/../../other")
+      .restrict_to("/srv")
+      .fs(fs)
+      .build();
+
+    assert!(matches!(
+      outside.try_find_up("package.json"),
+      Err(FindUpError::OutsideRoot { path, .. }) if path == Path::new("/other")
+    ));
+    assert!(outside.find_up("package.json").is_empty());
+  }
+
+  #[test]
+  fn should_reject_reparse_points_below_the_restricted_root() {
+    let up_finder = UpFinder::builder()
+      .cwd("/links/pkg/src")
+      .restrict_to("/links")
+      .fs(junction_fs())
+      .build();
+
+    assert!(matches!(
+      up_finder.try_find_up("package.json"),
+      Err(FindUpError::Escape(path)) if path == Path::new("/links/pkg")
+    ));
+
+    let at_root = UpFinder::builder()
+      .cwd("/links/pkg/src")
+      .restrict_to("/links/pkg")
+      .fs(junction_fs())
+      .build();
+
+    assert_eq!(
+      at_root.try_find_up("package.json").unwrap(),
+      vec![PathBuf::from("/links/pkg/package.json")]
+    );
+
+    let unchecked = UpFinder::builder()
+      .cwd("/links/missing")
+      .restrict_to("/links")
+      .fs(junction_fs())
+      .build();
+
+    assert!(matches!(
+      unchecked.try_find_up("package.json"),
+      Err(FindUpError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound
+    ));
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn should_stop_below_or_flag_directories_of_other_users() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/.npmrc", "")
+        .with_file("/tmp/.npmrc", "")
+        .with_file("/tmp/project/.npmrc", "")
+        .with_dir("/tmp/project/src")
+        .with_owner("/tmp", 4242),
+    );
+
+    let stop = UpFinder::builder()
+      .cwd("/tmp/project/src")
+      .ownership(OwnershipPolicy::Stop)
+      .fs(fs.clone())
+      .build();
+
+    assert_eq!(
+      stop.find_up(".npmrc"),
+      vec![PathBuf::from("/tmp/project/.npmrc")]
+    );
+
+    let flag = UpFinder::builder()
+      .cwd("/tmp/project/src")
+      .ownership(OwnershipPolicy::Flag)
+      .fs(fs)
+      .build();
+
+    let owners: Vec<_> = flag
+      .find_up_matches(".npmrc")
+      .iter()
+      .map(FindUpMatch::foreign_owner)
+      .collect();
+
+    assert_eq!(owners, vec![None, Some(4242), None]);
+  }
+
+  #[test]
+  fn should_only_report_candidates_whose_contents_match() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/package.json", r#"{ "workspaces": ["packages/*"] }"#)
+        .with_file("/packages/app/package.json", r#"{ "name": "app" }"#)
+        .with_dir("/packages/app/src"),
+    );
+
+    let up_finder = UpFinder::builder().cwd("/packages/app/src").fs(fs).build();
+
+    let paths = up_finder.find_up_where("package.json", |path, contents| {
+      assert!(path.ends_with("package.json"));
+      String::from_utf8_lossy(contents).contains("workspaces")
+    });
+
+    assert_eq!(paths, vec![PathBuf::from("/package.json")]);
+  }
+
+  #[test]
+  fn should_collect_the_transformed_candidates() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/.nvmrc", "20\n")
+        .with_file("/packages/.nvmrc", "")
+        .with_file("/packages/app/.nvmrc", "18\n")
+        .with_dir("/packages/app/src"),
+    );
+
+    let up_finder = UpFinder::builder()
+      .cwd("/packages/app/src")
+      .fs(Arc::clone(&fs) as Arc<dyn Fs>)
+      .build();
+
+    let versions = up_finder.find_up_map(".nvmrc", |path| {
+      let contents = fs.read(path).ok()?;
+      String::from_utf8(contents).ok()?.trim().parse::<u32>().ok()
+    });
+
+    assert_eq!(versions, [18, 20]);
+  }
+
+  #[test]
+  fn should_end_the_search_with_the_error_of_the_matcher() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/package.json", "{}")
+        .with_file("/packages/app/package.json", "{")
+        .with_dir("/packages/app/src"),
+    );
+
+    let up_finder = UpFinder::builder()
+      .cwd("/packages/app/src")
+      .fs(Arc::clone(&fs) as Arc<dyn Fs>)
+      .build();
+
+    let mut seen = vec![];
+    let error = up_finder
+      .try_find_up_with("package.json", |path| {
+        seen.push(path.clone());
+        let contents = fs.read(&path)?;
+        if contents.ends_with(b"}") {
+          Ok(FindUpResult::Saved(path))
+        } else {
+          Err(std::io::Error::other("unterminated object"))
+        }
+      })
+      .unwrap_err();
+
+    assert_eq!(seen, [PathBuf::from("/packages/app/package.json")]);
+    assert_eq!(
+      error.to_string(),
+      "failed to match /packages/app/package.json: unterminated object"
+    );
+    assert!(matches!(error, FindUpError::Matcher { .. }));
+
+    let paths = up_finder
+      .try_find_up_with("package.json", |path| {
+        Ok::<_, std::io::Error>(if path.starts_with("/packages") {
+          FindUpResult::Continue
+        } else {
+          FindUpResult::Saved(path)
+        })
+      })
+      .unwrap();
+    assert_eq!(paths, [PathBuf::from("/package.json")]);
+  }
+
+  #[test]
+  fn should_end_the_search_when_the_matcher_stops() {
+    let up_finder = UpFinder::builder().cwd("fixtures/a/b/c/d").build();
+
+    let mut seen = 0;
+    let paths = up_finder.find_up_with("package.json", |path| {
+      seen += 1;
+      if seen == 3 {
+        FindUpResult::Stop
+      } else {
+        FindUpResult::Saved(path)
+      }
+    });
+
+    assert_eq!(
+      paths,
+      vec![
+        PathBuf::from("fixtures/a/b/c/d/package.json"),
+        PathBuf::from("fixtures/a/b/c/package.json")
+      ]
+    );
+  }
+
+  #[test]
+  fn should_stop_after_the_match_declaring_the_root() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/.editorconfig", "[*]\nindent_size = 4")
+        .with_file("/repo/.editorconfig", "root = true\n[*]\nindent_size = 2")
+        .with_file("/repo/app/.editorconfig", "[*.md]\nindent_size = 3")
+        .with_dir("/repo/app/src"),
+    );
+
+    let up_finder = UpFinder::builder().cwd("/repo/app/src").fs(fs).build();
+
+    let paths = up_finder.find_up_until(".editorconfig", |_, contents| {
+      String::from_utf8_lossy(contents).starts_with("root = true")
+    });
+
+    assert_eq!(
+      paths,
+      vec![
+        PathBuf::from("/repo/app/.editorconfig"),
+        PathBuf::from("/repo/.editorconfig")
+      ]
+    );
+  }
+
+  #[test]
+  fn should_also_search_config_directories() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/.config/starship.toml", "")
+        .with_file("/app/starship.toml", "")
+        .with_file("/app/.config/starship.toml", "")
+        .with_dir("/app/src"),
+    );
+
+    let up_finder = UpFinder::builder()
+      .cwd("/app/src")
+      .config_dir(true)
+      .fs(fs)
+      .build();
+
+    assert_eq!(
+      up_finder.find_up("starship.toml"),
+      vec![
+        PathBuf::from("/app/starship.toml"),
+        PathBuf::from("/app/.config/starship.toml"),
+        PathBuf::from("/.config/starship.toml")
+      ]
+    );
+  }
+
+  #[test]
+  fn should_also_search_probe_directories() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/etc/app.toml", "")
+        .with_file("/srv/config/app.toml", "")
+        .with_file("/srv/.config/app.toml", "")
+        .with_dir("/srv/app"),
+    );
+
+    let up_finder = UpFinder::builder()
+      .cwd("/srv/app")
+      .config_dir(true)
+      .probe_dirs(["config", "etc"])
+      .fs(fs)
+      .build();
+
+    let matches: Vec<(PathBuf, Option<PathBuf>)> = up_finder
+      .find_up_matches("app.toml")
+      .into_iter()
+      .map(|found| {
+        (
+          found.dir().to_path_buf(),
+          found.probe().map(Path::to_path_buf),
+        )
+      })
+      .collect();
+
+    assert_eq!(
+      matches,
+      [
+        (PathBuf::from("/srv"), Some(PathBuf::from(".config"))),
+        (PathBuf::from("/srv"), Some(PathBuf::from("config"))),
+        (PathBuf::from("/"), Some(PathBuf::from("etc"))),
+      ]
+    );
+    assert_eq!(
+      up_finder.plan("app.toml")[..4],
+      [
+        PathBuf::from("/srv/app/app.toml"),
+        PathBuf::from("/srv/app/.config/app.toml"),
+        PathBuf::from("/srv/app/config/app.toml"),
+        PathBuf::from("/srv/app/etc/app.toml"),
+      ]
+    );
+  }
+
+  #[test]
+  fn should_return_the_directories_of_the_matches() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_dir("/.config/.git")
+        .with_dir("/app/.git")
+        .with_dir("/app/.config/.git")
+        .with_dir("/app/src"),
+    );
+
+    let up_finder = UpFinder::builder()
+      .cwd("/app/src")
+      .kind(FindUpKind::Dir)
+      .config_dir(true)
+      .fs(fs)
+      .build();
+
+    assert_eq!(
+      up_finder.find_up_dir_of(".git"),
+      [PathBuf::from("/app"), PathBuf::from("/")]
+    );
+  }
+
+  #[test]
+  fn should_fall_back_to_user_then_system_directories() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/app/.toolrc", "")
+        .with_file("/home/me/.config/tool/.toolrc", "")
+        .with_file("/home/me/.config/tool/theme.toml", "")
+        .with_file("/etc/xdg/tool/theme.toml", "")
+        .with_file("/etc/xdg/tool/keys.toml", "")
+        .with_dir("/app/src"),
+    );
+
+    let up_finder = UpFinder::builder()
+      .cwd("/app/src")
+      .config_fallback(
+        ConfigFallback::new()
+          .with_user_dir("/home/me/.config/tool")
+          .with_system_dir("/etc/xdg/tool"),
+      )
+      .fs(fs)
+      .build();
+
+    let found = up_finder.find_up_multi_matches(&[".toolrc", "theme.toml", "keys.toml", "missing"]);
+    let tiers = |name| {
+      found[name]
+        .iter()
+        .map(|found| (found.tier(), found.path().to_path_buf()))
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      tiers(".toolrc"),
+      vec![(ConfigTier::Project, PathBuf::from("/app/.toolrc"))]
+    );
+    assert_eq!(
+      tiers("theme.toml"),
+      vec![(
+        ConfigTier::User,
+        PathBuf::from("/home/me/.config/tool/theme.toml")
+      )]
+    );
+    assert_eq!(
+      tiers("keys.toml"),
+      vec![(ConfigTier::System, PathBuf::from("/etc/xdg/tool/keys.toml"))]
+    );
+    assert_eq!(tiers("missing"), vec![]);
+  }
+
+  #[test]
+  fn should_prefer_the_file_named_by_the_override_variable() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/app/.toolrc", "")
+        .with_file("/elsewhere/toolrc", "")
+        .with_dir("/app/src"),
+    );
+    let up_finder = |var: &str| {
+      UpFinder::builder()
+        .cwd("/app/src")
+        .env_override(var)
+        .fs(fs.clone())
+        .build()
+    };
+
+    // SAFETY: no other test reads or writes these variables.
+    unsafe {
+      std::env::set_var("UP_FINDER_TEST_OVERRIDE", "/elsewhere/toolrc");
+      std::env::set_var("UP_FINDER_TEST_MISSING_OVERRIDE", "/missing/toolrc");
+    }
+
+    let found = up_finder("UP_FINDER_TEST_OVERRIDE").find_up_matches(".toolrc");
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].path(), Path::new("/elsewhere/toolrc"));
+    assert_eq!(found[0].tier(), ConfigTier::Override);
+    assert_eq!(
+      up_finder("UP_FINDER_TEST_MISSING_OVERRIDE").find_up(".toolrc"),
+      vec![PathBuf::from("/app/.toolrc")]
+    );
+    assert_eq!(
+      up_finder("UP_FINDER_TEST_UNSET_OVERRIDE").find_up(".toolrc"),
+      vec![PathBuf::from("/app/.toolrc")]
+    );
+  }
+
+  #[test]
+  fn should_end_the_walk_at_stop_at_or_max_depth() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/.editorconfig", "")
+        .with_file("/repo/.editorconfig", "")
+        .with_file("/repo/app/.editorconfig", "")
+        .with_dir("/repo/app/src/.editorconfig"),
+    );
+    let up_finder = || UpFinder::builder().cwd("/repo/app/src").fs(fs.clone());
+
+    assert_eq!(
+      up_finder()
+        .stop_at("/repo")
+        .build()
+        .find_up(".editorconfig"),
+      vec![
+        PathBuf::from("/repo/app/.editorconfig"),
+        PathBuf::from("/repo/.editorconfig")
+      ]
+    );
+    assert_eq!(
+      up_finder().max_depth(1).build().find_up(".editorconfig"),
+      vec![PathBuf::from("/repo/app/.editorconfig")]
+    );
+    assert_eq!(
+      up_finder()
+        .kind(FindUpKind::Any)
+        .max_depth(1)
+        .build()
+        .find_up(".editorconfig"),
+      vec![
+        PathBuf::from("/repo/app/src/.editorconfig"),
+        PathBuf::from("/repo/app/.editorconfig")
+      ]
+    );
+  }
+
+  #[test]
+  fn should_report_progress_and_cancel_from_it() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/repo/.editorconfig", "")
+        .with_file("/repo/app/.editorconfig", "")
+        .with_dir("/repo/app/src"),
+    );
+    let up_finder = UpFinder::builder().cwd("/repo/app/src").fs(fs).build();
+
+    let mut entered = vec![];
+    let paths = up_finder.find_up_with_progress(".editorconfig", |dir, depth| {
+      entered.push((dir.to_path_buf(), depth));
+      if depth < 2 {
+        ControlFlow::Continue(())
+      } else {
+        ControlFlow::Break(())
+      }
+    });
+
+    assert_eq!(paths, vec![PathBuf::from("/repo/app/.editorconfig")]);
+    assert_eq!(
+      entered,
+      [
+        (PathBuf::from("/repo/app/src"), 0),
+        (PathBuf::from("/repo/app"), 1),
+        (PathBuf::from("/repo"), 2),
+      ]
+    );
+  }
+
+  #[test]
+  fn should_skip_and_annotate_from_hooks() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/repo/package.json", "")
+        .with_file("/repo/vendor/package.json", "")
+        .with_file("/repo/vendor/lib/package.json", "")
+        .with_file("/repo/vendor/lib/src/package.json", ""),
+    );
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/vendor/lib/src")
+      .fs(fs)
+      .on_enter_dir(|dir, depth| {
+        if dir.ends_with("vendor") {
+          HookResult::Skip
+        } else {
+          HookResult::Annotate(format!("level {depth}"))
+        }
+      })
+      .on_candidate(|path| {
+        if path.starts_with("/repo/vendor/lib/src") {
+          HookResult::Skip
+        } else {
+          HookResult::Annotate("candidate".into())
+        }
+      })
+      .build();
+
+    let found: Vec<(PathBuf, Vec<String>)> = up_finder
+      .find_up_matches("package.json")
+      .into_iter()
+      .map(|found| (found.path().to_path_buf(), found.annotations().to_vec()))
+      .collect();
+
+    assert_eq!(
+      found,
+      [
+        (
+          PathBuf::from("/repo/vendor/lib/package.json"),
+          vec!["level 1".to_string(), "candidate".to_string()]
+        ),
+        (
+          PathBuf::from("/repo/package.json"),
+          vec!["level 3".to_string(), "candidate".to_string()]
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn should_not_report_matches_inside_dependency_dirs() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/app/tsconfig.json", "{}")
+        .with_file("/app/node_modules/tsconfig.json", "{}")
+        .with_file("/app/node_modules/lib/tsconfig.json", "{}")
+        .with_dir("/app/node_modules/lib/src"),
+    );
+
+    let up_finder = UpFinder::builder()
+      .cwd("/app/node_modules/lib/src")
+      .exclude_dependency_dirs(true)
+      .fs(fs)
+      .build();
+
+    assert_eq!(
+      up_finder.find_up("tsconfig.json"),
+      vec![PathBuf::from("/app/tsconfig.json")]
+    );
+  }
+
+  #[test]
+  fn should_stop_at_or_below_reparse_points() {
+    let stop_at = UpFinder::builder()
+      .cwd("/links/pkg/src")
+      .fs(junction_fs())
+      .reparse_points(ReparsePointPolicy::StopAt)
+      .build();
+
+    let stop_below = UpFinder::builder()
+      .cwd("/links/pkg/src")
+      .fs(junction_fs())
+      .reparse_points(ReparsePointPolicy::StopBelow)
+      .build();
+
+    assert_eq!(
+      stop_at.find_up("package.json"),
+      vec![PathBuf::from("/links/pkg/package.json")]
+    );
+    assert!(stop_below.find_up("package.json").is_empty());
+  }
+
+  #[cfg(feature = "macros")]
+  #[test]
+  fn should_resolve_the_nearest_file_at_compile_time() {
+    assert_eq!(
+      Path::new(find_up_path!("LICENSE")),
+      Path::new(env!("CARGO_MANIFEST_DIR")).join("LICENSE")
+    );
+    assert!(include_str!(find_up_path!("LICENSE")).starts_with("MIT License"));
+  }
 }
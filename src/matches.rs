@@ -0,0 +1,120 @@
+use std::path::{Path, PathBuf};
+
+use crate::{ConfigTier, ReparsePoint};
+
+/// A match together with what the walk learned about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FindUpMatch {
+  pub(crate) path: PathBuf,
+  pub(crate) dir: PathBuf,
+  pub(crate) probe: Option<PathBuf>,
+  pub(crate) depth: usize,
+  pub(crate) reparse_point: Option<ReparsePoint>,
+  pub(crate) crossed_reparse_points: Vec<(PathBuf, ReparsePoint)>,
+  pub(crate) share_root: Option<PathBuf>,
+  pub(crate) hard_links: Vec<PathBuf>,
+  pub(crate) foreign_owner: Option<u32>,
+  pub(crate) tier: ConfigTier,
+  pub(crate) annotations: Vec<String>,
+}
+
+impl FindUpMatch {
+  /// A match that wasn't found by the walk.
+  pub(crate) fn outside_walk(path: PathBuf, tier: ConfigTier) -> Self {
+    Self {
+      tier,
+      ..Self::at_depth(path, 0)
+    }
+  }
+
+  /// A match found without any of the details of the walk.
+  pub(crate) fn at_depth(path: PathBuf, depth: usize) -> Self {
+    Self {
+      dir: path.parent().unwrap_or(&path).to_path_buf(),
+      probe: None,
+      path,
+      depth,
+      reparse_point: None,
+      crossed_reparse_points: vec![],
+      share_root: None,
+      hard_links: vec![],
+      foreign_owner: None,
+      tier: ConfigTier::Project,
+      annotations: vec![],
+    }
+  }
+
+  /// The path of the match.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  pub fn into_path(self) -> PathBuf {
+    self.path
+  }
+
+  /// The directory the match was found for: the level of the walk, also for
+  /// matches in its `.config` subdirectory, or the directory containing a
+  /// match found outside the walk.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// The subdirectory of [`FindUpMatch::dir`] the match was found in, such as
+  /// `.config` or one of the [`probe_dirs`](crate::UpFinderBuilder::probe_dirs),
+  /// `None` for matches in the directory itself.
+  pub fn probe(&self) -> Option<&Path> {
+    self.probe.as_deref()
+  }
+
+  /// How many levels above the start directory the match was found, `0` for
+  /// the start directory itself.
+  pub fn depth(&self) -> usize {
+    self.depth
+  }
+
+  /// Whether the matched entry itself is a reparse point.
+  pub fn reparse_point(&self) -> Option<ReparsePoint> {
+    self.reparse_point
+  }
+
+  /// The directories from the start directory up to the one containing the
+  /// match that are reparse points, nearest first.
+  pub fn crossed_reparse_points(&self) -> &[(PathBuf, ReparsePoint)] {
+    &self.crossed_reparse_points
+  }
+
+  /// The `\\server\share\` root the walk stopped at, for searches starting on
+  /// a UNC path.
+  pub fn share_root(&self) -> Option<&Path> {
+    self.share_root.as_deref()
+  }
+
+  /// Matches further up that are hard links to this one, nearest first. Only
+  /// collected with [`dedupe_hard_links`](crate::UpFinderBuilder::dedupe_hard_links).
+  pub fn hard_links(&self) -> &[PathBuf] {
+    &self.hard_links
+  }
+
+  /// The owner of the directory the match was found in, if that is another
+  /// user. Only collected with [`OwnershipPolicy::Flag`](crate::OwnershipPolicy::Flag).
+  pub fn foreign_owner(&self) -> Option<u32> {
+    self.foreign_owner
+  }
+
+  /// Whether the match was found by the walk, named by the
+  /// [`env_override`](crate::UpFinderBuilder::env_override) variable or found
+  /// in a [`ConfigFallback`](crate::ConfigFallback) directory. Matches not
+  /// found by the walk have a depth of `0` and none of the other details.
+  pub fn tier(&self) -> ConfigTier {
+    self.tier
+  }
+
+  /// The notes the [`on_enter_dir`](crate::UpFinderBuilder::on_enter_dir) hook
+  /// attached to the directory of the match, then the one the
+  /// [`on_candidate`](crate::UpFinderBuilder::on_candidate) hook attached to
+  /// the match itself.
+  pub fn annotations(&self) -> &[String] {
+    &self.annotations
+  }
+}
@@ -0,0 +1,171 @@
+//! The names of the metrics recorded for every search through the `metrics`
+//! facade, with the `metrics` feature.
+
+use crate::SearchReport;
+
+/// The counter of searches run.
+#[cfg(feature = "metrics")]
+pub const SEARCHES: &str = "up_finder_searches_total";
+/// The counter of levels walked by all searches.
+#[cfg(feature = "metrics")]
+pub const LEVELS_WALKED: &str = "up_finder_levels_walked_total";
+/// The counter of filesystem operations of all searches, see
+/// [`SearchReport::fs_operations`](crate::SearchReport::fs_operations).
+#[cfg(feature = "metrics")]
+pub const FS_OPERATIONS: &str = "up_finder_fs_operations_total";
+/// The histogram of how long searches took, in seconds. Not recorded on
+/// `wasm32-unknown-unknown`, which has no clock.
+#[cfg(feature = "metrics")]
+pub const SEARCH_DURATION: &str = "up_finder_search_duration_seconds";
+
+/// Give the installed recorder the units and descriptions of the metrics of
+/// the walk.
+///
+/// # Example
+///
+/// ```rust
+/// // After installing a recorder, e.g. a Prometheus exporter.
+/// up_finder::metrics::describe();
+/// ```
+#[cfg(feature = "metrics")]
+pub fn describe() {
+  use ::metrics::{Unit, describe_counter, describe_histogram};
+
+  describe_counter!(SEARCHES, Unit::Count, "Searches run by up_finder.");
+  describe_counter!(
+    LEVELS_WALKED,
+    Unit::Count,
+    "Directories walked by up_finder searches."
+  );
+  describe_counter!(
+    FS_OPERATIONS,
+    Unit::Count,
+    "Filesystem operations of up_finder searches."
+  );
+  describe_histogram!(
+    SEARCH_DURATION,
+    Unit::Seconds,
+    "How long up_finder searches took."
+  );
+}
+
+/// Record what a search did.
+pub(crate) fn record(report: &SearchReport) {
+  #[cfg(feature = "metrics")]
+  {
+    ::metrics::counter!(SEARCHES).increment(1);
+    ::metrics::counter!(LEVELS_WALKED).increment(report.dirs_visited() as u64);
+    ::metrics::counter!(FS_OPERATIONS).increment(report.fs_operations() as u64);
+
+    if let Some(elapsed) = report.elapsed() {
+      ::metrics::histogram!(SEARCH_DURATION).record(elapsed);
+    }
+  }
+
+  #[cfg(not(feature = "metrics"))]
+  let _ = report;
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+  use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+  };
+
+  use ::metrics::{
+    Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+  };
+
+  use super::*;
+  use crate::{MemoryFs, UpFinder};
+
+  type Values = Arc<Mutex<BTreeMap<String, u64>>>;
+
+  /// Sums the counters and counts the histogram samples by name.
+  #[derive(Default)]
+  struct Sums(Values);
+
+  struct Sum(String, Values);
+
+  impl CounterFn for Sum {
+    fn increment(&self, value: u64) {
+      *self.1.lock().unwrap().entry(self.0.clone()).or_default() += value;
+    }
+
+    fn absolute(&self, _: u64) {}
+  }
+
+  impl HistogramFn for Sum {
+    fn record(&self, _: f64) {
+      self.increment(1);
+    }
+  }
+
+  impl Recorder for Sums {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+      Counter::from_arc(Arc::new(Sum(key.name().into(), Arc::clone(&self.0))))
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+      Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+      Histogram::from_arc(Arc::new(Sum(key.name().into(), Arc::clone(&self.0))))
+    }
+  }
+
+  #[test]
+  fn should_record_levels_and_stats_of_every_search() {
+    let fs = MemoryFs::new().with_file("/a/package.json", "");
+    let find_up = UpFinder::builder().cwd("/a/b/c").fs(Arc::new(fs)).build();
+
+    let recorder = Sums::default();
+    ::metrics::with_local_recorder(&recorder, || {
+      find_up.find_up_multi(&["package.json", ".nvmrc"]);
+      find_up.find_up("package.json");
+    });
+
+    assert_eq!(
+      *recorder.0.lock().unwrap(),
+      BTreeMap::from([
+        (SEARCHES.into(), 2),
+        (LEVELS_WALKED.into(), 8),
+        (FS_OPERATIONS.into(), 12),
+        (SEARCH_DURATION.into(), 2),
+      ])
+    );
+  }
+
+  #[test]
+  fn should_record_an_iteration_as_one_search() {
+    let fs = MemoryFs::new()
+      .with_file("/a/package.json", "")
+      .with_file("/package.json", "");
+    let find_up = UpFinder::builder().cwd("/a/b/c").fs(Arc::new(fs)).build();
+
+    let recorder = Sums::default();
+    ::metrics::with_local_recorder(&recorder, || {
+      assert_eq!(find_up.find_up_iter("package.json").count(), 2);
+      assert_eq!(find_up.iter_levels(&["package.json"]).len(), 4);
+    });
+
+    assert_eq!(
+      *recorder.0.lock().unwrap(),
+      BTreeMap::from([
+        (SEARCHES.into(), 2),
+        (LEVELS_WALKED.into(), 8),
+        (FS_OPERATIONS.into(), 14),
+        (SEARCH_DURATION.into(), 2),
+      ])
+    );
+  }
+}
@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+use crate::wsl::drive_letter;
+
+/// Translate an MSYS2, Git Bash or Cygwin style path into a Windows path.
+///
+/// `/c/...` and `/cygdrive/c/...` become `C:\...`. Returns `None` for any other
+/// path, including ones like `/usr/bin` that are mapped into the installation
+/// directory, since where that is isn't known from the path alone. The
+/// translation is purely lexical and works on any platform.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::Path;
+///
+/// use up_finder::msys_to_windows;
+///
+/// assert_eq!(
+///   msys_to_windows(Path::new("/c/Users/me/project")).unwrap(),
+///   Path::new(r"C:\Users\me\project")
+/// );
+/// ```
+pub fn msys_to_windows(path: &Path) -> Option<PathBuf> {
+  let path = path.to_str()?.strip_prefix('/')?;
+  let components: Vec<&str> = path
+    .split('/')
+    .filter(|component| !component.is_empty())
+    .collect();
+
+  let (letter, rest) = match components.as_slice() {
+    ["cygdrive", drive, rest @ ..] => (drive_letter(drive)?, rest),
+    [drive, rest @ ..] => (drive_letter(drive)?, rest),
+    [] => return None,
+  };
+
+  let mut windows = format!("{}:", letter.to_ascii_uppercase());
+
+  for component in rest {
+    windows.push('\\');
+    windows.push_str(component);
+  }
+
+  if rest.is_empty() {
+    windows.push('\\');
+  }
+
+  Some(PathBuf::from(windows))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_translate_msys_paths() {
+    assert_eq!(
+      msys_to_windows(Path::new("/d/work/app/")),
+      Some(PathBuf::from(r"D:\work\app"))
+    );
+    assert_eq!(
+      msys_to_windows(Path::new("/cygdrive/c")),
+      Some(PathBuf::from(r"C:\"))
+    );
+    assert_eq!(msys_to_windows(Path::new("/usr/bin")), None);
+    assert_eq!(msys_to_windows(Path::new("/")), None);
+    assert_eq!(msys_to_windows(Path::new("c/work")), None);
+  }
+}
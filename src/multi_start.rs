@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+  FindUpMatch, UpFinder,
+  hash::HashMap,
+  iter::{PartedSearch, without_fallback},
+};
+
+/// The matches of one start directory of [`UpFinder::find_up_from`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartMatches {
+  start: PathBuf,
+  matches: Vec<FindUpMatch>,
+}
+
+impl StartMatches {
+  /// The start directory, as given.
+  pub fn start(&self) -> &Path {
+    &self.start
+  }
+
+  /// The matches found from the start directory, nearest first, with depths
+  /// relative to it.
+  pub fn matches(&self) -> &[FindUpMatch] {
+    &self.matches
+  }
+
+  pub fn into_matches(self) -> Vec<FindUpMatch> {
+    self.matches
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find a file from every directory of `starts` instead of the current
+  /// working directory, e.g. from every root of a multi-root workspace, with
+  /// the matches of each start apart. Ancestors the starts share are only
+  /// searched once.
+  ///
+  /// Like [`UpFinder::iter_levels`], the searches leave out the
+  /// [`ConfigFallback`](crate::ConfigFallback) directories.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// for found in find_up.find_up_from(&["src", "tests"], "Cargo.toml") {
+  ///   println!("{}: {:?}", found.start().display(), found.matches());
+  /// }
+  /// ```
+  pub fn find_up_from<S: AsRef<Path>>(&self, starts: &[S], name: &str) -> Vec<StartMatches> {
+    let mut searched: HashMap<PathBuf, Vec<FindUpMatch>> = HashMap::default();
+
+    starts
+      .iter()
+      .map(|start| {
+        let up_finder = UpFinder {
+          cwd: start.as_ref(),
+          ..without_fallback(self)
+        };
+
+        let (mut search, overridden, levels) = PartedSearch::plan(&up_finder, &[name]);
+
+        let mut matches: Vec<FindUpMatch> = overridden.into_iter().collect();
+        for level in levels {
+          let dir = level.dir.clone();
+          let depth = level.depth;
+          // What depends on the way up is that of this start.
+          let crossed = level.crossed_reparse_points.clone();
+          let found = searched.entry(dir).or_insert_with(|| {
+            search
+              .search(&up_finder, &[name], level)
+              .remove(name)
+              .unwrap_or_default()
+          });
+
+          matches.extend(found.iter().map(|found| FindUpMatch {
+            depth,
+            crossed_reparse_points: crossed.clone(),
+            ..found.clone()
+          }));
+        }
+
+        StartMatches {
+          start: start.as_ref().to_path_buf(),
+          matches,
+        }
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+  };
+
+  use super::*;
+  use crate::{HookResult, MemoryFs};
+
+  #[test]
+  fn should_search_shared_ancestors_once() {
+    let fs = MemoryFs::new()
+      .with_file("/ws/a/.editorconfig", "")
+      .with_file("/ws/.editorconfig", "")
+      .with_dir("/ws/a/src")
+      .with_dir("/ws/b");
+    let entered = Arc::new(AtomicUsize::new(0));
+    let up_finder = UpFinder::builder()
+      .cwd("/")
+      .on_enter_dir({
+        let entered = Arc::clone(&entered);
+        move |_, _| {
+          entered.fetch_add(1, Ordering::Relaxed);
+          HookResult::Continue
+        }
+      })
+      .fs(Arc::new(fs))
+      .build();
+
+    let found = up_finder.find_up_from(&["/ws/a/src", "/ws/b"], ".editorconfig");
+
+    let matches: Vec<(&Path, Vec<(&Path, usize)>)> = found
+      .iter()
+      .map(|found| {
+        let matches = found
+          .matches()
+          .iter()
+          .map(|found| (found.path(), found.depth()))
+          .collect();
+        (found.start(), matches)
+      })
+      .collect();
+    assert_eq!(
+      matches,
+      [
+        (
+          Path::new("/ws/a/src"),
+          vec![
+            (Path::new("/ws/a/.editorconfig"), 1),
+            (Path::new("/ws/.editorconfig"), 2)
+          ]
+        ),
+        (
+          Path::new("/ws/b"),
+          vec![(Path::new("/ws/.editorconfig"), 1)]
+        ),
+      ]
+    );
+
+    // `/ws/a/src`, `/ws/a`, `/ws`, `/` and `/ws/b`, each once.
+    assert_eq!(entered.load(Ordering::Relaxed), 5);
+  }
+}
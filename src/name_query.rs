@@ -0,0 +1,211 @@
+use std::{
+  fmt,
+  path::{Path, PathBuf},
+  sync::Arc,
+};
+
+use crate::{FindUpKind, FindUpMatch, FindUpResult, UpFinder};
+
+type ContentFilterFn = dyn Fn(&Path, &[u8]) -> bool + Send + Sync;
+
+/// A name of [`UpFinder::find_up_queries`] with its own kind, limit and
+/// content filter.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{FindUpKind, NameQuery, UpFinder};
+///
+/// let find_up = UpFinder::builder().cwd(".").build();
+///
+/// let found = find_up.find_up_queries(&[
+///   NameQuery::new(".git").with_kind(FindUpKind::Dir).with_limit(1),
+///   NameQuery::new("package.json").with_content_filter(|_, contents| {
+///     String::from_utf8_lossy(contents).contains("\"workspaces\"")
+///   }),
+/// ]);
+///
+/// println!("{:#?} {:#?}", found[0], found[1]);
+/// ```
+#[derive(Clone)]
+pub struct NameQuery {
+  name: String,
+  kind: Option<FindUpKind>,
+  limit: Option<usize>,
+  content_filter: Option<Arc<ContentFilterFn>>,
+}
+
+impl NameQuery {
+  /// Look for `name` with the kind of the [`UpFinder`], without a limit or a
+  /// filter.
+  pub fn new(name: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      kind: None,
+      limit: None,
+      content_filter: None,
+    }
+  }
+
+  /// Look for entries of `kind` instead of the kind of the [`UpFinder`].
+  pub fn with_kind(mut self, kind: FindUpKind) -> Self {
+    self.kind = Some(kind);
+    self
+  }
+
+  /// Report at most `limit` matches, nearest first. The walk for the name ends
+  /// on the level of the last one.
+  pub fn with_limit(mut self, limit: usize) -> Self {
+    self.limit = Some(limit);
+    self
+  }
+
+  /// Only report files whose contents satisfy `filter`, like
+  /// [`UpFinder::find_up_where`].
+  pub fn with_content_filter(
+    mut self,
+    filter: impl Fn(&Path, &[u8]) -> bool + Send + Sync + 'static,
+  ) -> Self {
+    self.content_filter = Some(Arc::new(filter));
+    self
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn kind(&self) -> Option<&FindUpKind> {
+    self.kind.as_ref()
+  }
+
+  pub fn limit(&self) -> Option<usize> {
+    self.limit
+  }
+}
+
+impl fmt::Debug for NameQuery {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("NameQuery")
+      .field("name", &self.name)
+      .field("kind", &self.kind)
+      .field("limit", &self.limit)
+      .field("content_filter", &self.content_filter.is_some())
+      .finish()
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find every query's name in the current working directory and all parent
+  /// directories, each with its own kind, limit and filter, see [`NameQuery`].
+  /// The matches are in the order of `queries`.
+  ///
+  /// Every query is a walk of its own, so a query that reaches its limit
+  /// doesn't end the others.
+  pub fn find_up_queries(&self, queries: &[NameQuery]) -> Vec<Vec<FindUpMatch>> {
+    queries
+      .iter()
+      .map(|query| self.find_up_query(query))
+      .collect()
+  }
+
+  fn find_up_query(&self, query: &NameQuery) -> Vec<FindUpMatch> {
+    if query.limit == Some(0) {
+      return vec![];
+    }
+
+    let mut found = 0;
+    let mut matcher = |path: PathBuf| {
+      if query.limit.is_some_and(|limit| found >= limit) {
+        return FindUpResult::Continue;
+      }
+
+      if let Some(filter) = &query.content_filter {
+        match self.fs.read(&path) {
+          Ok(contents) if filter(&path, &contents) => {}
+          _ => return FindUpResult::Continue,
+        }
+      }
+
+      found += 1;
+      if query.limit == Some(found) {
+        FindUpResult::Last(path)
+      } else {
+        FindUpResult::Saved(path)
+      }
+    };
+
+    let name = query.name.as_str();
+    let cwd = self.cwd.as_ref().to_path_buf();
+    let matches = match &query.kind {
+      Some(kind) if *kind != self.kind => {
+        self
+          .with_kind(kind.clone())
+          .find_up_with_impl(cwd, &[name], &mut matcher, true)
+      }
+      _ => self.find_up_with_impl(cwd, &[name], &mut matcher, true),
+    };
+
+    matches
+      .ok()
+      .and_then(|mut matches| matches.remove(name))
+      .unwrap_or_default()
+  }
+
+  /// This search for entries of `kind`.
+  fn with_kind(&self, kind: FindUpKind) -> UpFinder<&Path> {
+    UpFinder {
+      kind,
+      ..self.borrowed()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::MemoryFs;
+
+  #[test]
+  fn should_give_every_name_its_own_kind_limit_and_filter() {
+    let fs = MemoryFs::new()
+      .with_dir("/repo/.git")
+      .with_dir("/.git")
+      .with_file("/repo/app/.git", "gitdir: ../.git/worktrees/app")
+      .with_file("/repo/app/package.json", r#"{ "name": "app" }"#)
+      .with_file("/repo/package.json", r#"{ "workspaces": ["app"] }"#)
+      .with_file("/package.json", r#"{ "workspaces": [] }"#);
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/app")
+      .fs(Arc::new(fs))
+      .build();
+
+    let found = up_finder.find_up_queries(&[
+      NameQuery::new(".git")
+        .with_kind(FindUpKind::Dir)
+        .with_limit(1),
+      NameQuery::new(".git"),
+      NameQuery::new("package.json")
+        .with_content_filter(|_, contents| contents.starts_with(b"{ \"workspaces\""))
+        .with_limit(5),
+      NameQuery::new("package.json").with_limit(0),
+    ]);
+
+    let paths: Vec<Vec<PathBuf>> = found
+      .into_iter()
+      .map(|found| found.into_iter().map(FindUpMatch::into_path).collect())
+      .collect();
+
+    assert_eq!(
+      paths,
+      [
+        vec![PathBuf::from("/repo/.git")],
+        vec![PathBuf::from("/repo/app/.git")],
+        vec![
+          PathBuf::from("/repo/package.json"),
+          PathBuf::from("/package.json")
+        ],
+        vec![],
+      ]
+    );
+  }
+}
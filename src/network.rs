@@ -0,0 +1,310 @@
+use std::{
+  io,
+  path::Path,
+  sync::{Arc, mpsc},
+  thread,
+  time::Duration,
+};
+
+use crate::{Fs, trace};
+
+/// How long [`NetworkMountPolicy::Skip`] waits for the mount type of an ancestor
+/// before giving up on it.
+const DETECTION_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How the search treats ancestors that live on a network filesystem
+/// (NFS, SMB/CIFS, FUSE, AFS, 9p, autofs ...).
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use up_finder::{NetworkMountPolicy, UpFinder};
+///
+/// let find_up = UpFinder::builder()
+///   .cwd(".")
+///   .network_mounts(NetworkMountPolicy::Timeout(Duration::from_millis(500)))
+///   .build();
+/// let paths = find_up.find_up("package.json");
+///
+/// println!("{:#?}", paths);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NetworkMountPolicy {
+  /// Probe network mounts like any other directory.
+  #[default]
+  Follow,
+  /// Bound every filesystem operation on a network mount by the given timeout.
+  ///
+  /// When an operation times out the rest of that level is skipped, the stuck
+  /// operation is left to finish on a background thread.
+  Timeout(Duration),
+  /// Do not probe ancestors that live on a network mount at all. Ancestors whose
+  /// mount type cannot be determined in time are skipped as well.
+  Skip,
+}
+
+/// How a single ancestor level should be probed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LevelAccess {
+  /// Probe directly on the calling thread.
+  Direct,
+  /// Probe on a helper thread, abandoning it after the timeout.
+  Bounded(Duration),
+  /// Do not probe this level.
+  Skip,
+}
+
+impl NetworkMountPolicy {
+  pub(crate) fn level_access(&self, fs: &Arc<dyn Fs>, dir: &Path) -> LevelAccess {
+    let timeout = match self {
+      NetworkMountPolicy::Follow => return LevelAccess::Direct,
+      NetworkMountPolicy::Timeout(timeout) => *timeout,
+      NetworkMountPolicy::Skip => DETECTION_TIMEOUT,
+    };
+
+    let fs = Arc::clone(fs);
+    let dir = dir.to_path_buf();
+
+    match run_with_timeout(timeout, move || fs.is_network_mount(&dir)) {
+      Some(Ok(false)) => LevelAccess::Direct,
+      Some(Ok(true)) | Some(Err(_)) => match self {
+        NetworkMountPolicy::Timeout(timeout) => LevelAccess::Bounded(*timeout),
+        _ => LevelAccess::Skip,
+      },
+      None => LevelAccess::Skip,
+    }
+  }
+}
+
+impl LevelAccess {
+  /// Run `f` with the filesystem and `path` on the level `dir` as this access
+  /// allows. `None` if the level is skipped, or if `f` timed out, which skips
+  /// the rest of the level.
+  pub(crate) fn run<T, F>(&mut self, fs: &Arc<dyn Fs>, dir: &Path, path: &Path, f: F) -> Option<T>
+  where
+    T: Send + 'static,
+    F: FnOnce(&dyn Fs, &Path) -> T + Send + 'static,
+  {
+    match *self {
+      LevelAccess::Direct => Some(f(&**fs, path)),
+      LevelAccess::Bounded(timeout) => {
+        let fs = Arc::clone(fs);
+        let path = path.to_path_buf();
+        let result = run_with_timeout(timeout, move || f(&*fs, &path));
+
+        // The mount stopped responding, don't queue more operations on it.
+        if result.is_none() {
+          trace::level_skipped(dir, &"timed out");
+          *self = LevelAccess::Skip;
+        }
+
+        result
+      }
+      LevelAccess::Skip => None,
+    }
+  }
+}
+
+/// Run `f` on a helper thread and wait at most `timeout` for its result.
+///
+/// Returns `None` when the timeout elapses; the helper thread is detached and
+/// keeps running until the underlying operation returns. On wasm `f` runs on
+/// the calling thread.
+pub(crate) fn run_with_timeout<T, F>(timeout: Duration, f: F) -> Option<T>
+where
+  T: Send + 'static,
+  F: FnOnce() -> T + Send + 'static,
+{
+  // There are no threads to offload to, and no network mounts to guard against.
+  if cfg!(target_family = "wasm") {
+    return Some(f());
+  }
+
+  let (tx, rx) = mpsc::sync_channel(1);
+
+  thread::Builder::new()
+    .name("up_finder-probe".to_string())
+    .spawn(move || {
+      let _ = tx.send(f());
+    })
+    .ok()?;
+
+  rx.recv_timeout(timeout).ok()
+}
+
+/// Whether `path` lives on a network filesystem.
+///
+/// Uses `statfs` on Unix and the drive type on Windows. Platforms without a
+/// way to tell always report `false`.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::is_network_mount;
+///
+/// let remote = is_network_mount(".".as_ref()).unwrap();
+///
+/// println!("{}", remote);
+/// ```
+pub fn is_network_mount(path: &Path) -> io::Result<bool> {
+  // The empty path is what walking up a relative path ends with.
+  if path.as_os_str().is_empty() {
+    return sys::is_network_mount(Path::new("."));
+  }
+
+  sys::is_network_mount(path)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys {
+  use std::{ffi::CString, io, mem::MaybeUninit, os::unix::ffi::OsStrExt, path::Path};
+
+  /// `f_type` magic numbers of network and automount filesystems, see `statfs(2)`.
+  const NETWORK_MAGICS: &[u32] = &[
+    0x6969,     // NFS_SUPER_MAGIC
+    0x517B,     // SMB_SUPER_MAGIC
+    0xFF534D42, // CIFS_MAGIC_NUMBER
+    0xFE534D42, // SMB2_MAGIC_NUMBER
+    0x65735546, // FUSE_SUPER_MAGIC
+    0x73757245, // CODA_SUPER_MAGIC
+    0x5346414F, // AFS_SUPER_MAGIC
+    0x6B414653, // AFS_FS_MAGIC
+    0x564C,     // NCP_SUPER_MAGIC
+    0x00C36400, // CEPH_SUPER_MAGIC
+    0x01021997, // V9FS_MAGIC
+    0x0BD00BD0, // LUSTRE_SUPER_MAGIC
+    0x0187,     // AUTOFS_SUPER_MAGIC
+  ];
+
+  pub(super) fn is_network_mount(path: &Path) -> io::Result<bool> {
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    let mut stat = MaybeUninit::<libc::statfs>::uninit();
+
+    // SAFETY: `path` is NUL terminated and `stat` is valid for writes.
+    if unsafe { libc::statfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+      return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: `statfs` returned successfully, so `stat` is initialized.
+    let stat = unsafe { stat.assume_init() };
+
+    // The width and signedness of `f_type` differ between targets, the magic
+    // numbers all fit in the low 32 bits.
+    Ok(NETWORK_MAGICS.contains(&(stat.f_type as u32)))
+  }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+mod sys {
+  use std::{
+    ffi::{CStr, CString},
+    io,
+    mem::MaybeUninit,
+    os::unix::ffi::OsStrExt,
+    path::Path,
+  };
+
+  /// `f_fstypename` values of network and automount filesystems.
+  const NETWORK_TYPES: &[&[u8]] = &[
+    b"nfs", b"smbfs", b"afpfs", b"webdav", b"cifs", b"macfuse", b"osxfuse", b"fusefs", b"autofs",
+  ];
+
+  pub(super) fn is_network_mount(path: &Path) -> io::Result<bool> {
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    let mut stat = MaybeUninit::<libc::statfs>::uninit();
+
+    // SAFETY: `path` is NUL terminated and `stat` is valid for writes.
+    if unsafe { libc::statfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+      return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: `statfs` returned successfully, so `stat` is initialized.
+    let stat = unsafe { stat.assume_init() };
+
+    // SAFETY: the kernel NUL terminates `f_fstypename`.
+    let name = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+
+    Ok(NETWORK_TYPES.contains(&name.to_bytes()))
+  }
+}
+
+#[cfg(windows)]
+mod sys {
+  use std::{
+    io,
+    path::{Component, Path, Prefix},
+  };
+
+  const DRIVE_REMOTE: u32 = 4;
+
+  #[link(name = "kernel32")]
+  unsafe extern "system" {
+    fn GetDriveTypeW(root_path_name: *const u16) -> u32;
+  }
+
+  pub(super) fn is_network_mount(path: &Path) -> io::Result<bool> {
+    let Some(Component::Prefix(prefix)) = path.components().next() else {
+      return Ok(false);
+    };
+
+    let root = match prefix.kind() {
+      Prefix::UNC(..) | Prefix::VerbatimUNC(..) => return Ok(true),
+      Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => format!("{}:\\", letter as char),
+      _ => return Ok(false),
+    };
+
+    let root: Vec<u16> = root.encode_utf16().chain(Some(0)).collect();
+
+    // SAFETY: `root` is a NUL terminated wide string.
+    Ok(unsafe { GetDriveTypeW(root.as_ptr()) } == DRIVE_REMOTE)
+  }
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "android",
+  target_os = "macos",
+  target_os = "ios",
+  target_os = "freebsd",
+  windows
+)))]
+mod sys {
+  use std::{io, path::Path};
+
+  pub(super) fn is_network_mount(_path: &Path) -> io::Result<bool> {
+    Ok(false)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_not_report_fixtures_as_network_mount() {
+    assert!(!is_network_mount(Path::new("fixtures")).unwrap());
+  }
+
+  #[test]
+  fn should_probe_local_levels_directly() {
+    let fs: Arc<dyn Fs> = Arc::new(crate::StdFs);
+    let policy = NetworkMountPolicy::Timeout(Duration::from_secs(5));
+
+    assert_eq!(
+      policy.level_access(&fs, Path::new("fixtures")),
+      LevelAccess::Direct
+    );
+  }
+
+  #[test]
+  #[cfg(not(target_family = "wasm"))]
+  fn should_give_up_on_operations_exceeding_the_timeout() {
+    let result = run_with_timeout(Duration::from_millis(10), || {
+      thread::sleep(Duration::from_secs(1));
+    });
+
+    assert!(result.is_none());
+  }
+}
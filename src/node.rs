@@ -0,0 +1,194 @@
+use std::path::{Path, PathBuf};
+
+use crate::{FindUpMatch, FindUpResult, UpFinder};
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Resolve the installed package `name` (e.g. `eslint` or `@scope/pkg`) the
+  /// way Node does for bare specifiers: the nearest `node_modules/{name}` with
+  /// a `package.json`, not looking into `node_modules/node_modules`. Returns
+  /// the directory of the package. `exports` and `main` are not evaluated.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if let Some(eslint) = find_up.resolve_node_package("eslint") {
+  ///   println!("eslint is installed in {}", eslint.display());
+  /// }
+  /// ```
+  pub fn resolve_node_package(&self, name: &str) -> Option<PathBuf> {
+    let manifest = self
+      .find_up_iter(&format!("node_modules/{name}/package.json"))
+      .find(|found| {
+        found
+          .dir()
+          .file_name()
+          .is_none_or(|level| level != "node_modules")
+      })?;
+
+    manifest.path().parent().map(Path::to_path_buf)
+  }
+
+  /// Find the nearest executable `tool` npm linked into `node_modules/.bin`.
+  ///
+  /// On Windows the `{tool}.cmd` shim is preferred, then `{tool}.ps1`, since
+  /// the bare `{tool}` is a shell script there. On other platforms only the
+  /// bare `{tool}` is looked for.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::process::Command;
+  ///
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// // Prefer the project's copy over a global one.
+  /// let prettier = find_up
+  ///   .find_node_bin("prettier")
+  ///   .map_or_else(|| "prettier".into(), |path| path.into_os_string());
+  /// let command = Command::new(prettier);
+  /// ```
+  pub fn find_node_bin(&self, tool: &str) -> Option<PathBuf> {
+    let names: Vec<String> = if cfg!(windows) {
+      vec![
+        format!("node_modules/.bin/{tool}.cmd"),
+        format!("node_modules/.bin/{tool}.ps1"),
+        format!("node_modules/.bin/{tool}"),
+      ]
+    } else {
+      vec![format!("node_modules/.bin/{tool}")]
+    };
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+
+    let mut matches = self
+      .find_up_with_impl(
+        self.cwd.as_ref().to_path_buf(),
+        &names,
+        FindUpResult::Last,
+        false,
+      )
+      .ok()?;
+
+    // Only the nearest level has matches, earlier names win on it.
+    names
+      .iter()
+      .find_map(|name| matches.remove(name)?.into_iter().next())
+      .map(FindUpMatch::into_path)
+  }
+}
+
+/// Resolve the installed package `name` from `start` on the real filesystem,
+/// see [`UpFinder::resolve_node_package`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn resolve_node_package(start: impl AsRef<Path>, name: &str) -> Option<PathBuf> {
+  UpFinder::builder()
+    .cwd(start.as_ref())
+    .build()
+    .resolve_node_package(name)
+}
+
+/// Find the nearest executable `tool` in `node_modules/.bin` at or above
+/// `start` on the real filesystem, see [`UpFinder::find_node_bin`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn find_node_bin(start: impl AsRef<Path>, tool: &str) -> Option<PathBuf> {
+  UpFinder::builder()
+    .cwd(start.as_ref())
+    .build()
+    .find_node_bin(tool)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  fn resolve(cwd: &'static str, name: &str) -> Option<PathBuf> {
+    let fs = MemoryFs::new()
+      .with_file("/repo/node_modules/eslint/package.json", "{}")
+      .with_file("/repo/node_modules/@scope/pkg/package.json", "{}")
+      .with_file("/repo/node_modules/node_modules/react/package.json", "{}")
+      .with_file("/repo/app/node_modules/eslint/package.json", "{}")
+      .with_dir("/repo/app/node_modules/typescript")
+      .with_dir("/repo/app/src");
+
+    UpFinder::builder()
+      .cwd(cwd)
+      .fs(Arc::new(fs))
+      .build()
+      .resolve_node_package(name)
+  }
+
+  #[test]
+  fn should_resolve_the_nearest_installed_copy() {
+    assert_eq!(
+      resolve("/repo/app/src", "eslint"),
+      Some(PathBuf::from("/repo/app/node_modules/eslint"))
+    );
+    assert_eq!(
+      resolve("/repo/app/src", "@scope/pkg"),
+      Some(PathBuf::from("/repo/node_modules/@scope/pkg"))
+    );
+    assert_eq!(resolve("/repo/app/src", "typescript"), None);
+  }
+
+  #[test]
+  fn should_not_look_into_nested_node_modules() {
+    assert_eq!(resolve("/repo/node_modules", "react"), None);
+
+    let in_probe_dir = UpFinder::builder()
+      .cwd("/repo/node_modules")
+      .config_dir(true)
+      .fs(Arc::new(
+        MemoryFs::new()
+          .with_file(
+            "/repo/node_modules/.config/node_modules/react/package.json",
+            "{}",
+          )
+          .with_file("/repo/node_modules/react/package.json", "{}"),
+      ))
+      .build();
+
+    assert_eq!(
+      in_probe_dir.resolve_node_package("react"),
+      Some(PathBuf::from("/repo/node_modules/react"))
+    );
+  }
+
+  #[test]
+  fn should_find_the_nearest_bin() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/node_modules/.bin/eslint", "")
+      .with_file("/repo/node_modules/.bin/eslint.cmd", "")
+      .with_file("/repo/node_modules/.bin/tsc", "")
+      .with_file("/repo/app/node_modules/.bin/tsc", "")
+      .with_file("/repo/app/node_modules/.bin/tsc.cmd", "")
+      .with_dir("/repo/app/src");
+    let find_up = UpFinder::builder()
+      .cwd("/repo/app/src")
+      .fs(Arc::new(fs))
+      .build();
+
+    let (eslint, tsc) = if cfg!(windows) {
+      (
+        "/repo/node_modules/.bin/eslint.cmd",
+        "/repo/app/node_modules/.bin/tsc.cmd",
+      )
+    } else {
+      (
+        "/repo/node_modules/.bin/eslint",
+        "/repo/app/node_modules/.bin/tsc",
+      )
+    };
+
+    assert_eq!(find_up.find_node_bin("eslint"), Some(PathBuf::from(eslint)));
+    assert_eq!(find_up.find_node_bin("tsc"), Some(PathBuf::from(tsc)));
+    assert_eq!(find_up.find_node_bin("prettier"), None);
+  }
+}
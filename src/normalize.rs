@@ -0,0 +1,50 @@
+use std::ffi::OsString;
+
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::{UnicodeNormalization, is_nfc};
+
+/// The entry among `entries` that is `name` in another Unicode normalization
+/// form, e.g. the NFD spelling HFS+ stores for a composed `café.json`.
+#[cfg(feature = "unicode-normalization")]
+pub(crate) fn find_normalized(entries: &[OsString], name: &str) -> Option<OsString> {
+  let name: String = if is_nfc(name) {
+    name.to_owned()
+  } else {
+    name.nfc().collect()
+  };
+
+  entries
+    .iter()
+    .find(|entry| {
+      entry
+        .to_str()
+        .is_some_and(|entry| entry.nfc().eq(name.chars()))
+    })
+    .cloned()
+}
+
+/// Without the `unicode-normalization` feature no other form is known, the
+/// walk doesn't call this.
+#[cfg(not(feature = "unicode-normalization"))]
+pub(crate) fn find_normalized(_: &[OsString], _: &str) -> Option<OsString> {
+  None
+}
+
+#[cfg(all(test, feature = "unicode-normalization"))]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_find_names_in_other_normalization_forms() {
+    let entries = vec![
+      OsString::from("readme.md"),
+      OsString::from("cafe\u{301}.json"),
+    ];
+
+    assert_eq!(
+      find_normalized(&entries, "caf\u{e9}.json"),
+      Some(OsString::from("cafe\u{301}.json"))
+    );
+    assert_eq!(find_normalized(&entries, "cafe.json"), None);
+  }
+}
@@ -0,0 +1,85 @@
+use std::{io, path::Path};
+
+/// What the walk does on reaching an ancestor owned by another user, in the
+/// spirit of git's `safe.directory`: files in someone else's directory can be
+/// planted there to configure tools run by the current user.
+///
+/// Directories owned by the current user or by root are trusted. Owners are
+/// reported by [`Fs::owner`](crate::Fs::owner), which [`StdFs`](crate::StdFs)
+/// only implements on Unix.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{OwnershipPolicy, UpFinder};
+///
+/// let find_up = UpFinder::builder()
+///   .cwd(".")
+///   .ownership(OwnershipPolicy::Stop)
+///   .build();
+/// let paths = find_up.find_up(".npmrc");
+///
+/// println!("{:#?}", paths);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OwnershipPolicy {
+  /// Search ancestors regardless of their owner.
+  #[default]
+  Ignore,
+  /// Stop below the first ancestor owned by another user, without searching it.
+  Stop,
+  /// Search every ancestor, but report the owner of matches in directories
+  /// owned by other users, see
+  /// [`FindUpMatch::foreign_owner`](crate::FindUpMatch::foreign_owner).
+  Flag,
+}
+
+/// `owner`, if it is neither the current user nor root.
+pub(crate) fn foreign_owner(owner: io::Result<Option<u32>>) -> Option<u32> {
+  owner
+    .ok()
+    .flatten()
+    .filter(|&owner| owner != 0 && Some(owner) != sys::current_user())
+}
+
+/// The user owning the entry at `path`.
+#[cfg_attr(
+  all(target_family = "wasm", target_os = "unknown"),
+  allow(dead_code, reason = "there is no `StdFs` to call it")
+)]
+pub(crate) fn owner(path: &Path) -> io::Result<Option<u32>> {
+  let path = if path.as_os_str().is_empty() {
+    Path::new(".")
+  } else {
+    path
+  };
+
+  sys::owner(path)
+}
+
+#[cfg(unix)]
+mod sys {
+  use std::{fs, io, os::unix::fs::MetadataExt, path::Path};
+
+  pub(super) fn owner(path: &Path) -> io::Result<Option<u32>> {
+    Ok(Some(fs::metadata(path)?.uid()))
+  }
+
+  pub(super) fn current_user() -> Option<u32> {
+    // SAFETY: `geteuid` has no preconditions and always succeeds.
+    Some(unsafe { libc::geteuid() })
+  }
+}
+
+#[cfg(not(unix))]
+mod sys {
+  use std::{io, path::Path};
+
+  pub(super) fn owner(_path: &Path) -> io::Result<Option<u32>> {
+    Ok(None)
+  }
+
+  pub(super) fn current_user() -> Option<u32> {
+    None
+  }
+}
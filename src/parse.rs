@@ -0,0 +1,201 @@
+use std::{
+  error::Error,
+  path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::{FindUpError, Fs, UpFinder};
+
+/// The format of a configuration file, see [`UpFinder::find_up_and_parse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigFormat {
+  #[cfg(feature = "json")]
+  Json,
+  #[cfg(feature = "toml")]
+  Toml,
+  #[cfg(feature = "yaml")]
+  Yaml,
+}
+
+impl ConfigFormat {
+  /// The format of `path` going by its extension, `None` for unknown extensions
+  /// and formats whose feature is disabled.
+  pub fn from_extension(path: &Path) -> Option<Self> {
+    match path.extension()?.to_str()? {
+      #[cfg(feature = "json")]
+      "json" => Some(Self::Json),
+      #[cfg(feature = "toml")]
+      "toml" => Some(Self::Toml),
+      #[cfg(feature = "yaml")]
+      "yaml" | "yml" => Some(Self::Yaml),
+      _ => None,
+    }
+  }
+
+  pub(crate) fn parse<T: DeserializeOwned>(
+    self,
+    contents: &[u8],
+  ) -> Result<T, Box<dyn Error + Send + Sync>> {
+    match self {
+      #[cfg(feature = "json")]
+      Self::Json => Ok(serde_json::from_slice(contents)?),
+      #[cfg(feature = "toml")]
+      Self::Toml => Ok(::toml::from_str(std::str::from_utf8(contents)?)?),
+      #[cfg(feature = "yaml")]
+      Self::Yaml => Ok(serde_yaml_ng::from_slice(contents)?),
+    }
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the nearest `name` and deserialize it, choosing the format by its
+  /// extension, see [`ConfigFormat::from_extension`].
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::collections::BTreeMap;
+  ///
+  /// use serde::de::IgnoredAny;
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if let Ok(Some((path, manifest))) =
+  ///   find_up.find_up_and_parse::<BTreeMap<String, IgnoredAny>>("Cargo.toml")
+  /// {
+  ///   println!("{} has {} top-level keys", path.display(), manifest.len());
+  /// }
+  /// ```
+  pub fn find_up_and_parse<T: DeserializeOwned>(
+    &self,
+    name: &str,
+  ) -> Result<Option<(PathBuf, T)>, FindUpError> {
+    let format = ConfigFormat::from_extension(Path::new(name))
+      .ok_or_else(|| FindUpError::UnknownFormat(name.to_owned()))?;
+
+    self.find_up_and_parse_as(name, format)
+  }
+
+  /// Find the nearest `name` and deserialize it as `format`, for names such as
+  /// `.eslintrc` that don't tell.
+  pub fn find_up_and_parse_as<T: DeserializeOwned>(
+    &self,
+    name: &str,
+    format: ConfigFormat,
+  ) -> Result<Option<(PathBuf, T)>, FindUpError> {
+    let Some(path) = self.try_find_up(name)?.into_iter().next() else {
+      return Ok(None);
+    };
+
+    let config = parse(&*self.fs, &path, format)?;
+
+    Ok(Some((path, config)))
+  }
+}
+
+/// Read and deserialize the file at `path`.
+pub(crate) fn parse<T: DeserializeOwned>(
+  fs: &dyn Fs,
+  path: &Path,
+  format: ConfigFormat,
+) -> Result<T, FindUpError> {
+  let contents = fs.read(path)?;
+
+  format
+    .parse(&contents)
+    .map_err(|source| FindUpError::Parse {
+      path: path.to_path_buf(),
+      source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{collections::BTreeMap, sync::Arc};
+
+  use super::*;
+  use crate::MemoryFs;
+
+  fn finder(fs: MemoryFs) -> UpFinder<&'static str> {
+    UpFinder::builder()
+      .cwd("/project/src")
+      .fs(Arc::new(fs.with_dir("/project/src")))
+      .build()
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn should_parse_the_nearest_match() {
+    let up_finder = finder(
+      MemoryFs::new()
+        .with_file("/package.json", r#"{ "name": "root" }"#)
+        .with_file("/project/package.json", r#"{ "name": "project" }"#),
+    );
+
+    let (path, package_json) = up_finder
+      .find_up_and_parse::<BTreeMap<String, String>>("package.json")
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(path, PathBuf::from("/project/package.json"));
+    assert_eq!(package_json["name"], "project");
+
+    let missing = up_finder.find_up_and_parse::<BTreeMap<String, String>>("tsconfig.json");
+    assert!(missing.unwrap().is_none());
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn should_report_unknown_formats_and_parse_errors() {
+    let up_finder = finder(
+      MemoryFs::new()
+        .with_file("/project/.eslintrc", r#"{ "root": "true" }"#)
+        .with_file("/project/broken.json", "{"),
+    );
+
+    assert!(matches!(
+      up_finder.find_up_and_parse::<BTreeMap<String, String>>(".eslintrc"),
+      Err(FindUpError::UnknownFormat(_))
+    ));
+    assert!(matches!(
+      up_finder.find_up_and_parse::<BTreeMap<String, String>>("broken.json"),
+      Err(FindUpError::Parse { path, .. }) if path == Path::new("/project/broken.json")
+    ));
+
+    let (_, eslintrc) = up_finder
+      .find_up_and_parse_as::<BTreeMap<String, String>>(".eslintrc", ConfigFormat::Json)
+      .unwrap()
+      .unwrap();
+    assert_eq!(eslintrc["root"], "true");
+  }
+
+  #[cfg(feature = "toml")]
+  #[test]
+  fn should_parse_toml_files() {
+    let up_finder = finder(MemoryFs::new().with_file("/project/Cargo.toml", "name = \"app\""));
+
+    let (_, cargo_toml) = up_finder
+      .find_up_and_parse::<BTreeMap<String, String>>("Cargo.toml")
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(cargo_toml["name"], "app");
+  }
+
+  #[cfg(feature = "yaml")]
+  #[test]
+  fn should_parse_yaml_files() {
+    let up_finder =
+      finder(MemoryFs::new().with_file("/project/pnpm-workspace.yml", "name: workspace"));
+
+    let (_, workspace) = up_finder
+      .find_up_and_parse::<BTreeMap<String, String>>("pnpm-workspace.yml")
+      .unwrap()
+      .unwrap();
+
+    assert_eq!(workspace["name"], "workspace");
+  }
+}
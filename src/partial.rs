@@ -0,0 +1,250 @@
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use std::time::{Duration, Instant};
+use std::{
+  ops::ControlFlow,
+  path::{Path, PathBuf},
+};
+
+use crate::{FindUpMatch, FindUpResult, ReparsePoint, SearchReport, UpFinder, hash::HashSet};
+
+/// How much of a search [`UpFinder::find_up_partial`] and
+/// [`SearchCursor::resume`] do at a time. Every part searches at least one
+/// level, whatever the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchBudget {
+  levels: Option<usize>,
+  #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+  time: Option<Duration>,
+}
+
+impl SearchBudget {
+  /// Search at most `levels` levels.
+  pub fn levels(levels: usize) -> Self {
+    Self::default().with_levels(levels)
+  }
+
+  /// Start no level after `time` has passed. Not available on
+  /// `wasm32-unknown-unknown`, which has no clock.
+  #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+  pub fn time(time: Duration) -> Self {
+    Self::default().with_time(time)
+  }
+
+  /// Also search at most `levels` levels.
+  pub fn with_levels(mut self, levels: usize) -> Self {
+    self.levels = Some(levels);
+    self
+  }
+
+  /// Also start no level after `time` has passed.
+  #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+  pub fn with_time(mut self, time: Duration) -> Self {
+    self.time = Some(time);
+    self
+  }
+
+  /// A progress callback that cancels the walk on the first level over the
+  /// budget.
+  pub(crate) fn progress(self) -> impl FnMut(&Path, usize) -> ControlFlow<()> {
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let deadline = self.time.map(|time| Instant::now() + time);
+    let mut levels = 0;
+
+    move |_, _| {
+      #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+      let overdue = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+      #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+      let overdue = false;
+
+      if levels > 0 && (overdue || self.levels.is_some_and(|budget| levels >= budget)) {
+        return ControlFlow::Break(());
+      }
+
+      levels += 1;
+      ControlFlow::Continue(())
+    }
+  }
+}
+
+/// Where a walk cancelled by its progress callback goes on from.
+#[derive(Debug, Clone)]
+pub(crate) struct WalkPosition {
+  /// The next level to search.
+  pub(crate) dir: PathBuf,
+  pub(crate) depth: usize,
+  pub(crate) crossed_reparse_points: Vec<(PathBuf, ReparsePoint)>,
+  pub(crate) visited_dirs: HashSet<(u64, u64)>,
+  /// The names matched on earlier levels, which get no fallback.
+  pub(crate) found: Vec<String>,
+}
+
+/// What a walk is driven by and reports to, besides the names and matcher.
+pub(crate) struct WalkState<'w> {
+  /// Whether match metadata that costs extra filesystem operations is
+  /// collected.
+  pub(crate) detailed: bool,
+  pub(crate) progress: &'w mut dyn FnMut(&Path, usize) -> ControlFlow<()>,
+  /// Where the walk goes on from, set to where it stopped when `progress`
+  /// cancels it.
+  pub(crate) position: &'w mut Option<WalkPosition>,
+  pub(crate) report: &'w mut SearchReport,
+}
+
+/// The rest of a search started by [`UpFinder::find_up_partial`].
+#[derive(Debug)]
+pub struct SearchCursor<'a, P: AsRef<Path>> {
+  up_finder: &'a UpFinder<P>,
+  name: String,
+  position: Option<WalkPosition>,
+}
+
+impl<P: AsRef<Path>> SearchCursor<'_, P> {
+  /// Whether the search is over, so that [`SearchCursor::resume`] finds
+  /// nothing more.
+  pub fn is_done(&self) -> bool {
+    self.position.is_none()
+  }
+
+  /// The level the search goes on from, `None` once it is over.
+  pub fn next_dir(&self) -> Option<&Path> {
+    self
+      .position
+      .as_ref()
+      .map(|position| position.dir.as_path())
+  }
+
+  /// Search on from where the search stopped, within `budget`, returning the
+  /// matches of this part and the rest of the search.
+  pub fn resume(self, budget: SearchBudget) -> (Vec<FindUpMatch>, Self) {
+    match self.position {
+      Some(position) => self
+        .up_finder
+        .find_up_part(self.name, budget, Some(position)),
+      None => (vec![], self),
+    }
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find a file in the current working directory and parent directories,
+  /// stopping when `budget` runs out, e.g. to show the nearest match at once
+  /// and the farther ones later. The matches are those of the levels searched,
+  /// the cursor goes on with the rest.
+  ///
+  /// Matches further up that are hard links to a match of an earlier part are
+  /// reported as matches of their own.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::time::Duration;
+  ///
+  /// use up_finder::{SearchBudget, UpFinder};
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// let (nearest, mut cursor) = find_up.find_up_partial("package.json", SearchBudget::levels(1));
+  /// println!("{nearest:#?}");
+  ///
+  /// while !cursor.is_done() {
+  ///   let (farther, rest) = cursor.resume(SearchBudget::time(Duration::from_millis(10)));
+  ///   println!("{farther:#?}");
+  ///   cursor = rest;
+  /// }
+  /// ```
+  pub fn find_up_partial(
+    &self,
+    name: &str,
+    budget: SearchBudget,
+  ) -> (Vec<FindUpMatch>, SearchCursor<'_, P>) {
+    self.find_up_part(name.to_owned(), budget, None)
+  }
+
+  fn find_up_part(
+    &self,
+    name: String,
+    budget: SearchBudget,
+    mut position: Option<WalkPosition>,
+  ) -> (Vec<FindUpMatch>, SearchCursor<'_, P>) {
+    let (matches, _) = self.find_up_reported(
+      self.cwd.as_ref().to_path_buf(),
+      &[name.as_str()],
+      FindUpResult::Saved,
+      true,
+      &mut budget.progress(),
+      &mut position,
+    );
+    let matches = matches
+      .ok()
+      .and_then(|mut matches| matches.remove(name.as_str()))
+      .unwrap_or_default();
+
+    let cursor = SearchCursor {
+      up_finder: self,
+      name,
+      position,
+    };
+
+    (matches, cursor)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::{ConfigFallback, ConfigTier, MemoryFs};
+
+  #[test]
+  fn should_resume_the_search_where_it_stopped() {
+    let fs = MemoryFs::new()
+      .with_file("/a/b/c/.nvmrc", "")
+      .with_file("/a/.nvmrc", "")
+      .with_file("/.nvmrc", "");
+    let up_finder = UpFinder::builder().cwd("/a/b/c").fs(Arc::new(fs)).build();
+
+    let (matches, cursor) = up_finder.find_up_partial(".nvmrc", SearchBudget::levels(2));
+    assert_eq!(matches, [FindUpMatch::at_depth("/a/b/c/.nvmrc".into(), 0)]);
+    assert_eq!(cursor.next_dir(), Some(Path::new("/a")));
+
+    // Every part searches a level, even without a budget for one.
+    let (matches, cursor) = cursor.resume(SearchBudget::levels(0));
+    assert_eq!(matches, [FindUpMatch::at_depth("/a/.nvmrc".into(), 2)]);
+
+    let (matches, cursor) = cursor.resume(SearchBudget::default());
+    assert_eq!(matches, [FindUpMatch::at_depth("/.nvmrc".into(), 3)]);
+    assert!(cursor.is_done());
+    assert_eq!(cursor.resume(SearchBudget::levels(1)).0, []);
+  }
+
+  #[test]
+  fn should_only_fall_back_for_names_no_part_found() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/app/.nvmrc", "")
+      .with_file("/home/.nvmrc", "")
+      .with_file("/home/.node-version", "");
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/app")
+      .config_fallback(ConfigFallback::new().with_user_dir("/home"))
+      .fs(Arc::new(fs))
+      .build();
+
+    let (matches, cursor) = up_finder.find_up_partial(".nvmrc", SearchBudget::levels(1));
+    assert_eq!(matches.len(), 1);
+    let (matches, cursor) = cursor.resume(SearchBudget::default());
+    assert_eq!(matches, []);
+    assert!(cursor.is_done());
+
+    let (matches, cursor) = up_finder.find_up_partial(".node-version", SearchBudget::levels(1));
+    assert_eq!(matches, []);
+    let (matches, _) = cursor.resume(SearchBudget::default());
+    assert_eq!(
+      matches,
+      [FindUpMatch::outside_walk(
+        "/home/.node-version".into(),
+        ConfigTier::User
+      )]
+    );
+  }
+}
@@ -0,0 +1,149 @@
+use std::path::{Path, PathBuf};
+
+use crate::{UpFinder, is_in_dependency_dir, msys_to_windows, restrict::restrict, share_root};
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// The paths a search for `name` would look up, in order, without touching
+  /// the filesystem.
+  ///
+  /// The plan follows `stop_at`, `max_depth`, `restrict_to`, `config_dir`,
+  /// `exclude_dependency_dirs` and the [`ConfigFallback`](crate::ConfigFallback)
+  /// directories, which are only looked up for names the walk didn't find.
+  /// What depends on the filesystem, such as reparse points, ownership,
+  /// network mounts, boundaries of the [`Fs`](crate::Fs) or an
+  /// `env_override`, can only end a search earlier. Empty if the search would
+  /// fail, e.g. for a start directory outside of `restrict_to`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use std::path::PathBuf;
+  ///
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder()
+  ///   .cwd("/repo/app")
+  ///   .config_dir(true)
+  ///   .stop_at("/repo")
+  ///   .build();
+  ///
+  /// assert_eq!(
+  ///   find_up.plan("tsconfig.json"),
+  ///   [
+  ///     PathBuf::from("/repo/app/tsconfig.json"),
+  ///     PathBuf::from("/repo/app/.config/tsconfig.json"),
+  ///     PathBuf::from("/repo/tsconfig.json"),
+  ///     PathBuf::from("/repo/.config/tsconfig.json"),
+  ///   ]
+  /// );
+  /// ```
+  pub fn plan(&self, name: &str) -> Vec<PathBuf> {
+    self.plan_multi(&[name])
+  }
+
+  /// Like [`UpFinder::plan`], for a search for several names.
+  pub fn plan_multi(&self, names: &[&str]) -> Vec<PathBuf> {
+    let cwd = self.cwd.as_ref().to_path_buf();
+    let cwd = if cfg!(windows) && self.msys_paths {
+      msys_to_windows(&cwd).unwrap_or(cwd)
+    } else {
+      cwd
+    };
+
+    let (cwd, root) = match &self.restrict_to {
+      Some(root) => match restrict(&cwd, root) {
+        Ok((cwd, root)) => (cwd, Some(root)),
+        Err(_) => return vec![],
+      },
+      None => (cwd, None),
+    };
+
+    let share_root = share_root(&cwd);
+
+    let mut plan = vec![];
+    let mut cwd = cwd;
+    let mut depth = 0;
+
+    loop {
+      if !(self.exclude_dependency_dirs && is_in_dependency_dir(&cwd)) {
+        for name in names {
+          plan.push(cwd.join(name));
+          for probe in self.probes() {
+            plan.push(cwd.join(probe).join(name));
+          }
+        }
+      }
+
+      if self.ends_walk_at(&cwd, depth)
+        || root.as_ref() == Some(&cwd)
+        || share_root.as_deref() == Some(cwd.as_path())
+      {
+        break;
+      }
+
+      let Some(parent) = cwd.parent() else {
+        break;
+      };
+
+      cwd = parent.to_path_buf();
+      depth += 1;
+    }
+
+    if let Some(fallback) = &self.config_fallback {
+      for name in names {
+        plan.extend(fallback.dirs().map(|(_, dir)| dir.join(name)));
+      }
+    }
+
+    plan
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::ConfigFallback;
+
+  #[test]
+  fn should_plan_the_walk_and_the_fallback() {
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/node_modules/pkg")
+      .exclude_dependency_dirs(true)
+      .max_depth(2)
+      .config_fallback(ConfigFallback::new().with_user_dir("/home/me/.config/tool"))
+      .build();
+
+    assert_eq!(
+      up_finder.plan_multi(&["a.json", "b.json"]),
+      [
+        PathBuf::from("/repo/a.json"),
+        PathBuf::from("/repo/b.json"),
+        PathBuf::from("/home/me/.config/tool/a.json"),
+        PathBuf::from("/home/me/.config/tool/b.json"),
+      ]
+    );
+  }
+
+  #[test]
+  fn should_plan_nothing_outside_the_restricted_root() {
+    let up_finder = UpFinder::builder()
+      .cwd("/srv/other")
+      .restrict_to("/srv/projects")
+      .build();
+
+    assert!(up_finder.plan("package.json").is_empty());
+
+    let up_finder = UpFinder::builder()
+      .cwd("/srv/projects/app")
+      .restrict_to("/srv/projects")
+      .build();
+
+    assert_eq!(
+      up_finder.plan("package.json"),
+      [
+        PathBuf::from("/srv/projects/app/package.json"),
+        PathBuf::from("/srv/projects/package.json"),
+      ]
+    );
+  }
+}
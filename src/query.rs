@@ -0,0 +1,246 @@
+use std::{error::Error, fmt, path::Path, str::FromStr, sync::Arc};
+
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use crate::StdFs;
+use crate::{
+  FindUpMatch, Fs, Glob, UpFinder, UpFinderConfig,
+  finder_config::{KINDS, parse_kind},
+};
+
+/// A search written as a compact string, such as `file:package.json`,
+/// `dir:.git` or `glob:*.lock;max-depth=5`, for command line arguments,
+/// environment variables and configuration files.
+///
+/// A query is a target followed by options, separated by `;`. The target is
+/// `file:NAME`, `dir:NAME`, `any:NAME` or `glob:PATTERN`, see [`Glob`], and a
+/// bare `NAME` is a file. The options are `max-depth=N`, `stop-at=PATH`,
+/// `restrict-to=PATH`, `kind=file|dir|any` for globs, and the switches
+/// `config-dir` and `exclude-dependency-dirs`, which also take `=true` or
+/// `=false`.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::Query;
+///
+/// let query: Query = "glob:*.lock;max-depth=5;exclude-dependency-dirs".parse().unwrap();
+/// let find_up = query.up_finder(".");
+///
+/// for found in query.find(&find_up) {
+///   println!("{}", found.path().display());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+  target: QueryTarget,
+  config: UpFinderConfig,
+}
+
+/// What a [`Query`] looks for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryTarget {
+  /// An entry with this name.
+  Name(String),
+  /// Entries whose names match the pattern.
+  Glob(Glob),
+}
+
+impl Query {
+  /// Parse `query`, see [`Query`].
+  pub fn parse(query: &str) -> Result<Self, QueryError> {
+    let mut parts = query.split(';');
+    let target = parts.next().unwrap_or_default();
+
+    let mut config = UpFinderConfig::default();
+    let (kind, name) = target.split_once(':').unwrap_or(("file", target));
+    let target = if kind == "glob" {
+      QueryTarget::Glob(Glob::new(name))
+    } else {
+      config.kind = Some(parse_kind(kind).ok_or_else(|| QueryError::UnknownKind(kind.to_owned()))?);
+      QueryTarget::Name(name.to_owned())
+    };
+    if name.is_empty() {
+      return Err(QueryError::Empty);
+    }
+
+    for option in parts {
+      let (key, value) = match option.split_once('=') {
+        Some((key, value)) => (key, Some(value)),
+        None => (option, None),
+      };
+      let invalid = || QueryError::InvalidValue {
+        option: key.to_owned(),
+        value: value.unwrap_or_default().to_owned(),
+      };
+      let switch = || match value {
+        None | Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        Some(_) => Err(invalid()),
+      };
+      let value = || value.filter(|value| !value.is_empty()).ok_or_else(invalid);
+
+      match key {
+        "max-depth" => config.max_depth = Some(value()?.parse().map_err(|_| invalid())?),
+        "stop-at" => config.stop_at = Some(value()?.into()),
+        "restrict-to" => config.restrict_to = Some(value()?.into()),
+        "kind" if matches!(target, QueryTarget::Glob(_)) => {
+          config.kind = Some(parse_kind(value()?).ok_or_else(invalid)?);
+        }
+        "config-dir" => config.config_dir = switch()?,
+        "exclude-dependency-dirs" => config.exclude_dependency_dirs = switch()?,
+        _ => return Err(QueryError::UnknownOption(key.to_owned())),
+      }
+    }
+
+    Ok(Self { target, config })
+  }
+
+  /// What the query looks for.
+  pub fn target(&self) -> &QueryTarget {
+    &self.target
+  }
+
+  /// The options of the query.
+  pub fn config(&self) -> &UpFinderConfig {
+    &self.config
+  }
+
+  /// An [`UpFinder`] starting at `cwd` with the options of the query, over
+  /// the real filesystem.
+  #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+  pub fn up_finder<P: AsRef<Path>>(&self, cwd: P) -> UpFinder<P> {
+    self.up_finder_with_fs(cwd, Arc::new(StdFs))
+  }
+
+  /// Like [`Query::up_finder`], searching `fs`.
+  pub fn up_finder_with_fs<P: AsRef<Path>>(&self, cwd: P, fs: Arc<dyn Fs>) -> UpFinder<P> {
+    self.config.up_finder_with_fs(cwd, fs)
+  }
+
+  /// Run the query with `up_finder`, usually one from [`Query::up_finder`].
+  pub fn find<P: AsRef<Path>>(&self, up_finder: &UpFinder<P>) -> Vec<FindUpMatch> {
+    match &self.target {
+      QueryTarget::Name(name) => up_finder.find_up_matches(name),
+      QueryTarget::Glob(glob) => up_finder.find_up_glob_matches(glob),
+    }
+  }
+}
+
+impl FromStr for Query {
+  type Err = QueryError;
+
+  fn from_str(query: &str) -> Result<Self, Self::Err> {
+    Self::parse(query)
+  }
+}
+
+/// Why a [`Query`] could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QueryError {
+  /// The query names nothing to look for.
+  Empty,
+  /// The target starts with something other than `file:`, `dir:`, `any:` or
+  /// `glob:`.
+  UnknownKind(String),
+  /// An option the query language doesn't have.
+  UnknownOption(String),
+  /// An option with a value it doesn't take.
+  InvalidValue { option: String, value: String },
+}
+
+impl fmt::Display for QueryError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Empty => f.write_str("the query names nothing to look for"),
+      Self::UnknownKind(kind) => write!(
+        f,
+        "unknown kind `{kind}`, expected glob or one of {}",
+        KINDS.join(", ")
+      ),
+      Self::UnknownOption(option) => write!(f, "unknown option `{option}`"),
+      Self::InvalidValue { option, value } => {
+        write!(f, "invalid value `{value}` for `{option}`")
+      }
+    }
+  }
+}
+
+impl Error for QueryError {}
+
+#[cfg(test)]
+mod tests {
+  use std::path::PathBuf;
+
+  use super::*;
+  use crate::{FindUpKind, MemoryFs};
+
+  #[test]
+  fn should_parse_targets_and_options() {
+    let query = Query::parse("dir:.git;stop-at=/repo;config-dir").unwrap();
+    assert_eq!(query.target(), &QueryTarget::Name(".git".to_owned()));
+    assert_eq!(query.config().kind(), FindUpKind::Dir);
+    assert_eq!(query.config().stop_at(), Some(Path::new("/repo")));
+    assert!(query.config().config_dir());
+
+    let query: Query = "package.json".parse().unwrap();
+    assert_eq!(query.config().kind(), FindUpKind::File);
+
+    let query = Query::parse("glob:*.lock;max-depth=5;kind=any").unwrap();
+    assert_eq!(query.target(), &QueryTarget::Glob(Glob::new("*.lock")));
+    assert_eq!(query.config().max_depth(), Some(5));
+    assert_eq!(query.config().kind(), FindUpKind::Any);
+  }
+
+  #[test]
+  fn should_reject_malformed_queries() {
+    assert_eq!(Query::parse("dir:"), Err(QueryError::Empty));
+    assert_eq!(
+      Query::parse("link:x"),
+      Err(QueryError::UnknownKind("link".to_owned()))
+    );
+    assert_eq!(
+      Query::parse("x;depth=2"),
+      Err(QueryError::UnknownOption("depth".to_owned()))
+    );
+    assert_eq!(
+      Query::parse("x;kind=dir"),
+      Err(QueryError::UnknownOption("kind".to_owned()))
+    );
+    assert_eq!(
+      Query::parse("x;max-depth=-1"),
+      Err(QueryError::InvalidValue {
+        option: "max-depth".to_owned(),
+        value: "-1".to_owned()
+      })
+    );
+    assert_eq!(
+      Query::parse("x;config-dir=yes").unwrap_err().to_string(),
+      "invalid value `yes` for `config-dir`"
+    );
+  }
+
+  #[test]
+  fn should_run_the_query() {
+    let fs = MemoryFs::new()
+      .with_file("/repo/Cargo.lock", "")
+      .with_file("/repo/app/yarn.lock", "")
+      .with_file("/yarn.lock", "");
+    let query = Query::parse("glob:*.lock;max-depth=1").unwrap();
+    let up_finder = query.up_finder_with_fs("/repo/app", Arc::new(fs));
+
+    let paths: Vec<PathBuf> = query
+      .find(&up_finder)
+      .into_iter()
+      .map(FindUpMatch::into_path)
+      .collect();
+
+    assert_eq!(
+      paths,
+      [
+        PathBuf::from("/repo/app/yarn.lock"),
+        PathBuf::from("/repo/Cargo.lock")
+      ]
+    );
+  }
+}
@@ -0,0 +1,189 @@
+use std::{
+  ffi::OsString,
+  future::Future,
+  io,
+  path::{Path, PathBuf},
+};
+
+use crate::{Metadata, UpFinder, hash::HashMap};
+
+/// The asynchronous counterpart of [`Fs`](crate::Fs) for trees that are not on
+/// local storage, such as SFTP servers, container filesystem APIs or cloud
+/// workspaces.
+///
+/// Paths keep their local meaning: the walk goes up with [`Path::parent`] and
+/// each candidate is `ancestor.join(name)`, it is up to the implementation to
+/// map them onto the remote tree. No runtime is required by the crate, the
+/// futures run on whatever executor the caller uses.
+///
+/// # Example
+///
+/// ```rust
+/// use std::{ffi::OsString, io, path::Path};
+///
+/// use up_finder::{FileType, Metadata, RemoteFs, UpFinder};
+///
+/// struct Sftp;
+///
+/// impl RemoteFs for Sftp {
+///   async fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+///     // Ask the server about `path` ...
+///     Ok(Metadata::new(FileType::File, 0))
+///   }
+///
+///   async fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+///     Ok(vec![])
+///   }
+///
+///   async fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+///     Ok(vec![])
+///   }
+/// }
+///
+/// async fn nearest_package_json() {
+///   let find_up = UpFinder::builder().cwd("/srv/app/src").build();
+///   let paths = find_up.find_up_remote(&Sftp, "package.json").await;
+///
+///   println!("{:#?}", paths);
+/// }
+/// ```
+pub trait RemoteFs: Send + Sync {
+  /// The metadata of the entry at `path`, following symlinks.
+  fn metadata(&self, path: &Path) -> impl Future<Output = io::Result<Metadata>> + Send;
+
+  /// The names of the entries in the directory at `path`.
+  fn read_dir(&self, path: &Path) -> impl Future<Output = io::Result<Vec<OsString>>> + Send;
+
+  /// The contents of the file at `path`.
+  fn read(&self, path: &Path) -> impl Future<Output = io::Result<Vec<u8>>> + Send;
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find a file in the current working directory and all parent directories
+  /// of a remote tree.
+  pub async fn find_up_remote<R: RemoteFs>(&self, fs: &R, name: &str) -> Vec<PathBuf> {
+    let mut paths = self.find_up_multi_remote(fs, &[name]).await;
+
+    paths.remove(name).unwrap_or_default()
+  }
+
+  /// Find multiple files in the current working directory and all parent
+  /// directories of a remote tree.
+  ///
+  /// Candidates are probed one after another, in the same order as
+  /// [`UpFinder::find_up_multi`] probes them locally.
+  pub async fn find_up_multi_remote<'a, R: RemoteFs>(
+    &self,
+    fs: &R,
+    names: &[&'a str],
+  ) -> HashMap<&'a str, Vec<PathBuf>> {
+    let mut paths: HashMap<&'a str, Vec<PathBuf>> =
+      names.iter().map(|&name| (name, vec![])).collect();
+
+    let mut cwd = Some(self.cwd.as_ref());
+    let mut depth = 0;
+
+    while let Some(dir) = cwd {
+      for &name in names {
+        let file = dir.join(name);
+
+        let Ok(metadata) = fs.metadata(&file).await else {
+          continue;
+        };
+
+        if self.is_kind(&metadata) {
+          paths.entry(name).or_default().push(file);
+        }
+      }
+
+      if self.ends_walk_at(dir, depth) {
+        break;
+      }
+
+      cwd = dir.parent();
+      depth += 1;
+    }
+
+    paths
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+  };
+
+  use super::*;
+  use crate::{Fs, MemoryFs};
+
+  /// A remote tree whose answers are only available after a yield.
+  struct Remote(MemoryFs);
+
+  async fn yield_now() {
+    let mut yielded = false;
+    std::future::poll_fn(|cx| {
+      if yielded {
+        Poll::Ready(())
+      } else {
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+      }
+    })
+    .await
+  }
+
+  impl RemoteFs for Remote {
+    async fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+      yield_now().await;
+      self.0.metadata(path)
+    }
+
+    async fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+      yield_now().await;
+      self.0.read_dir(path)
+    }
+
+    async fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+      yield_now().await;
+      self.0.read(path)
+    }
+  }
+
+  fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    loop {
+      if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+        return output;
+      }
+    }
+  }
+
+  #[test]
+  fn should_find_files_in_remote_trees() {
+    let remote = Remote(
+      MemoryFs::new()
+        .with_file("/srv/package.json", "{}")
+        .with_file("/srv/app/package.json", "{}")
+        .with_file("/srv/app/.nvmrc", "22")
+        .with_dir("/srv/app/src"),
+    );
+
+    let up_finder = UpFinder::builder().cwd("/srv/app/src").build();
+
+    let paths = block_on(up_finder.find_up_multi_remote(&remote, &["package.json", ".nvmrc"]));
+
+    assert_eq!(
+      paths["package.json"],
+      vec![
+        PathBuf::from("/srv/app/package.json"),
+        PathBuf::from("/srv/package.json")
+      ]
+    );
+    assert_eq!(paths[".nvmrc"], vec![PathBuf::from("/srv/app/.nvmrc")]);
+  }
+}
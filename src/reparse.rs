@@ -0,0 +1,156 @@
+use std::{io, path::Path};
+
+/// A directory entry that redirects to somewhere else.
+///
+/// On Windows these are NTFS reparse points: junctions and volume mount points
+/// (which share a tag), symbolic links and anything else carrying a reparse
+/// tag, e.g. Dev Drive or cloud placeholders. On Unix only symbolic links are
+/// reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReparsePoint {
+  /// A junction or volume mount point (`IO_REPARSE_TAG_MOUNT_POINT`).
+  MountPoint,
+  /// A symbolic link.
+  Symlink,
+  /// Any other reparse point, with its tag.
+  Other(u32),
+}
+
+/// What the walk does when an ancestor is a [`ReparsePoint`].
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{ReparsePointPolicy, UpFinder};
+///
+/// // Don't wander from a pnpm junction into the lexical parent of the link.
+/// let find_up = UpFinder::builder()
+///   .cwd(".")
+///   .reparse_points(ReparsePointPolicy::StopAt)
+///   .build();
+/// let paths = find_up.find_up("package.json");
+///
+/// println!("{:#?}", paths);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReparsePointPolicy {
+  /// Walk through reparse points like through any other directory.
+  #[default]
+  Follow,
+  /// Search the first reparse point reached, then stop.
+  StopAt,
+  /// Stop below the first reparse point above the start directory, without
+  /// searching it. A start directory that is itself a reparse point is still
+  /// searched.
+  StopBelow,
+}
+
+/// Whether the entry at `path` itself is a reparse point, without following it.
+#[cfg_attr(
+  all(target_family = "wasm", target_os = "unknown"),
+  allow(dead_code, reason = "there is no `StdFs` to call it")
+)]
+pub(crate) fn reparse_point(path: &Path) -> io::Result<Option<ReparsePoint>> {
+  if path.as_os_str().is_empty() {
+    return Ok(None);
+  }
+
+  sys::reparse_point(path)
+}
+
+#[cfg(windows)]
+mod sys {
+  use std::{io, os::windows::ffi::OsStrExt, path::Path};
+
+  use super::ReparsePoint;
+
+  const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+  const IO_REPARSE_TAG_MOUNT_POINT: u32 = 0xA000_0003;
+  const IO_REPARSE_TAG_SYMLINK: u32 = 0xA000_000C;
+  const INVALID_HANDLE_VALUE: isize = -1;
+
+  #[repr(C)]
+  struct FileTime {
+    low: u32,
+    high: u32,
+  }
+
+  #[repr(C)]
+  struct Win32FindDataW {
+    file_attributes: u32,
+    creation_time: FileTime,
+    last_access_time: FileTime,
+    last_write_time: FileTime,
+    file_size_high: u32,
+    file_size_low: u32,
+    reserved0: u32,
+    reserved1: u32,
+    file_name: [u16; 260],
+    alternate_file_name: [u16; 14],
+  }
+
+  #[link(name = "kernel32")]
+  unsafe extern "system" {
+    fn FindFirstFileW(file_name: *const u16, find_data: *mut Win32FindDataW) -> isize;
+    fn FindClose(find_file: isize) -> i32;
+  }
+
+  pub(super) fn reparse_point(path: &Path) -> io::Result<Option<ReparsePoint>> {
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut data = std::mem::MaybeUninit::<Win32FindDataW>::uninit();
+
+    // SAFETY: `wide` is NUL terminated and `data` is valid for writes.
+    let handle = unsafe { FindFirstFileW(wide.as_ptr(), data.as_mut_ptr()) };
+
+    if handle == INVALID_HANDLE_VALUE {
+      return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: `FindFirstFileW` succeeded, so `data` is initialized and `handle`
+    // is a search handle that has to be closed.
+    let data = unsafe {
+      FindClose(handle);
+      data.assume_init()
+    };
+
+    if data.file_attributes & FILE_ATTRIBUTE_REPARSE_POINT == 0 {
+      return Ok(None);
+    }
+
+    // For reparse points `dwReserved0` holds the reparse tag.
+    Ok(Some(match data.reserved0 {
+      IO_REPARSE_TAG_MOUNT_POINT => ReparsePoint::MountPoint,
+      IO_REPARSE_TAG_SYMLINK => ReparsePoint::Symlink,
+      tag => ReparsePoint::Other(tag),
+    }))
+  }
+}
+
+#[cfg(not(windows))]
+mod sys {
+  use std::{fs, io, path::Path};
+
+  use super::ReparsePoint;
+
+  pub(super) fn reparse_point(path: &Path) -> io::Result<Option<ReparsePoint>> {
+    let metadata = fs::symlink_metadata(path)?;
+
+    Ok(
+      metadata
+        .file_type()
+        .is_symlink()
+        .then_some(ReparsePoint::Symlink),
+    )
+  }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_not_report_plain_directories() {
+    assert_eq!(reparse_point(Path::new("fixtures/a")).unwrap(), None);
+    assert_eq!(reparse_point(Path::new("")).unwrap(), None);
+  }
+}
@@ -0,0 +1,286 @@
+use std::{
+  fmt,
+  path::{Path, PathBuf},
+  time::Duration,
+};
+
+use crate::trace;
+
+/// What a search did, returned by [`UpFinder::find_up_report`](crate::UpFinder::find_up_report)
+/// and [`UpFinder::find_up_multi_report`](crate::UpFinder::find_up_multi_report).
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::UpFinder;
+///
+/// let find_up = UpFinder::builder().cwd(".").max_depth(2).build();
+/// let (matches, report) = find_up.find_up_report("package.json");
+///
+/// println!(
+///   "{} matches in {} directories with {} filesystem operations, stopped at {}",
+///   matches.len(),
+///   report.dirs_visited(),
+///   report.fs_operations(),
+///   report.stop_reason(),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SearchReport {
+  pub(crate) dirs_visited: usize,
+  pub(crate) fs_operations: usize,
+  pub(crate) elapsed: Option<Duration>,
+  pub(crate) stop_reason: StopReason,
+  pub(crate) visits: Vec<Visit>,
+  pub(crate) record_visits: bool,
+}
+
+impl SearchReport {
+  /// How many levels the walk reached, skipped ones included.
+  pub fn dirs_visited(&self) -> usize {
+    self.dirs_visited
+  }
+
+  /// How many calls the search made to the [`Fs`](crate::Fs), also counting
+  /// those abandoned after a timeout.
+  pub fn fs_operations(&self) -> usize {
+    self.fs_operations
+  }
+
+  /// How long the search took. Not measured on `wasm32-unknown-unknown`,
+  /// which has no clock.
+  pub fn elapsed(&self) -> Option<Duration> {
+    self.elapsed
+  }
+
+  /// Why the walk didn't go on to the next parent.
+  pub fn stop_reason(&self) -> StopReason {
+    self.stop_reason
+  }
+
+  /// Every level the walk reached and the candidates it looked up there, in
+  /// order. Only recorded with
+  /// [`record_visits`](crate::UpFinderBuilder::record_visits).
+  pub fn visits(&self) -> &[Visit] {
+    &self.visits
+  }
+
+  /// The walk reached another level.
+  pub(crate) fn level(&mut self, dir: &Path, depth: usize) {
+    trace::level(dir, depth);
+    self.dirs_visited += 1;
+
+    if self.record_visits {
+      self.visits.push(Visit {
+        dir: dir.to_path_buf(),
+        depth,
+        candidates: vec![],
+      });
+    }
+  }
+
+  /// The walk looks up `path` on the current level.
+  pub(crate) fn candidate(&mut self, path: &Path) {
+    trace::candidate(path);
+
+    if let Some(visit) = self.visits.last_mut() {
+      visit.candidates.push(path.to_path_buf());
+    }
+  }
+
+  /// The search called the filesystem.
+  pub(crate) fn fs_operation(&mut self) {
+    self.fs_operations += 1;
+  }
+
+  /// The walk ends at `dir`.
+  pub(crate) fn stop(&mut self, dir: &Path, reason: StopReason) {
+    trace::stopped(dir, &reason);
+    self.stop_reason = reason;
+  }
+}
+
+/// A level of the walk, see [`SearchReport::visits`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Visit {
+  dir: PathBuf,
+  depth: usize,
+  candidates: Vec<PathBuf>,
+}
+
+impl Visit {
+  /// The directory of the level.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// How many levels above the start directory it is.
+  pub fn depth(&self) -> usize {
+    self.depth
+  }
+
+  /// The paths looked up on the level, including those in its `.config`
+  /// directory, in order. Empty for skipped levels.
+  pub fn candidates(&self) -> &[PathBuf] {
+    &self.candidates
+  }
+}
+
+/// Why a walk ended, see [`SearchReport::stop_reason`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum StopReason {
+  /// The filesystem root was searched.
+  #[default]
+  Root,
+  /// The [`Fs`](crate::Fs) reported a boundary, such as a WASI preopened
+  /// directory.
+  Boundary,
+  /// The [`stop_at`](crate::UpFinderBuilder::stop_at) directory was searched.
+  StopAt,
+  /// The [`max_depth`](crate::UpFinderBuilder::max_depth) level was searched.
+  MaxDepth,
+  /// The [`restrict_to`](crate::UpFinderBuilder::restrict_to) root was searched.
+  RestrictTo,
+  /// The `\\server\share\` root of a UNC path was searched.
+  ShareRoot,
+  /// The matcher returned [`FindUpResult::Stop`](crate::FindUpResult::Stop)
+  /// or [`FindUpResult::Last`](crate::FindUpResult::Last).
+  Matcher,
+  /// A reparse point ended the walk, see [`ReparsePointPolicy`](crate::ReparsePointPolicy).
+  ReparsePoint,
+  /// A directory owned by another user ended the walk, see
+  /// [`OwnershipPolicy::Stop`](crate::OwnershipPolicy::Stop).
+  ForeignOwner,
+  /// A directory that was already searched under another path ended the walk,
+  /// see [`BindMountPolicy::Stop`](crate::BindMountPolicy::Stop).
+  BindMount,
+  /// The [`env_override`](crate::UpFinderBuilder::env_override) variable named
+  /// the match, there was no walk.
+  EnvOverride,
+  /// The progress callback of
+  /// [`UpFinder::find_up_with_progress`](crate::UpFinder::find_up_with_progress)
+  /// cancelled the search.
+  Cancelled,
+  /// The search could not be run, the `try_` methods report why.
+  Error,
+}
+
+impl fmt::Display for StopReason {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Self::Root => "the filesystem root",
+      Self::Boundary => "a filesystem boundary",
+      Self::StopAt => "the stop_at directory",
+      Self::MaxDepth => "the maximum depth",
+      Self::RestrictTo => "the restrict_to root",
+      Self::ShareRoot => "the share root",
+      Self::Matcher => "the matcher",
+      Self::ReparsePoint => "a reparse point",
+      Self::ForeignOwner => "a directory owned by another user",
+      Self::BindMount => "a directory already searched under another path",
+      Self::EnvOverride => "the environment override",
+      Self::Cancelled => "the progress callback",
+      Self::Error => "an error",
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{path::PathBuf, sync::Arc};
+
+  use super::*;
+  use crate::{MemoryFs, UpFinder};
+
+  #[test]
+  fn should_report_the_levels_operations_and_reason_of_the_walk() {
+    let fs = Arc::new(
+      MemoryFs::new()
+        .with_file("/repo/.editorconfig", "")
+        .with_file("/repo/app/.editorconfig", "")
+        .with_dir("/repo/app/src"),
+    );
+    let up_finder = || UpFinder::builder().cwd("/repo/app/src").fs(fs.clone());
+
+    let (matches, report) = up_finder()
+      .stop_at("/repo")
+      .build()
+      .find_up_report(".editorconfig");
+
+    assert_eq!(
+      matches
+        .into_iter()
+        .map(|found| found.into_path())
+        .collect::<Vec<_>>(),
+      [
+        PathBuf::from("/repo/app/.editorconfig"),
+        PathBuf::from("/repo/.editorconfig")
+      ]
+    );
+    assert_eq!(report.dirs_visited(), 3);
+    assert_eq!(report.fs_operations(), 8);
+    assert_eq!(report.stop_reason(), StopReason::StopAt);
+    assert!(report.elapsed().is_some());
+
+    let (_, report) = up_finder()
+      .max_depth(1)
+      .build()
+      .find_up_multi_report(&[".editorconfig", ".prettierrc"]);
+
+    assert_eq!(report.dirs_visited(), 2);
+    assert_eq!(report.fs_operations(), 7);
+    assert_eq!(report.stop_reason(), StopReason::MaxDepth);
+
+    let (_, report) = up_finder().build().find_up_report(".prettierrc");
+
+    assert_eq!(report.dirs_visited(), 4);
+    assert_eq!(report.stop_reason(), StopReason::Root);
+  }
+
+  #[test]
+  fn should_record_the_visited_levels_and_their_candidates() {
+    let fs = Arc::new(MemoryFs::new().with_dir("/repo/app"));
+    let up_finder = UpFinder::builder()
+      .cwd("/repo/app")
+      .fs(fs)
+      .config_dir(true)
+      .max_depth(1)
+      .record_visits(true)
+      .build();
+
+    let (_, report) = up_finder.find_up_multi_report(&["a.toml", "b.toml"]);
+
+    let visits: Vec<(&Path, usize, &[PathBuf])> = report
+      .visits()
+      .iter()
+      .map(|visit| (visit.dir(), visit.depth(), visit.candidates()))
+      .collect();
+
+    assert_eq!(
+      visits,
+      [
+        (
+          Path::new("/repo/app"),
+          0,
+          &[
+            PathBuf::from("/repo/app/a.toml"),
+            PathBuf::from("/repo/app/.config/a.toml"),
+            PathBuf::from("/repo/app/b.toml"),
+            PathBuf::from("/repo/app/.config/b.toml"),
+          ][..]
+        ),
+        (
+          Path::new("/repo"),
+          1,
+          &[
+            PathBuf::from("/repo/a.toml"),
+            PathBuf::from("/repo/.config/a.toml"),
+            PathBuf::from("/repo/b.toml"),
+            PathBuf::from("/repo/.config/b.toml"),
+          ][..]
+        ),
+      ]
+    );
+  }
+}
@@ -0,0 +1,94 @@
+use std::path::{self, Component, Path, PathBuf};
+
+use crate::{FindUpError, UpFinder};
+
+/// `cwd` and `root` in comparable form, or an error if `cwd` is not inside
+/// `root`. Both are resolved lexically, and made absolute if only one of them
+/// is, so `root/../elsewhere` is not mistaken for a path inside `root`.
+pub(crate) fn restrict(cwd: &Path, root: &Path) -> Result<(PathBuf, PathBuf), FindUpError> {
+  let (cwd, root) = if cwd.is_absolute() == root.is_absolute() {
+    (clean(cwd), clean(root))
+  } else {
+    (clean(&path::absolute(cwd)?), clean(&path::absolute(root)?))
+  };
+
+  let inside = cwd
+    .strip_prefix(&root)
+    .is_ok_and(|rest| !matches!(rest.components().next(), Some(Component::ParentDir)));
+
+  if !inside {
+    return Err(FindUpError::OutsideRoot { path: cwd, root });
+  }
+
+  Ok((cwd, root))
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// The current working directory and the `restrict_to` root in comparable
+  /// form, for the searches that walk on their own instead of through
+  /// [`Levels`](crate::levels::Levels).
+  pub(crate) fn restricted_cwd(&self) -> Result<(PathBuf, Option<PathBuf>), FindUpError> {
+    match &self.restrict_to {
+      Some(root) => {
+        let (cwd, root) = restrict(self.cwd.as_ref(), root)?;
+        Ok((cwd, Some(root)))
+      }
+      None => Ok((self.cwd.as_ref().to_path_buf(), None)),
+    }
+  }
+}
+
+/// Resolve `.` and `..` components without touching the filesystem, the way
+/// `restrict_to` compares paths. `..` of a root is the root itself, leading
+/// `..` of a relative path are kept.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::Path;
+///
+/// use up_finder::clean;
+///
+/// assert_eq!(clean(Path::new("a/./b/../c")), Path::new("a/c"));
+/// assert_eq!(clean(Path::new("../a/..")), Path::new(".."));
+/// ```
+pub fn clean(path: &Path) -> PathBuf {
+  let mut cleaned = PathBuf::new();
+
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir => match cleaned.components().next_back() {
+        Some(Component::Normal(_)) => {
+          cleaned.pop();
+        }
+        // `..` of a root is the root itself.
+        Some(Component::RootDir | Component::Prefix(_)) => {}
+        _ => cleaned.push(component),
+      },
+      component => cleaned.push(component),
+    }
+  }
+
+  cleaned
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_resolve_parent_components() {
+    assert_eq!(clean(Path::new("a/./b/../c")), PathBuf::from("a/c"));
+    assert_eq!(clean(Path::new("../a/..")), PathBuf::from(".."));
+    assert_eq!(clean(Path::new("/..")), PathBuf::from("/"));
+  }
+
+  #[test]
+  fn should_reject_paths_outside_the_root() {
+    assert!(restrict(Path::new("srv/app/src"), Path::new("srv/app")).is_ok());
+    assert!(restrict(Path::new("srv/app/../other"), Path::new("srv/app")).is_err());
+    assert!(restrict(Path::new("srv/application"), Path::new("srv/app")).is_err());
+    assert!(restrict(Path::new("../app"), Path::new(".")).is_err());
+  }
+}
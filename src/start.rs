@@ -0,0 +1,59 @@
+use std::{io, path::PathBuf};
+
+/// Where a search starts, for the common cases that aren't a fixed path.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{StartFrom, UpFinder};
+///
+/// // Config shipped next to or above the binary of a portable application.
+/// let find_up = UpFinder::builder()
+///   .cwd(StartFrom::CurrentExe.dir()?)
+///   .build();
+/// let paths = find_up.find_up("app.toml");
+///
+/// println!("{:#?}", paths);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartFrom {
+  /// The current directory of the process.
+  CurrentDir,
+  /// The directory containing the executable of the process, see
+  /// [`std::env::current_exe`]. If the executable was started through a
+  /// symlink, that may be the directory of the link.
+  CurrentExe,
+}
+
+impl StartFrom {
+  /// The directory to pass to [`cwd`](crate::UpFinderBuilder::cwd).
+  pub fn dir(self) -> io::Result<PathBuf> {
+    match self {
+      Self::CurrentDir => std::env::current_dir(),
+      Self::CurrentExe => {
+        let exe = std::env::current_exe()?;
+        exe
+          .parent()
+          .map(PathBuf::from)
+          .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "the executable has no parent"))
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_start_next_to_the_executable() {
+    let exe = std::env::current_exe().unwrap();
+
+    assert_eq!(StartFrom::CurrentExe.dir().unwrap(), exe.parent().unwrap());
+    assert_eq!(
+      StartFrom::CurrentDir.dir().unwrap(),
+      std::env::current_dir().unwrap()
+    );
+  }
+}
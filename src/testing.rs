@@ -0,0 +1,212 @@
+//! Temporary directory trees for tests, with the `testing` feature.
+
+use std::{
+  fs, io,
+  path::{Component, Path, PathBuf},
+  sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::UpFinder;
+
+/// A description of a directory tree, created by [`FixtureTree::build`] in a
+/// fresh temporary directory.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::testing::FixtureTree;
+///
+/// let fixture = FixtureTree::new()
+///   .file("package.json", r#"{ "name": "repo" }"#)
+///   .file("app/package.json", r#"{ "name": "app" }"#)
+///   .dir("app/src")
+///   .build()?;
+///
+/// let paths = fixture.up_finder("app/src")?.find_up("package.json");
+///
+/// assert_eq!(
+///   paths,
+///   [fixture.path("app/package.json")?, fixture.path("package.json")?]
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FixtureTree {
+  entries: Vec<(PathBuf, Option<String>)>,
+}
+
+impl FixtureTree {
+  /// An empty tree.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Add a file with `contents`, and its parent directories, at the relative
+  /// `path`.
+  pub fn file(mut self, path: impl AsRef<Path>, contents: impl Into<String>) -> Self {
+    self
+      .entries
+      .push((path.as_ref().to_path_buf(), Some(contents.into())));
+    self
+  }
+
+  /// Add a directory, and its parents, at the relative `path`.
+  pub fn dir(mut self, path: impl AsRef<Path>) -> Self {
+    self.entries.push((path.as_ref().to_path_buf(), None));
+    self
+  }
+
+  /// Create the tree in a new directory under [`std::env::temp_dir`], removed
+  /// again when the [`Fixture`] is dropped.
+  ///
+  /// Fails with [`io::ErrorKind::InvalidInput`] if a path is absolute or
+  /// contains `..`, and so would point outside of the tree.
+  pub fn build(&self) -> io::Result<Fixture> {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+    for (path, _) in &self.entries {
+      relative(path)?;
+    }
+
+    let root = loop {
+      let root = std::env::temp_dir().join(format!(
+        "up_finder-{}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+      ));
+
+      match fs::create_dir(&root) {
+        Ok(()) => break root,
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+        Err(err) => return Err(err),
+      }
+    };
+    let fixture = Fixture { root };
+
+    for (path, contents) in &self.entries {
+      let path = fixture.path(path)?;
+
+      match contents {
+        Some(contents) => {
+          if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+          }
+          fs::write(path, contents)?;
+        }
+        None => fs::create_dir_all(path)?,
+      }
+    }
+
+    Ok(fixture)
+  }
+}
+
+/// A directory tree created by [`FixtureTree::build`], removed on drop.
+#[derive(Debug)]
+pub struct Fixture {
+  root: PathBuf,
+}
+
+impl Fixture {
+  /// The temporary directory holding the tree.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  /// The absolute path of the relative `path` in the tree. Fails like
+  /// [`FixtureTree::build`] for paths outside of the tree.
+  pub fn path(&self, path: impl AsRef<Path>) -> io::Result<PathBuf> {
+    Ok(self.root.join(relative(path.as_ref())?))
+  }
+
+  /// An [`UpFinder`] starting at the relative directory `cwd` that never
+  /// searches above the root of the tree, so files elsewhere in the temporary
+  /// directory don't leak into the results.
+  pub fn up_finder(&self, cwd: impl AsRef<Path>) -> io::Result<UpFinder<PathBuf>> {
+    Ok(
+      UpFinder::builder()
+        .cwd(self.path(cwd)?)
+        .stop_at(self.root.clone())
+        .build(),
+    )
+  }
+}
+
+/// `path`, if it stays inside of the directory it is relative to.
+fn relative(path: &Path) -> io::Result<&Path> {
+  let escapes = path
+    .components()
+    .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir));
+
+  if escapes {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!(
+        "{} is not a relative path inside of the tree",
+        path.display()
+      ),
+    ));
+  }
+
+  Ok(path)
+}
+
+impl Drop for Fixture {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.root);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::FindUpKind;
+
+  #[test]
+  fn should_create_the_tree_and_remove_it_on_drop() {
+    let fixture = FixtureTree::new()
+      .file(".nvmrc", "22")
+      .file("a/b/.nvmrc", "20")
+      .dir("a/b/c/d")
+      .build()
+      .unwrap();
+
+    assert_eq!(
+      fs::read_to_string(fixture.path("a/b/.nvmrc").unwrap()).unwrap(),
+      "20"
+    );
+    assert_eq!(
+      fixture.up_finder("a/b/c/d").unwrap().find_up(".nvmrc"),
+      [
+        fixture.path("a/b/.nvmrc").unwrap(),
+        fixture.path(".nvmrc").unwrap()
+      ]
+    );
+
+    let dirs = UpFinder::builder()
+      .cwd(fixture.path("a/b/c/d").unwrap())
+      .kind(FindUpKind::Dir)
+      .stop_at(fixture.root().to_path_buf())
+      .build()
+      .find_up("c");
+    assert_eq!(dirs, [fixture.path("a/b/c").unwrap()]);
+
+    let root = fixture.root().to_path_buf();
+    drop(fixture);
+    assert!(!root.exists());
+  }
+
+  #[test]
+  fn should_reject_paths_outside_of_the_tree() {
+    for path in ["../escape", "a/../../escape", "/etc/escape"] {
+      let error = FixtureTree::new().file(path, "").build().unwrap_err();
+      assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    let fixture = FixtureTree::new().build().unwrap();
+    assert_eq!(
+      fixture.path("..").unwrap_err().kind(),
+      io::ErrorKind::InvalidInput
+    );
+  }
+}
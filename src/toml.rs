@@ -0,0 +1,100 @@
+use ::toml::{Table, Value};
+
+/// A filter on the TOML contents of candidates, for use with
+/// [`UpFinder::find_up_where`](crate::UpFinder::find_up_where).
+///
+/// Candidates that are not valid TOML never match. Paths are dotted keys like
+/// `package.edition`, quoting is not supported.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{TomlMatcher, UpFinder};
+///
+/// let find_up = UpFinder::builder().cwd(".").build();
+///
+/// let workspace_root = TomlMatcher::has_table("workspace");
+/// let paths = find_up.find_up_where("Cargo.toml", |_, contents| {
+///   workspace_root.matches(contents)
+/// });
+///
+/// println!("{:#?}", paths);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TomlMatcher(Condition);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+  HasKey(String),
+  HasTable(String),
+  PathEquals(String, Value),
+}
+
+impl TomlMatcher {
+  /// Match documents with a value at `path`, whatever it is.
+  pub fn has_key(path: impl Into<String>) -> Self {
+    Self(Condition::HasKey(path.into()))
+  }
+
+  /// Match documents with a table at `path`, e.g. `workspace` for a Cargo
+  /// workspace root.
+  pub fn has_table(path: impl Into<String>) -> Self {
+    Self(Condition::HasTable(path.into()))
+  }
+
+  /// Match documents where the value at `path` equals `value`.
+  pub fn path_equals(path: impl Into<String>, value: impl Into<Value>) -> Self {
+    Self(Condition::PathEquals(path.into(), value.into()))
+  }
+
+  /// Whether `contents` satisfy the condition.
+  pub fn matches(&self, contents: &[u8]) -> bool {
+    let Some(document) = std::str::from_utf8(contents)
+      .ok()
+      .and_then(|contents| contents.parse::<Table>().ok())
+    else {
+      return false;
+    };
+
+    match &self.0 {
+      Condition::HasKey(path) => lookup(&document, path).is_some(),
+      Condition::HasTable(path) => lookup(&document, path).is_some_and(Value::is_table),
+      Condition::PathEquals(path, value) => lookup(&document, path) == Some(value),
+    }
+  }
+}
+
+fn lookup<'a>(document: &'a Table, path: &str) -> Option<&'a Value> {
+  let mut keys = path.split('.');
+  let mut value = document.get(keys.next()?)?;
+
+  for key in keys {
+    value = value.as_table()?.get(key)?;
+  }
+
+  Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_match_tables_and_values() {
+    let cargo_toml = br#"
+      [workspace]
+      members = ["crates/*"]
+
+      [package]
+      name = "app"
+      edition = "2024"
+    "#;
+
+    assert!(TomlMatcher::has_table("workspace").matches(cargo_toml));
+    assert!(!TomlMatcher::has_table("package.name").matches(cargo_toml));
+    assert!(TomlMatcher::has_key("workspace.members").matches(cargo_toml));
+    assert!(TomlMatcher::path_equals("package.edition", "2024").matches(cargo_toml));
+    assert!(!TomlMatcher::path_equals("package.name", "lib").matches(cargo_toml));
+    assert!(!TomlMatcher::has_key("workspace").matches(b"[workspace"));
+  }
+}
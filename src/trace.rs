@@ -0,0 +1,190 @@
+//! The `tracing` instrumentation of the walk. Without the `tracing` feature
+//! every function here does nothing.
+//!
+//! Searches get a `find_up` span at the `DEBUG` level. Levels, matches and the
+//! reason the walk ended are `DEBUG` events inside it, every candidate checked
+//! and every candidate or level skipped is a `TRACE` event.
+use std::{fmt::Display, path::Path};
+
+/// Entered for the duration of a search, see [`search`].
+pub(crate) struct SearchSpan {
+  #[cfg(feature = "tracing")]
+  _span: tracing::span::EnteredSpan,
+}
+
+/// Enter the span of a search starting at `cwd`.
+pub(crate) fn search(cwd: &Path, names: &[&str]) -> SearchSpan {
+  #[cfg(feature = "tracing")]
+  return SearchSpan {
+    _span: tracing::debug_span!("find_up", cwd = %cwd.display(), ?names).entered(),
+  };
+
+  #[cfg(not(feature = "tracing"))]
+  {
+    let _ = (cwd, names);
+    SearchSpan {}
+  }
+}
+
+/// The span of a search done a part at a time, such as the iteration of a
+/// [`FindUpIter`](crate::FindUpIter), entered for every part with
+/// [`PartedSearch::enter`].
+#[derive(Debug)]
+pub(crate) struct PartedSearch {
+  #[cfg(feature = "tracing")]
+  span: tracing::Span,
+}
+
+impl PartedSearch {
+  /// The span of a search starting at `cwd`.
+  pub(crate) fn new(cwd: &Path, names: &[&str]) -> Self {
+    #[cfg(feature = "tracing")]
+    return Self {
+      span: tracing::debug_span!("find_up", cwd = %cwd.display(), ?names),
+    };
+
+    #[cfg(not(feature = "tracing"))]
+    {
+      let _ = (cwd, names);
+      Self {}
+    }
+  }
+
+  /// Enter the span for the duration of a part of the search.
+  pub(crate) fn enter(&self) -> SearchSpan {
+    SearchSpan {
+      #[cfg(feature = "tracing")]
+      _span: self.span.clone().entered(),
+    }
+  }
+}
+
+/// The walk reached `dir`, `depth` levels above the start directory.
+pub(crate) fn level(dir: &Path, depth: usize) {
+  #[cfg(feature = "tracing")]
+  tracing::debug!(dir = %dir.display(), depth, "searching level");
+
+  #[cfg(not(feature = "tracing"))]
+  let _ = (dir, depth);
+}
+
+/// `dir` or what is left of it isn't searched.
+pub(crate) fn level_skipped(dir: &Path, reason: &dyn Display) {
+  #[cfg(feature = "tracing")]
+  tracing::trace!(dir = %dir.display(), %reason, "skipping level");
+
+  #[cfg(not(feature = "tracing"))]
+  let _ = (dir, reason);
+}
+
+/// `path` is looked up.
+pub(crate) fn candidate(path: &Path) {
+  #[cfg(feature = "tracing")]
+  tracing::trace!(path = %path.display(), "checking candidate");
+
+  #[cfg(not(feature = "tracing"))]
+  let _ = path;
+}
+
+/// `path` isn't a match.
+pub(crate) fn candidate_skipped(path: &Path, reason: &dyn Display) {
+  #[cfg(feature = "tracing")]
+  tracing::trace!(path = %path.display(), %reason, "skipping candidate");
+
+  #[cfg(not(feature = "tracing"))]
+  let _ = (path, reason);
+}
+
+/// `path` is reported as a match.
+pub(crate) fn matched(path: &Path, depth: usize) {
+  #[cfg(feature = "tracing")]
+  tracing::debug!(path = %path.display(), depth, "found match");
+
+  #[cfg(not(feature = "tracing"))]
+  let _ = (path, depth);
+}
+
+/// The walk ends at `dir`.
+pub(crate) fn stopped(dir: &Path, reason: &dyn Display) {
+  #[cfg(feature = "tracing")]
+  tracing::debug!(dir = %dir.display(), %reason, "stopping walk");
+
+  #[cfg(not(feature = "tracing"))]
+  let _ = (dir, reason);
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+  use std::sync::{Arc, Mutex};
+
+  use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+  };
+
+  use crate::{MemoryFs, UpFinder};
+
+  /// Records the message of every event.
+  #[derive(Default)]
+  struct Messages(Arc<Mutex<Vec<String>>>);
+
+  struct MessageVisitor<'a>(&'a mut Vec<String>);
+
+  impl Visit for MessageVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+      if field.name() == "message" {
+        self.0.push(format!("{value:?}"));
+      }
+    }
+  }
+
+  impl Subscriber for Messages {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+      true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+      Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+      event.record(&mut MessageVisitor(&mut self.0.lock().unwrap()));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+  }
+
+  #[test]
+  fn should_emit_events_for_every_level_and_candidate() {
+    let fs = MemoryFs::new().with_file("/a/package.json", "");
+    let find_up = UpFinder::builder()
+      .cwd("/a/b")
+      .fs(Arc::new(fs))
+      .max_depth(1)
+      .build();
+
+    let subscriber = Messages::default();
+    let messages = Arc::clone(&subscriber.0);
+    tracing::subscriber::with_default(subscriber, || find_up.find_up("package.json"));
+
+    assert_eq!(
+      *messages.lock().unwrap(),
+      [
+        "searching level",
+        "checking candidate",
+        "skipping candidate",
+        "searching level",
+        "checking candidate",
+        "found match",
+        "stopping walk",
+      ]
+    );
+  }
+}
@@ -0,0 +1,86 @@
+use std::path::{Path, PathBuf};
+
+/// The `\\server\share\` root of a UNC path, `None` for any other path.
+///
+/// A share root has no parent in `std::path`, so the walk naturally ends there
+/// instead of trying `\\server`, which is not a directory.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::Path;
+///
+/// use up_finder::share_root;
+///
+/// if cfg!(windows) {
+///   assert_eq!(
+///     share_root(Path::new(r"\\server\share\project")).unwrap(),
+///     Path::new(r"\\server\share\")
+///   );
+/// }
+/// ```
+pub fn share_root(path: &Path) -> Option<PathBuf> {
+  sys::share_root(path)
+}
+
+#[cfg(windows)]
+mod sys {
+  use std::path::{Component, Path, PathBuf, Prefix};
+
+  pub(super) fn share_root(path: &Path) -> Option<PathBuf> {
+    let Some(Component::Prefix(prefix)) = path.components().next() else {
+      return None;
+    };
+
+    match prefix.kind() {
+      Prefix::UNC(..) | Prefix::VerbatimUNC(..) => {
+        let mut root = PathBuf::from(prefix.as_os_str());
+        root.push(r"\");
+        Some(root)
+      }
+      _ => None,
+    }
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_share_roots() {
+      assert_eq!(
+        share_root(Path::new(r"\\server\share\a\b")),
+        Some(PathBuf::from(r"\\server\share\"))
+      );
+      assert_eq!(
+        share_root(Path::new(r"\\?\UNC\server\share\a")),
+        Some(PathBuf::from(r"\\?\UNC\server\share\"))
+      );
+      assert_eq!(share_root(Path::new(r"C:\a")), None);
+    }
+
+    #[test]
+    fn should_not_ascend_above_share_roots() {
+      let share = Path::new(r"\\server\share\a");
+
+      let ancestors: Vec<_> = share.ancestors().collect();
+
+      assert_eq!(
+        ancestors,
+        vec![
+          Path::new(r"\\server\share\a"),
+          Path::new(r"\\server\share\")
+        ]
+      );
+    }
+  }
+}
+
+#[cfg(not(windows))]
+mod sys {
+  use std::path::{Path, PathBuf};
+
+  pub(super) fn share_root(_path: &Path) -> Option<PathBuf> {
+    None
+  }
+}
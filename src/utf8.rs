@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::{UpFinder, hash::HashMap};
+
+/// An [`UpFinder`] rooted at a UTF-8 path.
+///
+/// # Example
+///
+/// ```rust
+/// use camino::Utf8PathBuf;
+/// use up_finder::Utf8UpFinder;
+///
+/// let find_up = Utf8UpFinder::builder().cwd(Utf8PathBuf::from(".")).build();
+/// let paths: Vec<Utf8PathBuf> = find_up.find_up_utf8("package.json");
+///
+/// println!("{:#?}", paths);
+/// ```
+pub type Utf8UpFinder = UpFinder<Utf8PathBuf>;
+
+impl<P: AsRef<Path> + AsRef<Utf8Path>> UpFinder<P> {
+  /// Like [`UpFinder::find_up`], returning UTF-8 paths.
+  pub fn find_up_utf8(&self, name: &str) -> Vec<Utf8PathBuf> {
+    into_utf8(self.find_up(name))
+  }
+
+  /// Like [`UpFinder::find_up_multi`], returning UTF-8 paths.
+  pub fn find_up_multi_utf8<'a>(&self, names: &[&'a str]) -> HashMap<&'a str, Vec<Utf8PathBuf>> {
+    self
+      .find_up_multi(names)
+      .into_iter()
+      .map(|(name, paths)| (name, into_utf8(paths)))
+      .collect()
+  }
+}
+
+/// Matches are the UTF-8 `cwd` ancestors joined with UTF-8 names, so the
+/// conversion never drops anything.
+fn into_utf8(paths: Vec<std::path::PathBuf>) -> Vec<Utf8PathBuf> {
+  paths
+    .into_iter()
+    .filter_map(|path| Utf8PathBuf::from_path_buf(path).ok())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_find_utf8_paths() {
+    let up_finder = Utf8UpFinder::builder()
+      .cwd(Utf8PathBuf::from("fixtures/a/b/c/d"))
+      .build();
+
+    let paths = up_finder.find_up_multi_utf8(&[".node-version"]);
+
+    assert_eq!(
+      paths[".node-version"],
+      vec![Utf8PathBuf::from("fixtures/a/b/c/.node-version")]
+    );
+  }
+}
@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+
+/// Convert `path` to its extended-length `\\?\` form on Windows, which is not
+/// subject to `MAX_PATH` and is passed to the filesystem unparsed.
+///
+/// Relative paths are made absolute first, `.` and `..` are resolved lexically
+/// since verbatim paths cannot contain them. Paths that already are verbatim,
+/// device paths and every path on other platforms are returned unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::Path;
+///
+/// use up_finder::to_verbatim;
+///
+/// let path = to_verbatim(Path::new("package.json"));
+///
+/// println!("{}", path.display());
+/// ```
+pub fn to_verbatim(path: &Path) -> PathBuf {
+  sys::to_verbatim(path)
+}
+
+#[cfg(windows)]
+mod sys {
+  use std::{
+    ffi::OsString,
+    path::{self, Component, Path, PathBuf, Prefix},
+  };
+
+  pub(super) fn to_verbatim(path: &Path) -> PathBuf {
+    // `absolute` normalizes through `GetFullPathNameW` on Windows.
+    let Ok(absolute) = path::absolute(path) else {
+      return path.to_path_buf();
+    };
+
+    let mut components = absolute.components();
+
+    let Some(Component::Prefix(prefix)) = components.next() else {
+      return absolute;
+    };
+
+    let mut verbatim = match prefix.kind() {
+      Prefix::Disk(_) => OsString::from(r"\\?\"),
+      Prefix::UNC(server, share) => {
+        let mut verbatim = OsString::from(r"\\?\UNC\");
+        verbatim.push(server);
+        verbatim.push(r"\");
+        verbatim.push(share);
+        verbatim
+      }
+      _ => return absolute,
+    };
+
+    if let Prefix::Disk(_) = prefix.kind() {
+      verbatim.push(prefix.as_os_str());
+    }
+
+    for component in components {
+      match component {
+        Component::RootDir => {}
+        Component::Normal(name) => {
+          verbatim.push(r"\");
+          verbatim.push(name);
+        }
+        Component::Prefix(_) | Component::CurDir | Component::ParentDir => {}
+      }
+    }
+
+    // The root itself, `\\?\C:` alone would be a drive-relative path.
+    if absolute.parent().is_none() {
+      verbatim.push(r"\");
+    }
+
+    PathBuf::from(verbatim)
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_disk_and_unc_paths() {
+      assert_eq!(
+        to_verbatim(Path::new(r"C:\a\.\b\..\c")),
+        PathBuf::from(r"\\?\C:\a\c")
+      );
+      assert_eq!(to_verbatim(Path::new(r"C:\")), PathBuf::from(r"\\?\C:\"));
+      assert_eq!(
+        to_verbatim(Path::new(r"\\server\share\a")),
+        PathBuf::from(r"\\?\UNC\server\share\a")
+      );
+      assert_eq!(
+        to_verbatim(Path::new(r"\\?\C:\a")),
+        PathBuf::from(r"\\?\C:\a")
+      );
+    }
+  }
+}
+
+#[cfg(not(windows))]
+mod sys {
+  use std::path::{Path, PathBuf};
+
+  pub(super) fn to_verbatim(path: &Path) -> PathBuf {
+    path.to_path_buf()
+  }
+}
@@ -0,0 +1,388 @@
+use std::{
+  collections::BTreeMap,
+  path::{Path, PathBuf},
+};
+
+use crate::{FindUpResult, UpFinder};
+
+/// The file names of [`UpFinder::find_tool_versions`], in order of precedence
+/// on the same level.
+#[cfg(feature = "toml")]
+const TOOL_VERSIONS_FILES: &[&str] = &["mise.toml", ".mise.toml", ".tool-versions"];
+#[cfg(not(feature = "toml"))]
+const TOOL_VERSIONS_FILES: &[&str] = &[".tool-versions"];
+
+/// A version pinned by the nearest version file, see
+/// [`UpFinder::find_node_version`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionFile {
+  path: PathBuf,
+  version: String,
+}
+
+impl VersionFile {
+  /// The file the version was read from.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// The version as written, e.g. `20.11.1`, `lts/iron` or `stable`.
+  pub fn version(&self) -> &str {
+    &self.version
+  }
+}
+
+/// The version of a tool, see [`UpFinder::find_tool_versions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolVersion {
+  versions: Vec<String>,
+  path: PathBuf,
+}
+
+impl ToolVersion {
+  /// The preferred version.
+  pub fn version(&self) -> &str {
+    &self.versions[0]
+  }
+
+  /// All versions listed for the tool, in order of preference. Besides the
+  /// preferred one these are the fallbacks asdf and mise try when it isn't
+  /// installed.
+  pub fn versions(&self) -> &[String] {
+    &self.versions
+  }
+
+  /// The file the tool was pinned in.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the Node.js version pinned by the nearest `.node-version` or
+  /// `.nvmrc`, preferring `.node-version` on the same level.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if let Some(pinned) = find_up.find_node_version() {
+  ///   println!("node {} from {}", pinned.version(), pinned.path().display());
+  /// }
+  /// ```
+  pub fn find_node_version(&self) -> Option<VersionFile> {
+    self.find_version(&[".node-version", ".nvmrc"], |_, contents| {
+      first_line(contents)
+    })
+  }
+
+  /// Find the Python version pinned by the nearest `.python-version`. Of
+  /// files listing several versions, the first is returned.
+  pub fn find_python_version(&self) -> Option<VersionFile> {
+    self.find_version(&[".python-version"], |_, contents| first_line(contents))
+  }
+
+  /// Find the Rust toolchain pinned by the nearest `rust-toolchain` or
+  /// `rust-toolchain.toml`, preferring the legacy `rust-toolchain` on the
+  /// same level as rustup does. The version is the `toolchain.channel`, or the whole of a
+  /// legacy `rust-toolchain` file that isn't TOML.
+  #[cfg(feature = "toml")]
+  pub fn find_rust_toolchain(&self) -> Option<VersionFile> {
+    self.find_version(
+      &["rust-toolchain", "rust-toolchain.toml"],
+      |path, contents| {
+        let channel = || {
+          let table: ::toml::Table = contents.parse().ok()?;
+          Some(table.get("toolchain")?.get("channel")?.as_str()?.to_owned())
+        };
+        if path.ends_with("rust-toolchain.toml") || contents.contains('[') {
+          channel()
+        } else {
+          first_line(contents)
+        }
+      },
+    )
+  }
+
+  /// Find the tool versions pinned by every asdf `.tool-versions` and, with
+  /// the `toml` feature, every `[tools]` table of a `mise.toml` or
+  /// `.mise.toml` at or above the current working directory, keyed by tool.
+  ///
+  /// Nearer files win, and on the same level `mise.toml` wins over
+  /// `.mise.toml`, which wins over `.tool-versions`, like mise merges them.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// for (tool, pinned) in find_up.find_tool_versions() {
+  ///   println!("{tool} {} from {}", pinned.version(), pinned.path().display());
+  /// }
+  /// ```
+  pub fn find_tool_versions(&self) -> BTreeMap<String, ToolVersion> {
+    let matches = self.find_up_multi_matches(TOOL_VERSIONS_FILES);
+
+    let mut files: Vec<_> = TOOL_VERSIONS_FILES
+      .iter()
+      .enumerate()
+      .flat_map(|(rank, name)| {
+        matches[name]
+          .iter()
+          .map(move |found| ((found.depth(), rank), found.path()))
+      })
+      .collect();
+    files.sort_by_key(|(order, _)| *order);
+
+    let mut tools = BTreeMap::new();
+
+    for (_, path) in files {
+      let Some(contents) = self
+        .fs
+        .read(path)
+        .ok()
+        .and_then(|contents| String::from_utf8(contents).ok())
+      else {
+        continue;
+      };
+
+      for (tool, versions) in parse_tool_versions(path, &contents) {
+        tools.entry(tool).or_insert_with(|| ToolVersion {
+          versions,
+          path: path.to_path_buf(),
+        });
+      }
+    }
+
+    tools
+  }
+
+  /// The version read from the nearest of `names` that has one, preferring
+  /// earlier names on the same level.
+  fn find_version(
+    &self,
+    names: &[&str],
+    parse: impl Fn(&Path, &str) -> Option<String>,
+  ) -> Option<VersionFile> {
+    let mut versions = vec![];
+
+    self
+      .find_up_with_impl(
+        self.cwd.as_ref().to_path_buf(),
+        names,
+        |path| {
+          let Some(version) = self
+            .fs
+            .read(&path)
+            .ok()
+            .and_then(|contents| String::from_utf8(contents).ok())
+            .and_then(|contents| parse(&path, &contents))
+          else {
+            return FindUpResult::Continue;
+          };
+          versions.push(VersionFile {
+            path: path.clone(),
+            version,
+          });
+          FindUpResult::Last(path)
+        },
+        false,
+      )
+      .ok()?;
+
+    // Only the nearest level has versions, in the order of `names`.
+    versions.into_iter().next()
+  }
+}
+
+/// The tools of a `.tool-versions` or mise file, with at least one version.
+fn parse_tool_versions(path: &Path, contents: &str) -> Vec<(String, Vec<String>)> {
+  if !path.ends_with(".tool-versions") {
+    return parse_mise_tools(contents);
+  }
+
+  contents
+    .lines()
+    .filter_map(|line| {
+      let line = line.split_once('#').map_or(line, |(line, _)| line);
+      let mut words = line.split_whitespace();
+      let tool = words.next()?.to_owned();
+      let versions: Vec<String> = words.map(str::to_owned).collect();
+      (!versions.is_empty()).then_some((tool, versions))
+    })
+    .collect()
+}
+
+/// The `[tools]` of a mise file, whose values are a version, a list of
+/// versions or tables with a `version`.
+#[cfg(feature = "toml")]
+fn parse_mise_tools(contents: &str) -> Vec<(String, Vec<String>)> {
+  use ::toml::{Table, Value};
+
+  fn version(value: &Value) -> Option<String> {
+    match value {
+      Value::String(version) => Some(version.clone()),
+      Value::Table(table) => table.get("version")?.as_str().map(str::to_owned),
+      _ => None,
+    }
+  }
+
+  let Some(Value::Table(tools)) = contents
+    .parse::<Table>()
+    .ok()
+    .and_then(|mut table| table.remove("tools"))
+  else {
+    return vec![];
+  };
+
+  tools
+    .into_iter()
+    .filter_map(|(tool, value)| {
+      let versions: Vec<String> = match &value {
+        Value::Array(values) => values.iter().filter_map(version).collect(),
+        value => version(value).into_iter().collect(),
+      };
+      (!versions.is_empty()).then_some((tool, versions))
+    })
+    .collect()
+}
+
+#[cfg(not(feature = "toml"))]
+fn parse_mise_tools(_contents: &str) -> Vec<(String, Vec<String>)> {
+  vec![]
+}
+
+/// The first line that isn't empty or a `#` comment, trimmed.
+fn first_line(contents: &str) -> Option<String> {
+  contents
+    .lines()
+    .map(str::trim)
+    .find(|line| !line.is_empty() && !line.starts_with('#'))
+    .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  fn finder(fs: MemoryFs) -> UpFinder<&'static str> {
+    UpFinder::builder()
+      .cwd("/repo/app/src")
+      .fs(Arc::new(fs.with_dir("/repo/app/src")))
+      .build()
+  }
+
+  #[test]
+  fn should_read_the_nearest_version() {
+    let find_up = finder(
+      MemoryFs::new()
+        .with_file("/repo/.node-version", "18.19.0\n")
+        .with_file("/repo/app/.nvmrc", "# team default\nlts/iron\n")
+        .with_file("/repo/app/.python-version", "\n")
+        .with_file("/repo/.python-version", "3.12.1\n3.11.7\n"),
+    );
+
+    let node = find_up.find_node_version().unwrap();
+    let python = find_up.find_python_version().unwrap();
+
+    assert_eq!(node.version(), "lts/iron");
+    assert_eq!(node.path(), Path::new("/repo/app/.nvmrc"));
+    assert_eq!(python.version(), "3.12.1");
+    assert_eq!(python.path(), Path::new("/repo/.python-version"));
+  }
+
+  #[test]
+  fn should_prefer_earlier_names_on_the_same_level() {
+    let find_up = finder(
+      MemoryFs::new()
+        .with_file("/repo/app/.nvmrc", "20")
+        .with_file("/repo/app/.node-version", "18"),
+    );
+
+    assert_eq!(find_up.find_node_version().unwrap().version(), "18");
+    assert_eq!(find_up.find_python_version(), None);
+  }
+
+  #[cfg(feature = "toml")]
+  #[test]
+  fn should_read_rust_toolchains() {
+    let toml = finder(
+      MemoryFs::new()
+        .with_file("/repo/rust-toolchain", "nightly-2024-01-01")
+        .with_file(
+          "/repo/app/rust-toolchain.toml",
+          "[toolchain]\nchannel = \"1.85.0\"\ncomponents = [\"clippy\"]",
+        ),
+    );
+    let legacy = finder(MemoryFs::new().with_file("/repo/rust-toolchain", "nightly-2024-01-01\n"));
+    let both = finder(
+      MemoryFs::new()
+        .with_file("/repo/rust-toolchain", "nightly-2024-01-01")
+        .with_file(
+          "/repo/rust-toolchain.toml",
+          "[toolchain]\nchannel = \"1.85.0\"",
+        ),
+    );
+
+    assert_eq!(toml.find_rust_toolchain().unwrap().version(), "1.85.0");
+    assert_eq!(
+      legacy.find_rust_toolchain().unwrap().version(),
+      "nightly-2024-01-01"
+    );
+    let pinned = both.find_rust_toolchain().unwrap();
+    assert_eq!(pinned.version(), "nightly-2024-01-01");
+    assert_eq!(pinned.path(), Path::new("/repo/rust-toolchain"));
+  }
+
+  #[test]
+  fn should_merge_tool_versions_nearest_first() {
+    let find_up = finder(
+      MemoryFs::new()
+        .with_file(
+          "/repo/.tool-versions",
+          "nodejs 18.19.0\npython 3.12.1 3.11.7 # fallback\n",
+        )
+        .with_file("/repo/app/.tool-versions", "# pinned\nnodejs 20.11.1\n"),
+    );
+
+    let tools = find_up.find_tool_versions();
+
+    assert_eq!(tools.len(), 2);
+    assert_eq!(tools["nodejs"].version(), "20.11.1");
+    assert_eq!(
+      tools["nodejs"].path(),
+      Path::new("/repo/app/.tool-versions")
+    );
+    assert_eq!(tools["python"].versions(), ["3.12.1", "3.11.7"]);
+    assert_eq!(tools["python"].path(), Path::new("/repo/.tool-versions"));
+  }
+
+  #[cfg(feature = "toml")]
+  #[test]
+  fn should_prefer_mise_files_on_the_same_level() {
+    let find_up = finder(
+      MemoryFs::new()
+        .with_file("/repo/.tool-versions", "nodejs 18\ngo 1.22\n")
+        .with_file(
+          "/repo/mise.toml",
+          "[tools]\nnodejs = \"20\"\npython = [\"3.12\", \"3.11\"]\nterraform = { version = \"1.7\" }",
+        ),
+    );
+
+    let tools = find_up.find_tool_versions();
+
+    assert_eq!(tools["nodejs"].version(), "20");
+    assert_eq!(tools["nodejs"].path(), Path::new("/repo/mise.toml"));
+    assert_eq!(tools["python"].versions(), ["3.12", "3.11"]);
+    assert_eq!(tools["terraform"].version(), "1.7");
+    assert_eq!(tools["go"].path(), Path::new("/repo/.tool-versions"));
+  }
+}
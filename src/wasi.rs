@@ -0,0 +1,82 @@
+//! Preopened directories, the only part of the filesystem a WASI program can
+//! reach. Walking up past one of them only yields capability errors, so they
+//! are treated as traversal boundaries.
+
+use std::{path::PathBuf, sync::OnceLock};
+
+pub(crate) fn preopened_dirs() -> &'static [PathBuf] {
+  static PREOPENS: OnceLock<Vec<PathBuf>> = OnceLock::new();
+
+  PREOPENS.get_or_init(sys::preopened_dirs)
+}
+
+#[cfg(target_env = "p1")]
+mod sys {
+  use std::path::PathBuf;
+
+  const PREOPENTYPE_DIR: u8 = 0;
+
+  #[repr(C)]
+  struct Prestat {
+    tag: u8,
+    name_len: usize,
+  }
+
+  #[link(wasm_import_module = "wasi_snapshot_preview1")]
+  unsafe extern "C" {
+    fn fd_prestat_get(fd: u32, prestat: *mut Prestat) -> i32;
+    fn fd_prestat_dir_name(fd: u32, path: *mut u8, len: usize) -> i32;
+  }
+
+  pub(super) fn preopened_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+
+    // Preopens are numbered consecutively after stdio, the first descriptor
+    // that is not one ends the list.
+    for fd in 3.. {
+      let mut prestat = Prestat {
+        tag: u8::MAX,
+        name_len: 0,
+      };
+
+      // SAFETY: `prestat` is valid for writes.
+      if unsafe { fd_prestat_get(fd, &mut prestat) } != 0 {
+        break;
+      }
+
+      if prestat.tag != PREOPENTYPE_DIR {
+        continue;
+      }
+
+      let mut name = vec![0; prestat.name_len];
+
+      // SAFETY: `name` is valid for writes of `name_len` bytes.
+      if unsafe { fd_prestat_dir_name(fd, name.as_mut_ptr(), name.len()) } != 0 {
+        continue;
+      }
+
+      // Some runtimes include the NUL terminator in the length.
+      if name.last() == Some(&0) {
+        name.pop();
+      }
+
+      if let Ok(name) = String::from_utf8(name) {
+        dirs.push(PathBuf::from(name));
+      }
+    }
+
+    dirs
+  }
+}
+
+#[cfg(not(target_env = "p1"))]
+mod sys {
+  use std::path::PathBuf;
+
+  pub(super) fn preopened_dirs() -> Vec<PathBuf> {
+    wasip2::filesystem::preopens::get_directories()
+      .into_iter()
+      .map(|(_, path)| PathBuf::from(path))
+      .collect()
+  }
+}
@@ -0,0 +1,407 @@
+use std::{
+  collections::VecDeque,
+  fmt, io,
+  path::{Path, PathBuf},
+  sync::mpsc,
+};
+
+use crate::{UpFinder, hash::HashSet};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher, event::ModifyKind};
+
+/// What changed on a level of the walk, see [`UpFinder::invalidations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invalidation {
+  dir: PathBuf,
+  path: PathBuf,
+  cause: InvalidationCause,
+}
+
+impl Invalidation {
+  /// The level, a directory the search looks in.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// The entry that changed, the level itself for
+  /// [`InvalidationCause::Rescan`] or when the level was removed.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// How the entry changed.
+  pub fn cause(&self) -> InvalidationCause {
+    self.cause
+  }
+}
+
+/// How an entry changed, see [`Invalidation::cause`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InvalidationCause {
+  /// The entry was created.
+  Created,
+  /// The entry was removed.
+  Removed,
+  /// The entry was renamed, to or from its path.
+  Renamed,
+  /// The contents or metadata of the entry changed.
+  Modified,
+  /// Changes were lost, e.g. because the watcher's queue overflowed, and
+  /// anything on the level may have changed.
+  Rescan,
+}
+
+/// The changes on the levels of a search, see [`UpFinder::invalidations`].
+pub struct Invalidations<'a, P: AsRef<Path>> {
+  up_finder: &'a UpFinder<P>,
+  names: Vec<String>,
+  watcher: RecommendedWatcher,
+  events: mpsc::Receiver<notify::Result<notify::Event>>,
+  watched: HashSet<PathBuf>,
+  pending: VecDeque<Invalidation>,
+}
+
+/// The nearest match of a search, again whenever it changes, see
+/// [`UpFinder::watch`].
+///
+/// Every item blocks until the filesystem changes in a way that gives the
+/// search another nearest match, or none. The first item is the nearest match
+/// when watching started.
+#[derive(Debug)]
+pub struct NearestWatch<'a, P: AsRef<Path>> {
+  invalidations: Invalidations<'a, P>,
+  last: Option<Option<PathBuf>>,
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Watch every directory the search for `name` looks in, from the start
+  /// directory up to the end of the walk and the
+  /// [`ConfigFallback`](crate::ConfigFallback) directories, and report the
+  /// nearest match whenever another one takes its place, e.g. because a
+  /// configuration file was added above the workspace.
+  ///
+  /// Directories that appear later, such as a `.config` directory, are
+  /// watched as soon as they exist.
+  ///
+  /// # Example
+  ///
+  /// ```rust,no_run
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// for nearest in find_up.watch("tsconfig.json")? {
+  ///   match nearest? {
+  ///     Some(path) => println!("using {}", path.display()),
+  ///     None => println!("no tsconfig.json"),
+  ///   }
+  /// }
+  /// # Ok::<(), std::io::Error>(())
+  /// ```
+  pub fn watch(&self, name: &str) -> io::Result<NearestWatch<'_, P>> {
+    self.watch_multi(&[name])
+  }
+
+  /// Like [`UpFinder::watch`], for the nearest match of any of `names`.
+  /// Matches on the same level are preferred in the order of `names`.
+  pub fn watch_multi(&self, names: &[&str]) -> io::Result<NearestWatch<'_, P>> {
+    Ok(NearestWatch {
+      invalidations: self.invalidations(names)?,
+      last: None,
+    })
+  }
+
+  /// Watch the same directories as [`UpFinder::watch_multi`], and report
+  /// every entry created, removed, renamed or modified in them instead of the
+  /// nearest match, for applications that derive their own caches from the
+  /// levels of a search.
+  ///
+  /// # Example
+  ///
+  /// ```rust,no_run
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").config_dir(true).build();
+  ///
+  /// for invalidation in find_up.invalidations(&["package.json", ".npmrc"])? {
+  ///   let invalidation = invalidation?;
+  ///   println!(
+  ///     "{} in {}: {:?}",
+  ///     invalidation.path().display(),
+  ///     invalidation.dir().display(),
+  ///     invalidation.cause()
+  ///   );
+  /// }
+  /// # Ok::<(), std::io::Error>(())
+  /// ```
+  pub fn invalidations(&self, names: &[&str]) -> io::Result<Invalidations<'_, P>> {
+    let (sender, events) = mpsc::channel();
+    let watcher = notify::recommended_watcher(sender).map_err(to_io)?;
+
+    let mut invalidations = Invalidations {
+      up_finder: self,
+      names: names.iter().map(|&name| name.to_owned()).collect(),
+      watcher,
+      events,
+      watched: HashSet::default(),
+      pending: VecDeque::new(),
+    };
+    invalidations.sync()?;
+
+    Ok(invalidations)
+  }
+}
+
+impl<P: AsRef<Path>> Invalidations<'_, P> {
+  /// The directories currently watched.
+  pub fn dirs(&self) -> impl Iterator<Item = &Path> {
+    self.watched.iter().map(PathBuf::as_path)
+  }
+
+  /// Watch the directories of the plan that exist now, and stop watching
+  /// those that went away.
+  fn sync(&mut self) -> io::Result<()> {
+    let names: Vec<&str> = self.names.iter().map(String::as_str).collect();
+    let dirs: HashSet<PathBuf> = self
+      .up_finder
+      .plan_multi(&names)
+      .iter()
+      .filter_map(|path| path.parent())
+      .filter(|dir| !dir.as_os_str().is_empty() && dir.is_dir())
+      .map(Path::to_path_buf)
+      .collect();
+
+    for dir in self.watched.difference(&dirs) {
+      // The directory may be gone already, which ends its watch as well.
+      let _ = self.watcher.unwatch(dir);
+    }
+    for dir in dirs.difference(&self.watched) {
+      self
+        .watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .map_err(to_io)?;
+    }
+    self.watched = dirs;
+
+    Ok(())
+  }
+
+  /// The next invalidation, waiting for one only if `block` is set.
+  fn recv(&mut self, block: bool) -> Option<io::Result<Invalidation>> {
+    loop {
+      if let Some(invalidation) = self.pending.pop_front() {
+        return Some(Ok(invalidation));
+      }
+
+      let event = if block {
+        self.events.recv().ok()?
+      } else {
+        self.events.try_recv().ok()?
+      };
+      let event = match event {
+        Ok(event) => event,
+        Err(error) => return Some(Err(to_io(error))),
+      };
+
+      let cause = if event.need_rescan() {
+        InvalidationCause::Rescan
+      } else {
+        match event.kind {
+          EventKind::Create(_) => InvalidationCause::Created,
+          EventKind::Remove(_) => InvalidationCause::Removed,
+          EventKind::Modify(ModifyKind::Name(_)) => InvalidationCause::Renamed,
+          EventKind::Modify(_) | EventKind::Any => InvalidationCause::Modified,
+          EventKind::Access(_) | EventKind::Other => continue,
+        }
+      };
+
+      let before = self.pending.len();
+      if cause == InvalidationCause::Rescan && event.paths.is_empty() {
+        self
+          .pending
+          .extend(self.watched.iter().map(|dir| Invalidation {
+            dir: dir.clone(),
+            path: dir.clone(),
+            cause,
+          }));
+      }
+      for path in event.paths {
+        let dir = if self.watched.contains(&path) {
+          path.clone()
+        } else {
+          match path.parent() {
+            Some(dir) if self.watched.contains(dir) => dir.to_path_buf(),
+            _ => continue,
+          }
+        };
+        self.pending.push_back(Invalidation { dir, path, cause });
+      }
+
+      // Entries that come and go may be levels, or their `.config`
+      // directories.
+      if self.pending.len() > before && cause != InvalidationCause::Modified {
+        if let Err(error) = self.sync() {
+          return Some(Err(error));
+        }
+      }
+    }
+  }
+}
+
+impl<P: AsRef<Path>> Iterator for Invalidations<'_, P> {
+  type Item = io::Result<Invalidation>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.recv(true)
+  }
+}
+
+impl<P: AsRef<Path>> fmt::Debug for Invalidations<'_, P> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Invalidations")
+      .field("names", &self.names)
+      .field("watched", &self.watched)
+      .field("pending", &self.pending)
+      .finish_non_exhaustive()
+  }
+}
+
+impl<P: AsRef<Path>> NearestWatch<'_, P> {
+  /// The nearest match of any of the names.
+  fn nearest(&self) -> Option<PathBuf> {
+    let names: Vec<&str> = self
+      .invalidations
+      .names
+      .iter()
+      .map(String::as_str)
+      .collect();
+    let mut matches = self.invalidations.up_finder.find_up_multi_matches(&names);
+
+    names
+      .iter()
+      .filter_map(|name| matches.remove(name)?.into_iter().next())
+      .min_by_key(|found| found.depth())
+      .map(|found| found.into_path())
+  }
+}
+
+impl<P: AsRef<Path>> Iterator for NearestWatch<'_, P> {
+  type Item = io::Result<Option<PathBuf>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if self.last.is_some() {
+        if let Err(error) = self.invalidations.recv(true)? {
+          return Some(Err(error));
+        }
+        // One change often comes as several events.
+        while let Some(invalidation) = self.invalidations.recv(false) {
+          if let Err(error) = invalidation {
+            return Some(Err(error));
+          }
+        }
+      }
+
+      let nearest = self.nearest();
+      if self.last.as_ref() != Some(&nearest) {
+        self.last = Some(nearest.clone());
+        return Some(Ok(nearest));
+      }
+    }
+  }
+}
+
+fn to_io(error: notify::Error) -> io::Error {
+  match error.kind {
+    notify::ErrorKind::Io(error) => error,
+    _ => io::Error::other(error),
+  }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+  use std::fs;
+
+  use super::*;
+  use crate::testing::FixtureTree;
+
+  #[test]
+  fn should_report_the_nearest_match_whenever_it_changes() {
+    let fixture = FixtureTree::new()
+      .file("tsconfig.json", "{}")
+      .dir("app/src")
+      .build()
+      .unwrap();
+    let up_finder = fixture.up_finder("app/src").unwrap();
+
+    let mut watch = up_finder.watch("tsconfig.json").unwrap();
+    assert_eq!(
+      watch.next().unwrap().unwrap(),
+      Some(fixture.path("tsconfig.json").unwrap())
+    );
+
+    fs::write(fixture.path("app/tsconfig.json").unwrap(), "{}").unwrap();
+    assert_eq!(
+      watch.next().unwrap().unwrap(),
+      Some(fixture.path("app/tsconfig.json").unwrap())
+    );
+
+    fs::remove_file(fixture.path("app/tsconfig.json").unwrap()).unwrap();
+    fs::remove_file(fixture.path("tsconfig.json").unwrap()).unwrap();
+    let mut nearest = watch.next().unwrap().unwrap();
+    if nearest.is_some() {
+      // The removals were reported one at a time.
+      nearest = watch.next().unwrap().unwrap();
+    }
+    assert_eq!(nearest, None);
+  }
+
+  #[test]
+  fn should_report_the_changed_entries_of_every_level() {
+    let fixture = FixtureTree::new().dir("app").build().unwrap();
+    let up_finder = UpFinder::builder()
+      .cwd(fixture.path("app").unwrap())
+      .stop_at(fixture.root().to_path_buf())
+      .config_dir(true)
+      .build();
+
+    let mut invalidations = up_finder.invalidations(&["tsconfig.json"]).unwrap();
+    let mut next = |cause: InvalidationCause| loop {
+      let invalidation = invalidations.next().unwrap().unwrap();
+      if invalidation.cause() == cause {
+        break (
+          invalidation.dir().to_path_buf(),
+          invalidation.path().to_path_buf(),
+        );
+      }
+    };
+
+    fs::create_dir(fixture.path(".config").unwrap()).unwrap();
+    assert_eq!(
+      next(InvalidationCause::Created),
+      (
+        fixture.root().to_path_buf(),
+        fixture.path(".config").unwrap()
+      )
+    );
+
+    // The new `.config` directory is a level of the search as well.
+    fs::write(fixture.path(".config/tsconfig.json").unwrap(), "{}").unwrap();
+    assert_eq!(
+      next(InvalidationCause::Created),
+      (
+        fixture.path(".config").unwrap(),
+        fixture.path(".config/tsconfig.json").unwrap()
+      )
+    );
+
+    fs::remove_file(fixture.path(".config/tsconfig.json").unwrap()).unwrap();
+    assert_eq!(
+      next(InvalidationCause::Removed),
+      (
+        fixture.path(".config").unwrap(),
+        fixture.path(".config/tsconfig.json").unwrap()
+      )
+    );
+  }
+}
@@ -0,0 +1,40 @@
+//! Finding the roots of workspaces.
+mod build;
+#[cfg(feature = "toml")]
+mod cargo;
+mod go;
+mod monorepo;
+#[cfg(feature = "json")]
+mod npm;
+mod project;
+mod python;
+
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub use build::find_build_workspace_root;
+pub use build::{BuildSystem, BuildWorkspace};
+#[cfg(feature = "toml")]
+pub use cargo::CargoWorkspace;
+#[cfg(all(
+  feature = "toml",
+  not(all(target_family = "wasm", target_os = "unknown"))
+))]
+pub use cargo::find_cargo_workspace_root;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub use go::find_go_module_root;
+pub use go::{GoModule, GoWorkspace};
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub use monorepo::find_monorepo_root;
+pub use monorepo::{Monorepo, MonorepoTool};
+#[cfg(all(
+  feature = "json",
+  not(all(target_family = "wasm", target_os = "unknown"))
+))]
+pub use npm::find_npm_workspace_root;
+#[cfg(feature = "json")]
+pub use npm::{NpmWorkspace, PackageManager};
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub use project::detect_project_type;
+pub use project::{Project, ProjectType};
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub use python::find_python_project_root;
+pub use python::{PythonMarker, PythonProject};
@@ -0,0 +1,131 @@
+use std::path::{Path, PathBuf};
+
+use crate::UpFinder;
+
+/// A build system whose workspace spans a whole repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BuildSystem {
+  /// `MODULE.bazel`, `WORKSPACE.bazel` or `WORKSPACE`.
+  Bazel,
+  /// `.buckconfig`
+  Buck,
+}
+
+impl BuildSystem {
+  const MARKERS: [(&str, BuildSystem); 4] = [
+    ("MODULE.bazel", Self::Bazel),
+    ("WORKSPACE.bazel", Self::Bazel),
+    ("WORKSPACE", Self::Bazel),
+    (".buckconfig", Self::Buck),
+  ];
+}
+
+/// The root of a Bazel or Buck workspace, see
+/// [`UpFinder::find_build_workspace_root`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildWorkspace {
+  root: PathBuf,
+  build_system: BuildSystem,
+}
+
+impl BuildWorkspace {
+  /// The directory containing the marker.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  pub fn build_system(&self) -> BuildSystem {
+    self.build_system
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the outermost directory containing a Bazel or Buck workspace marker.
+  ///
+  /// Unlike other markers these are farthest first: nested `WORKSPACE` files
+  /// and `.buckconfig`s (of vendored or test repositories, for instance) are
+  /// part of the outer workspace. If both build systems mark the same
+  /// directory, Bazel wins.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if let Some(workspace) = find_up.find_build_workspace_root() {
+  ///   println!("{:?} at {}", workspace.build_system(), workspace.root().display());
+  /// }
+  /// ```
+  pub fn find_build_workspace_root(&self) -> Option<BuildWorkspace> {
+    let matches = self.find_up_multi_matches(&BuildSystem::MARKERS.map(|(marker, _)| marker));
+
+    let (_, build_system, found) = BuildSystem::MARKERS
+      .into_iter()
+      .filter_map(|(marker, build_system)| {
+        let found = matches.get(marker)?.last()?;
+        Some((found.depth(), build_system, found))
+      })
+      .max_by_key(|(depth, build_system, _)| (*depth, std::cmp::Reverse(*build_system)))?;
+
+    Some(BuildWorkspace {
+      root: found.dir().to_path_buf(),
+      build_system,
+    })
+  }
+}
+
+/// Find the outermost Bazel or Buck workspace root at or above `start` on the
+/// real filesystem, see [`UpFinder::find_build_workspace_root`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn find_build_workspace_root(start: impl AsRef<Path>) -> Option<BuildWorkspace> {
+  UpFinder::builder()
+    .cwd(start.as_ref())
+    .build()
+    .find_build_workspace_root()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  fn find(fs: MemoryFs) -> Option<BuildWorkspace> {
+    UpFinder::builder()
+      .cwd("/repo/third_party/lib/src")
+      .fs(Arc::new(fs.with_dir("/repo/third_party/lib/src")))
+      .build()
+      .find_build_workspace_root()
+  }
+
+  #[test]
+  fn should_prefer_the_outermost_marker() {
+    let workspace = find(
+      MemoryFs::new()
+        .with_file("/repo/MODULE.bazel", "")
+        .with_file("/repo/.buckconfig", "")
+        .with_file("/repo/third_party/lib/WORKSPACE", ""),
+    )
+    .unwrap();
+
+    assert_eq!(workspace.root(), Path::new("/repo"));
+    assert_eq!(workspace.build_system(), BuildSystem::Bazel);
+    assert_eq!(find(MemoryFs::new()), None);
+  }
+
+  #[test]
+  fn should_find_buck_workspaces() {
+    let workspace = find(
+      MemoryFs::new()
+        .with_file("/repo/.buckconfig", "")
+        .with_file("/repo/third_party/.buckconfig", ""),
+    )
+    .unwrap();
+
+    assert_eq!(workspace.root(), Path::new("/repo"));
+    assert_eq!(workspace.build_system(), BuildSystem::Buck);
+  }
+}
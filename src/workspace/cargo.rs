@@ -0,0 +1,199 @@
+use std::path::{Path, PathBuf};
+
+use ::toml::{Table, Value};
+
+use crate::{UpFinder, restrict::clean};
+
+/// The root of a Cargo workspace, see [`UpFinder::find_cargo_workspace_root`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CargoWorkspace {
+  root: PathBuf,
+  standalone: bool,
+}
+
+impl CargoWorkspace {
+  /// The directory containing the root manifest.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  /// Whether the root is a package that is not part of any workspace.
+  pub fn is_standalone(&self) -> bool {
+    self.standalone
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the root of the Cargo workspace the package at or above the current
+  /// working directory belongs to, the way Cargo does.
+  ///
+  /// The nearest `Cargo.toml` is the package. It is its own root if it has a
+  /// `[workspace]` table, or its `package.workspace` key points at the root.
+  /// Otherwise the root is the next `Cargo.toml` above with a `[workspace]`
+  /// table, unless that lists the package in `workspace.exclude`, which makes
+  /// the package standalone, as does not finding any workspace at all.
+  /// `workspace.members` globs are not evaluated.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd("src").build();
+  ///
+  /// let workspace = find_up.find_cargo_workspace_root().unwrap();
+  ///
+  /// assert!(workspace.root().join("Cargo.toml").exists());
+  /// ```
+  pub fn find_cargo_workspace_root(&self) -> Option<CargoWorkspace> {
+    let mut manifests = self.find_up_matches("Cargo.toml").into_iter();
+
+    let package_manifest = manifests.next()?;
+    let package_root = package_manifest.dir().to_path_buf();
+    let package = self.read_manifest(package_manifest.path())?;
+
+    if package.get("workspace").is_some_and(Value::is_table) {
+      return Some(CargoWorkspace {
+        root: package_root,
+        standalone: false,
+      });
+    }
+
+    if let Some(workspace) = package
+      .get("package")
+      .and_then(|package| package.get("workspace"))
+      .and_then(Value::as_str)
+    {
+      return Some(CargoWorkspace {
+        root: clean(&package_root.join(workspace)),
+        standalone: false,
+      });
+    }
+
+    for manifest in manifests {
+      let Some(parsed) = self.read_manifest(manifest.path()) else {
+        continue;
+      };
+      let Some(workspace) = parsed.get("workspace").and_then(Value::as_table) else {
+        continue;
+      };
+      let root = manifest.dir().to_path_buf();
+
+      if is_excluded(workspace, &root, &package_root) {
+        break;
+      }
+
+      return Some(CargoWorkspace {
+        root,
+        standalone: false,
+      });
+    }
+
+    Some(CargoWorkspace {
+      root: package_root,
+      standalone: true,
+    })
+  }
+
+  fn read_manifest(&self, path: &Path) -> Option<Table> {
+    let contents = self.fs.read(path).ok()?;
+
+    std::str::from_utf8(&contents).ok()?.parse().ok()
+  }
+}
+
+/// Whether `workspace.exclude` of the workspace at `root` covers `package`.
+fn is_excluded(workspace: &Table, root: &Path, package: &Path) -> bool {
+  let Ok(relative) = package.strip_prefix(root) else {
+    return false;
+  };
+
+  workspace
+    .get("exclude")
+    .and_then(Value::as_array)
+    .is_some_and(|exclude| {
+      exclude
+        .iter()
+        .filter_map(Value::as_str)
+        .any(|excluded| relative.starts_with(clean(Path::new(excluded))))
+    })
+}
+
+/// Find the root of the Cargo workspace at or above `start` on the real
+/// filesystem, see [`UpFinder::find_cargo_workspace_root`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn find_cargo_workspace_root(start: impl AsRef<Path>) -> Option<CargoWorkspace> {
+  UpFinder::builder()
+    .cwd(start.as_ref())
+    .build()
+    .find_cargo_workspace_root()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  fn find(fs: MemoryFs) -> Option<CargoWorkspace> {
+    UpFinder::builder()
+      .cwd("/repo/crates/app/src")
+      .fs(Arc::new(fs.with_dir("/repo/crates/app/src")))
+      .build()
+      .find_cargo_workspace_root()
+  }
+
+  const PACKAGE: &str = "[package]\nname = \"app\"";
+
+  #[test]
+  fn should_find_the_enclosing_workspace() {
+    let workspace = find(
+      MemoryFs::new()
+        .with_file("/Cargo.toml", "[workspace]")
+        .with_file("/repo/Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]")
+        .with_file("/repo/crates/Cargo.toml", "[package]\nname = \"crates\"")
+        .with_file("/repo/crates/app/Cargo.toml", PACKAGE),
+    )
+    .unwrap();
+
+    assert_eq!(workspace.root(), Path::new("/repo"));
+    assert!(!workspace.is_standalone());
+  }
+
+  #[test]
+  fn should_follow_the_package_workspace_key() {
+    let workspace = find(
+      MemoryFs::new()
+        .with_file("/repo/Cargo.toml", "[workspace]")
+        .with_file(
+          "/repo/crates/app/Cargo.toml",
+          "[package]\nname = \"app\"\nworkspace = \"../..\"",
+        ),
+    )
+    .unwrap();
+
+    assert_eq!(workspace.root(), Path::new("/repo"));
+  }
+
+  #[test]
+  fn should_treat_excluded_and_lone_packages_as_standalone() {
+    let excluded = find(
+      MemoryFs::new()
+        .with_file(
+          "/repo/Cargo.toml",
+          "[workspace]\nexclude = [\"crates/app\"]",
+        )
+        .with_file("/repo/crates/app/Cargo.toml", PACKAGE),
+    )
+    .unwrap();
+
+    assert_eq!(excluded.root(), Path::new("/repo/crates/app"));
+    assert!(excluded.is_standalone());
+
+    let alone = find(MemoryFs::new().with_file("/repo/crates/app/Cargo.toml", PACKAGE)).unwrap();
+
+    assert_eq!(alone.root(), Path::new("/repo/crates/app"));
+    assert!(alone.is_standalone());
+  }
+}
@@ -0,0 +1,214 @@
+use std::path::{Path, PathBuf};
+
+use crate::{UpFinder, restrict::clean};
+
+/// A Go module, see [`UpFinder::find_go_module_root`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoModule {
+  root: PathBuf,
+  path: Option<String>,
+  go_version: Option<String>,
+  workspace: Option<GoWorkspace>,
+}
+
+impl GoModule {
+  /// The directory containing `go.mod`.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  /// The module path from the `module` directive.
+  pub fn path(&self) -> Option<&str> {
+    self.path.as_deref()
+  }
+
+  /// The version from the `go` directive.
+  pub fn go_version(&self) -> Option<&str> {
+    self.go_version.as_deref()
+  }
+
+  /// The workspace the module is in, if there is a `go.work` at or above it.
+  pub fn workspace(&self) -> Option<&GoWorkspace> {
+    self.workspace.as_ref()
+  }
+}
+
+/// A Go workspace, declared by a `go.work` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoWorkspace {
+  root: PathBuf,
+  modules: Vec<PathBuf>,
+}
+
+impl GoWorkspace {
+  /// The directory containing `go.work`.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  /// The module directories from the `use` directives, joined onto the root
+  /// without `.` and `..`.
+  pub fn modules(&self) -> &[PathBuf] {
+    &self.modules
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the nearest Go module, and the workspace it belongs to the way the
+  /// `go` command picks it: the nearest `go.work` at or above the start
+  /// directory.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if let Some(module) = find_up.find_go_module_root() {
+  ///   println!("{:?} at {}", module.path(), module.root().display());
+  /// }
+  /// ```
+  pub fn find_go_module_root(&self) -> Option<GoModule> {
+    let matches = self.find_up_multi_matches(&["go.mod", "go.work"]);
+    let nearest = |name| matches.get(name)?.first();
+
+    let manifest = nearest("go.mod")?;
+    let workspace = nearest("go.work").and_then(|work| {
+      let root = work.dir().to_path_buf();
+      let contents = self.read_go_file(work.path())?;
+      let modules = directives(&contents)
+        .filter(|(directive, _)| *directive == "use")
+        .map(|(_, module)| clean(&root.join(module)))
+        .collect();
+      Some(GoWorkspace { root, modules })
+    });
+
+    let contents = self.read_go_file(manifest.path()).unwrap_or_default();
+    let directive = |name| {
+      directives(&contents)
+        .find(|(directive, _)| *directive == name)
+        .map(|(_, value)| value.to_string())
+    };
+
+    Some(GoModule {
+      root: manifest.dir().to_path_buf(),
+      path: directive("module"),
+      go_version: directive("go"),
+      workspace,
+    })
+  }
+
+  fn read_go_file(&self, path: &Path) -> Option<String> {
+    String::from_utf8(self.fs.read(path).ok()?).ok()
+  }
+}
+
+/// The directives of a `go.mod` or `go.work` file with their first argument,
+/// with `directive ( ... )` blocks flattened.
+fn directives(contents: &str) -> impl Iterator<Item = (&str, &str)> {
+  let mut block = None;
+
+  contents.lines().filter_map(move |line| {
+    let line = line.split_once("//").map_or(line, |(line, _)| line).trim();
+    let mut words = line.split_whitespace();
+    let first = words.next()?;
+
+    let (directive, argument) = match block {
+      Some(_) if first == ")" => {
+        block = None;
+        return None;
+      }
+      Some(directive) => (directive, first),
+      None if words.clone().next() == Some("(") => {
+        block = Some(first);
+        return None;
+      }
+      None => (first, words.next()?),
+    };
+
+    Some((directive, unquote(argument)))
+  })
+}
+
+fn unquote(argument: &str) -> &str {
+  argument
+    .strip_prefix('"')
+    .and_then(|argument| argument.strip_suffix('"'))
+    .or_else(|| {
+      argument
+        .strip_prefix('`')
+        .and_then(|argument| argument.strip_suffix('`'))
+    })
+    .unwrap_or(argument)
+}
+
+/// Find the nearest Go module at or above `start` on the real filesystem, see
+/// [`UpFinder::find_go_module_root`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn find_go_module_root(start: impl AsRef<Path>) -> Option<GoModule> {
+  UpFinder::builder()
+    .cwd(start.as_ref())
+    .build()
+    .find_go_module_root()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  fn find(fs: MemoryFs) -> Option<GoModule> {
+    UpFinder::builder()
+      .cwd("/repo/svc/api/handlers")
+      .fs(Arc::new(fs.with_dir("/repo/svc/api/handlers")))
+      .build()
+      .find_go_module_root()
+  }
+
+  #[test]
+  fn should_parse_the_nearest_module() {
+    let module = find(
+      MemoryFs::new()
+        .with_file("/repo/go.mod", "module example.com/repo\n")
+        .with_file(
+          "/repo/svc/api/go.mod",
+          "// The API.\nmodule \"example.com/repo/svc/api\" // quoted\n\ngo 1.22\n\nrequire (\n\tgolang.org/x/net v0.1.0\n)\n",
+        ),
+    )
+    .unwrap();
+
+    assert_eq!(module.root(), Path::new("/repo/svc/api"));
+    assert_eq!(module.path(), Some("example.com/repo/svc/api"));
+    assert_eq!(module.go_version(), Some("1.22"));
+    assert_eq!(module.workspace(), None);
+    assert_eq!(find(MemoryFs::new()), None);
+  }
+
+  #[test]
+  fn should_recognize_workspaces() {
+    let module = find(
+      MemoryFs::new()
+        .with_file(
+          "/repo/go.work",
+          "go 1.22\n\nuse (\n\t./svc/api\n\t./lib // shared\n\t../vendor/x\n)\nuse ./tools\n",
+        )
+        .with_file("/repo/svc/api/go.mod", "module example.com/api"),
+    )
+    .unwrap();
+    let workspace = module.workspace().unwrap();
+
+    assert_eq!(workspace.root(), Path::new("/repo"));
+    assert_eq!(
+      workspace.modules(),
+      [
+        Path::new("/repo/svc/api"),
+        Path::new("/repo/lib"),
+        Path::new("/vendor/x"),
+        Path::new("/repo/tools"),
+      ]
+    );
+  }
+}
@@ -0,0 +1,151 @@
+use std::path::{Path, PathBuf};
+
+use crate::UpFinder;
+
+/// A tool that manages a monorepo, recognized by its configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MonorepoTool {
+  /// `nx.json`
+  Nx,
+  /// `turbo.json`
+  Turborepo,
+  /// `lerna.json`
+  Lerna,
+  /// `rush.json`
+  Rush,
+}
+
+impl MonorepoTool {
+  const ALL: [MonorepoTool; 4] = [Self::Nx, Self::Turborepo, Self::Lerna, Self::Rush];
+
+  /// The name of the configuration file at the root of the monorepo.
+  pub fn file_name(self) -> &'static str {
+    match self {
+      Self::Nx => "nx.json",
+      Self::Turborepo => "turbo.json",
+      Self::Lerna => "lerna.json",
+      Self::Rush => "rush.json",
+    }
+  }
+}
+
+/// The root of a monorepo, see [`UpFinder::find_monorepo_root`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monorepo {
+  root: PathBuf,
+  tools: Vec<MonorepoTool>,
+}
+
+impl Monorepo {
+  /// The directory containing the configuration files.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  /// The tools configured at the root, in the order of [`MonorepoTool`]. Nx
+  /// and Lerna, for instance, are often used together.
+  pub fn tools(&self) -> &[MonorepoTool] {
+    &self.tools
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the nearest directory configuring a monorepo tool, together with
+  /// the tools found there.
+  ///
+  /// Turborepo also allows a `turbo.json` in every package, so for it the
+  /// outermost `turbo.json` counts as the root.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if let Some(monorepo) = find_up.find_monorepo_root() {
+  ///   println!("{:?} at {}", monorepo.tools(), monorepo.root().display());
+  /// }
+  /// ```
+  pub fn find_monorepo_root(&self) -> Option<Monorepo> {
+    let mut matches = self.find_up_multi_matches(&MonorepoTool::ALL.map(MonorepoTool::file_name));
+
+    let roots: Vec<(MonorepoTool, usize, PathBuf)> = MonorepoTool::ALL
+      .into_iter()
+      .filter_map(|tool| {
+        let mut found = matches.remove(tool.file_name())?.into_iter();
+        let found = match tool {
+          MonorepoTool::Turborepo => found.next_back(),
+          _ => found.next(),
+        }?;
+        Some((tool, found.depth(), found.dir().to_path_buf()))
+      })
+      .collect();
+
+    let (_, depth, root) = roots.iter().min_by_key(|(_, depth, _)| *depth)?;
+    let tools = roots
+      .iter()
+      .filter(|(_, other, _)| other == depth)
+      .map(|(tool, _, _)| *tool)
+      .collect();
+
+    Some(Monorepo {
+      root: root.clone(),
+      tools,
+    })
+  }
+}
+
+/// Find the nearest monorepo root at or above `start` on the real filesystem,
+/// see [`UpFinder::find_monorepo_root`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn find_monorepo_root(start: impl AsRef<Path>) -> Option<Monorepo> {
+  UpFinder::builder()
+    .cwd(start.as_ref())
+    .build()
+    .find_monorepo_root()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  fn find(fs: MemoryFs) -> Option<Monorepo> {
+    UpFinder::builder()
+      .cwd("/repo/packages/app/src")
+      .fs(Arc::new(fs.with_dir("/repo/packages/app/src")))
+      .build()
+      .find_monorepo_root()
+  }
+
+  #[test]
+  fn should_find_every_tool_at_the_nearest_root() {
+    let monorepo = find(
+      MemoryFs::new()
+        .with_file("/rush.json", "{}")
+        .with_file("/repo/lerna.json", "{}")
+        .with_file("/repo/nx.json", "{}"),
+    )
+    .unwrap();
+
+    assert_eq!(monorepo.root(), Path::new("/repo"));
+    assert_eq!(monorepo.tools(), [MonorepoTool::Nx, MonorepoTool::Lerna]);
+    assert_eq!(find(MemoryFs::new()), None);
+  }
+
+  #[test]
+  fn should_skip_turborepo_package_configurations() {
+    let monorepo = find(
+      MemoryFs::new()
+        .with_file("/repo/turbo.json", "{}")
+        .with_file("/repo/packages/app/turbo.json", r#"{ "extends": ["//"] }"#),
+    )
+    .unwrap();
+
+    assert_eq!(monorepo.root(), Path::new("/repo"));
+    assert_eq!(monorepo.tools(), [MonorepoTool::Turborepo]);
+  }
+}
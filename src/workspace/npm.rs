@@ -0,0 +1,209 @@
+use std::path::{Path, PathBuf};
+
+use crate::{FindUpResult, JsonMatcher, UpFinder};
+
+/// The package manager that manages a workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+  Npm,
+  /// Yarn 1.
+  Yarn,
+  /// Yarn 2 and later.
+  YarnBerry,
+  Pnpm,
+  Bun,
+}
+
+/// The root of a JavaScript workspace, see [`UpFinder::find_npm_workspace_root`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NpmWorkspace {
+  root: PathBuf,
+  manager: PackageManager,
+}
+
+impl NpmWorkspace {
+  /// The directory containing the workspace manifest.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  pub fn manager(&self) -> PackageManager {
+    self.manager
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the nearest directory declaring a JavaScript workspace, either with a
+  /// `pnpm-workspace.yaml` or with a `workspaces` field in `package.json`.
+  ///
+  /// The package manager is taken from the `packageManager` field of the
+  /// root `package.json` if there is one, otherwise it is guessed from the
+  /// lockfiles and yarn's `.yarnrc.yml`.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if let Some(workspace) = find_up.find_npm_workspace_root() {
+  ///   println!("{:?} workspace at {}", workspace.manager(), workspace.root().display());
+  /// }
+  /// ```
+  pub fn find_npm_workspace_root(&self) -> Option<NpmWorkspace> {
+    let declares_workspaces = JsonMatcher::has_key("workspaces");
+
+    let matches = self
+      .find_up_with_impl(
+        self.cwd.as_ref().to_path_buf(),
+        &["pnpm-workspace.yaml", "package.json"],
+        |path| {
+          if path.file_name().is_some_and(|name| name == "package.json")
+            && !self
+              .fs
+              .read(&path)
+              .is_ok_and(|contents| declares_workspaces.matches(&contents))
+          {
+            return FindUpResult::Continue;
+          }
+          FindUpResult::Saved(path)
+        },
+        false,
+      )
+      .ok()?;
+
+    let manifest = matches
+      .into_values()
+      .filter_map(|matches| matches.into_iter().next())
+      // On the same level `pnpm-workspace.yaml` wins.
+      .min_by_key(|found| {
+        (
+          found.depth(),
+          !found.path().ends_with("pnpm-workspace.yaml"),
+        )
+      })?;
+
+    let root = manifest.dir().to_path_buf();
+    let manager = self.package_manager(&root, manifest.path());
+
+    Some(NpmWorkspace { root, manager })
+  }
+
+  fn package_manager(&self, root: &Path, manifest: &Path) -> PackageManager {
+    if manifest.ends_with("pnpm-workspace.yaml") {
+      return PackageManager::Pnpm;
+    }
+
+    if let Some(manager) = self
+      .fs
+      .read(manifest)
+      .ok()
+      .and_then(|contents| serde_json::from_slice::<serde_json::Value>(&contents).ok())
+      .and_then(|package_json| {
+        package_json["packageManager"]
+          .as_str()
+          .and_then(parse_package_manager)
+      })
+    {
+      return manager;
+    }
+
+    let exists = |name: &str| self.fs.exists(&root.join(name));
+
+    if exists(".yarnrc.yml") {
+      PackageManager::YarnBerry
+    } else if exists("yarn.lock") {
+      PackageManager::Yarn
+    } else if exists("bun.lock") || exists("bun.lockb") {
+      PackageManager::Bun
+    } else if exists("pnpm-lock.yaml") {
+      PackageManager::Pnpm
+    } else {
+      PackageManager::Npm
+    }
+  }
+}
+
+/// Parse a `packageManager` field such as `yarn@4.1.0+sha224.abc`.
+fn parse_package_manager(field: &str) -> Option<PackageManager> {
+  let (name, version) = field.split_once('@')?;
+
+  match name {
+    "npm" => Some(PackageManager::Npm),
+    "pnpm" => Some(PackageManager::Pnpm),
+    "bun" => Some(PackageManager::Bun),
+    "yarn" if version.starts_with("1.") => Some(PackageManager::Yarn),
+    "yarn" => Some(PackageManager::YarnBerry),
+    _ => None,
+  }
+}
+
+/// Find the nearest JavaScript workspace root at or above `start` on the real
+/// filesystem, see [`UpFinder::find_npm_workspace_root`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn find_npm_workspace_root(start: impl AsRef<Path>) -> Option<NpmWorkspace> {
+  UpFinder::builder()
+    .cwd(start.as_ref())
+    .build()
+    .find_npm_workspace_root()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  fn find(fs: MemoryFs) -> Option<NpmWorkspace> {
+    UpFinder::builder()
+      .cwd("/repo/packages/app/src")
+      .fs(Arc::new(fs.with_dir("/repo/packages/app/src")))
+      .build()
+      .find_npm_workspace_root()
+  }
+
+  #[test]
+  fn should_find_workspaces_declared_in_package_json() {
+    let workspace = find(
+      MemoryFs::new()
+        .with_file("/repo/package.json", r#"{ "workspaces": ["packages/*"] }"#)
+        .with_file("/repo/yarn.lock", "")
+        .with_file("/repo/.yarnrc.yml", "")
+        .with_file("/repo/packages/app/package.json", r#"{ "name": "app" }"#),
+    )
+    .unwrap();
+
+    assert_eq!(workspace.root(), Path::new("/repo"));
+    assert_eq!(workspace.manager(), PackageManager::YarnBerry);
+  }
+
+  #[test]
+  fn should_find_pnpm_workspaces() {
+    let workspace = find(
+      MemoryFs::new()
+        .with_file("/repo/pnpm-workspace.yaml", "packages: ['packages/*']")
+        .with_file("/repo/package.json", "{}"),
+    )
+    .unwrap();
+
+    assert_eq!(workspace.root(), Path::new("/repo"));
+    assert_eq!(workspace.manager(), PackageManager::Pnpm);
+  }
+
+  #[test]
+  fn should_prefer_the_package_manager_field() {
+    let workspace = find(MemoryFs::new().with_file(
+      "/repo/package.json",
+      r#"{ "workspaces": ["packages/*"], "packageManager": "yarn@1.22.22" }"#,
+    ))
+    .unwrap();
+
+    assert_eq!(workspace.manager(), PackageManager::Yarn);
+    assert_eq!(
+      find(MemoryFs::new().with_file("/repo/package.json", "{}")),
+      None
+    );
+  }
+}
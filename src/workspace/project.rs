@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+
+use crate::{FindUpResult, UpFinder};
+
+/// The kind of project a marker file identifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ProjectType {
+  /// `Cargo.toml`
+  Rust,
+  /// `deno.json` or `deno.jsonc`
+  Deno,
+  /// `package.json`
+  Node,
+  /// `go.work` or `go.mod`
+  Go,
+  /// `pyproject.toml`, `setup.py`, `setup.cfg` or `Pipfile`
+  Python,
+  /// `pom.xml`
+  Maven,
+  /// `settings.gradle(.kts)` or `build.gradle(.kts)`
+  Gradle,
+  /// `Gemfile`
+  Ruby,
+  /// `composer.json`
+  Php,
+  /// `mix.exs`
+  Elixir,
+  /// `Package.swift`
+  Swift,
+  /// `pubspec.yaml`
+  Dart,
+}
+
+impl ProjectType {
+  /// The markers in order of precedence, for markers on the same level.
+  const MARKERS: [(&str, ProjectType); 21] = [
+    ("Cargo.toml", Self::Rust),
+    ("deno.json", Self::Deno),
+    ("deno.jsonc", Self::Deno),
+    ("package.json", Self::Node),
+    ("go.work", Self::Go),
+    ("go.mod", Self::Go),
+    ("pyproject.toml", Self::Python),
+    ("setup.py", Self::Python),
+    ("setup.cfg", Self::Python),
+    ("Pipfile", Self::Python),
+    ("pom.xml", Self::Maven),
+    ("settings.gradle.kts", Self::Gradle),
+    ("settings.gradle", Self::Gradle),
+    ("build.gradle.kts", Self::Gradle),
+    ("build.gradle", Self::Gradle),
+    ("Gemfile", Self::Ruby),
+    ("composer.json", Self::Php),
+    ("mix.exs", Self::Elixir),
+    ("Package.swift", Self::Swift),
+    ("pubspec.yaml", Self::Dart),
+    ("pubspec.yml", Self::Dart),
+  ];
+}
+
+/// A project found by [`UpFinder::detect_project_type`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+  root: PathBuf,
+  project_type: ProjectType,
+}
+
+impl Project {
+  /// The directory containing the marker.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  pub fn project_type(&self) -> ProjectType {
+    self.project_type
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the nearest directory containing a well-known project marker, in a
+  /// single walk that ends at the first level with a marker. Markers on the
+  /// same level are ranked in the order of
+  /// [`ProjectType`], so a Rust crate with a `package.json` is a Rust project.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::{UpFinder, workspace::ProjectType};
+  ///
+  /// let find_up = UpFinder::builder().cwd("src").build();
+  ///
+  /// let project = find_up.detect_project_type().unwrap();
+  ///
+  /// assert_eq!(project.project_type(), ProjectType::Rust);
+  /// ```
+  pub fn detect_project_type(&self) -> Option<Project> {
+    let matches = self
+      .find_up_with_impl(
+        self.cwd.as_ref().to_path_buf(),
+        &ProjectType::MARKERS.map(|(marker, _)| marker),
+        FindUpResult::Last,
+        true,
+      )
+      .ok()?;
+
+    let (_, _, project_type, found) = ProjectType::MARKERS
+      .into_iter()
+      .enumerate()
+      .filter_map(|(rank, (marker, project_type))| {
+        let found = matches.get(marker)?.first()?;
+        Some((found.depth(), rank, project_type, found))
+      })
+      .min_by_key(|(depth, rank, _, _)| (*depth, *rank))?;
+
+    Some(Project {
+      root: found.dir().to_path_buf(),
+      project_type,
+    })
+  }
+}
+
+/// Find the nearest project at or above `start` on the real filesystem, see
+/// [`UpFinder::detect_project_type`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn detect_project_type(start: impl AsRef<Path>) -> Option<Project> {
+  UpFinder::builder()
+    .cwd(start.as_ref())
+    .build()
+    .detect_project_type()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+  };
+
+  use super::*;
+  use crate::{HookResult, MemoryFs};
+
+  fn detect(fs: MemoryFs) -> Option<Project> {
+    UpFinder::builder()
+      .cwd("/repo/app/src")
+      .fs(Arc::new(fs.with_dir("/repo/app/src")))
+      .build()
+      .detect_project_type()
+  }
+
+  #[test]
+  fn should_detect_the_nearest_project() {
+    let project = detect(
+      MemoryFs::new()
+        .with_file("/repo/Cargo.toml", "")
+        .with_file("/repo/app/build.gradle.kts", ""),
+    )
+    .unwrap();
+
+    assert_eq!(project.root(), Path::new("/repo/app"));
+    assert_eq!(project.project_type(), ProjectType::Gradle);
+    assert_eq!(detect(MemoryFs::new()), None);
+  }
+
+  #[test]
+  fn should_rank_markers_on_the_same_level() {
+    let project = detect(
+      MemoryFs::new()
+        .with_file("/repo/app/package.json", "")
+        .with_file("/repo/app/Cargo.toml", ""),
+    )
+    .unwrap();
+
+    assert_eq!(project.project_type(), ProjectType::Rust);
+  }
+
+  #[test]
+  fn should_end_the_walk_at_the_first_level_with_a_marker() {
+    let entered = Arc::new(AtomicUsize::new(0));
+    let project = UpFinder::builder()
+      .cwd("/repo/app/src")
+      .on_enter_dir({
+        let entered = Arc::clone(&entered);
+        move |_, _| {
+          entered.fetch_add(1, Ordering::Relaxed);
+          HookResult::Continue
+        }
+      })
+      .fs(Arc::new(
+        MemoryFs::new()
+          .with_file("/repo/Cargo.toml", "")
+          .with_file("/repo/app/package.json", "")
+          .with_dir("/repo/app/src"),
+      ))
+      .build()
+      .detect_project_type()
+      .unwrap();
+
+    assert_eq!(project.root(), Path::new("/repo/app"));
+    assert_eq!(entered.load(Ordering::Relaxed), 2);
+  }
+}
@@ -0,0 +1,233 @@
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "toml")]
+use crate::TomlMatcher;
+use crate::{FindUpMatch, FindUpResult, UpFinder, hash::HashMap};
+
+/// The file that marks the root of a Python project, in order of precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PythonMarker {
+  /// `pyproject.toml`
+  PyProject,
+  /// `setup.cfg`
+  SetupCfg,
+  /// `setup.py`
+  SetupPy,
+  /// `Pipfile`
+  Pipfile,
+}
+
+impl PythonMarker {
+  const ALL: [PythonMarker; 4] = [
+    Self::PyProject,
+    Self::SetupCfg,
+    Self::SetupPy,
+    Self::Pipfile,
+  ];
+
+  pub fn file_name(self) -> &'static str {
+    match self {
+      Self::PyProject => "pyproject.toml",
+      Self::SetupCfg => "setup.cfg",
+      Self::SetupPy => "setup.py",
+      Self::Pipfile => "Pipfile",
+    }
+  }
+
+  fn from_file_name(name: &str) -> Option<Self> {
+    Self::ALL
+      .into_iter()
+      .find(|marker| marker.file_name() == name)
+  }
+}
+
+/// The root of a Python project, see [`UpFinder::find_python_project_root`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonProject {
+  root: PathBuf,
+  marker: PythonMarker,
+}
+
+impl PythonProject {
+  /// The directory containing the marker.
+  pub fn root(&self) -> &Path {
+    &self.root
+  }
+
+  /// The marker with the highest precedence found at the root.
+  pub fn marker(&self) -> PythonMarker {
+    self.marker
+  }
+}
+
+impl<P: AsRef<Path>> UpFinder<P> {
+  /// Find the nearest directory containing any [`PythonMarker`].
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// if let Some(project) = find_up.find_python_project_root() {
+  ///   println!("{:?} at {}", project.marker(), project.root().display());
+  /// }
+  /// ```
+  pub fn find_python_project_root(&self) -> Option<PythonProject> {
+    let matches = self
+      .find_up_with_impl(
+        self.cwd.as_ref().to_path_buf(),
+        &PythonMarker::ALL.map(PythonMarker::file_name),
+        FindUpResult::Saved,
+        false,
+      )
+      .ok()?;
+
+    nearest(matches)
+  }
+
+  /// Like [`UpFinder::find_python_project_root`], only accepting roots that
+  /// configure `tool`: a `pyproject.toml` with a `[tool.<tool>]` table or a
+  /// `setup.cfg` with a `[tool:<tool>]` or `[<tool>]` section. `setup.py` and
+  /// `Pipfile` never do.
+  ///
+  /// # Example
+  ///
+  /// ```rust
+  /// use up_finder::UpFinder;
+  ///
+  /// let find_up = UpFinder::builder().cwd(".").build();
+  ///
+  /// // Where pytest takes its configuration from.
+  /// let project = find_up.find_python_project_root_for("pytest");
+  ///
+  /// println!("{:?}", project);
+  /// ```
+  #[cfg(feature = "toml")]
+  pub fn find_python_project_root_for(&self, tool: &str) -> Option<PythonProject> {
+    let configures_tool = TomlMatcher::has_table(format!("tool.{tool}"));
+
+    let matches = self
+      .find_up_with_impl(
+        self.cwd.as_ref().to_path_buf(),
+        &[
+          PythonMarker::PyProject.file_name(),
+          PythonMarker::SetupCfg.file_name(),
+        ],
+        |path| {
+          let Ok(contents) = self.fs.read(&path) else {
+            return FindUpResult::Continue;
+          };
+          let configured = if path.ends_with(PythonMarker::PyProject.file_name()) {
+            configures_tool.matches(&contents)
+          } else {
+            has_setup_cfg_section(&contents, tool)
+          };
+          if configured {
+            FindUpResult::Saved(path)
+          } else {
+            FindUpResult::Continue
+          }
+        },
+        false,
+      )
+      .ok()?;
+
+    nearest(matches)
+  }
+}
+
+/// The nearest match, the one with the highest precedence on the same level.
+fn nearest(matches: HashMap<&str, Vec<FindUpMatch>>) -> Option<PythonProject> {
+  let (_, marker, found) = matches
+    .into_iter()
+    .filter_map(|(name, matches)| {
+      let found = matches.into_iter().next()?;
+      Some((found.depth(), PythonMarker::from_file_name(name)?, found))
+    })
+    .min_by_key(|(depth, marker, _)| (*depth, *marker))?;
+
+  Some(PythonProject {
+    root: found.dir().to_path_buf(),
+    marker,
+  })
+}
+
+/// Whether a `setup.cfg` has a `[tool:<tool>]` or `[<tool>]` section.
+#[cfg(feature = "toml")]
+fn has_setup_cfg_section(contents: &[u8], tool: &str) -> bool {
+  let Ok(contents) = std::str::from_utf8(contents) else {
+    return false;
+  };
+
+  contents.lines().any(|line| {
+    line
+      .trim()
+      .strip_prefix('[')
+      .and_then(|line| line.strip_suffix(']'))
+      .map(|section| section.strip_prefix("tool:").unwrap_or(section).trim())
+      .is_some_and(|section| section == tool)
+  })
+}
+
+/// Find the nearest Python project root at or above `start` on the real
+/// filesystem, see [`UpFinder::find_python_project_root`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn find_python_project_root(start: impl AsRef<Path>) -> Option<PythonProject> {
+  UpFinder::builder()
+    .cwd(start.as_ref())
+    .build()
+    .find_python_project_root()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use super::*;
+  use crate::MemoryFs;
+
+  fn finder(fs: MemoryFs) -> UpFinder<&'static str> {
+    UpFinder::builder()
+      .cwd("/repo/pkg/src")
+      .fs(Arc::new(fs.with_dir("/repo/pkg/src")))
+      .build()
+  }
+
+  #[test]
+  fn should_find_the_nearest_marker_by_precedence() {
+    let project = finder(
+      MemoryFs::new()
+        .with_file("/repo/pyproject.toml", "")
+        .with_file("/repo/pkg/setup.py", "")
+        .with_file("/repo/pkg/setup.cfg", ""),
+    )
+    .find_python_project_root()
+    .unwrap();
+
+    assert_eq!(project.root(), Path::new("/repo/pkg"));
+    assert_eq!(project.marker(), PythonMarker::SetupCfg);
+    assert_eq!(finder(MemoryFs::new()).find_python_project_root(), None);
+  }
+
+  #[cfg(feature = "toml")]
+  #[test]
+  fn should_confirm_roots_by_their_tool_sections() {
+    let find_up = finder(
+      MemoryFs::new()
+        .with_file("/repo/pyproject.toml", "[tool.black]\nline-length = 100")
+        .with_file("/repo/pkg/pyproject.toml", "[project]\nname = \"pkg\"")
+        .with_file("/repo/pkg/setup.cfg", "[tool:pytest]\naddopts = -q"),
+    );
+
+    let black = find_up.find_python_project_root_for("black").unwrap();
+    let pytest = find_up.find_python_project_root_for("pytest").unwrap();
+
+    assert_eq!(black.root(), Path::new("/repo"));
+    assert_eq!(black.marker(), PythonMarker::PyProject);
+    assert_eq!(pytest.root(), Path::new("/repo/pkg"));
+    assert_eq!(pytest.marker(), PythonMarker::SetupCfg);
+    assert_eq!(find_up.find_python_project_root_for("mypy"), None);
+  }
+}
@@ -0,0 +1,189 @@
+use std::path::{Path, PathBuf};
+
+/// Translate a path as seen inside WSL into the Windows path of the same entry.
+///
+/// `/mnt/<drive>/...` becomes `<DRIVE>:\...`. Any other absolute path lives in
+/// the distribution's own filesystem and becomes `\\wsl$\<distro>\...`, which
+/// needs the name of the distribution, inside WSL it is in `WSL_DISTRO_NAME`.
+///
+/// Returns `None` for relative or non-UTF-8 paths and for paths outside
+/// `/mnt/<drive>` when no distribution is given. The translation is purely
+/// lexical and works on any platform.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::Path;
+///
+/// use up_finder::wsl_to_windows;
+///
+/// assert_eq!(
+///   wsl_to_windows(Path::new("/mnt/c/Users/me/package.json"), None).unwrap(),
+///   Path::new(r"C:\Users\me\package.json")
+/// );
+/// assert_eq!(
+///   wsl_to_windows(Path::new("/home/me/package.json"), Some("Ubuntu")).unwrap(),
+///   Path::new(r"\\wsl$\Ubuntu\home\me\package.json")
+/// );
+/// ```
+pub fn wsl_to_windows(path: &Path, distro: Option<&str>) -> Option<PathBuf> {
+  let path = path.to_str()?.strip_prefix('/')?;
+  let components: Vec<&str> = path
+    .split('/')
+    .filter(|component| !component.is_empty())
+    .collect();
+
+  let drive = match components.as_slice() {
+    ["mnt", drive, ..] => drive_letter(drive),
+    _ => None,
+  };
+
+  let (mut windows, rest) = match drive {
+    Some(letter) => (
+      format!("{}:", letter.to_ascii_uppercase()),
+      &components[2..],
+    ),
+    None => (format!(r"\\wsl$\{}", distro?), &components[..]),
+  };
+
+  for component in rest {
+    windows.push('\\');
+    windows.push_str(component);
+  }
+
+  if rest.is_empty() {
+    windows.push('\\');
+  }
+
+  Some(PathBuf::from(windows))
+}
+
+/// Translate a Windows path into the path of the same entry as seen inside WSL,
+/// the inverse of [`wsl_to_windows`].
+///
+/// `<drive>:\...` becomes `/mnt/<drive>/...` and `\\wsl$\<distro>\...` or
+/// `\\wsl.localhost\<distro>\...` becomes `/...`. Both `\` and `/` are accepted
+/// as separators. Returns `None` for any other path, including non-UTF-8 ones.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::Path;
+///
+/// use up_finder::windows_to_wsl;
+///
+/// assert_eq!(
+///   windows_to_wsl(Path::new(r"C:\Users\me\package.json")).unwrap(),
+///   Path::new("/mnt/c/Users/me/package.json")
+/// );
+/// assert_eq!(
+///   windows_to_wsl(Path::new(r"\\wsl$\Ubuntu\home\me")).unwrap(),
+///   Path::new("/home/me")
+/// );
+/// ```
+pub fn windows_to_wsl(path: &Path) -> Option<PathBuf> {
+  let path = path.to_str()?;
+  let path = path.strip_prefix(r"\\?\").unwrap_or(path);
+
+  let (mut wsl, rest) = if let Some(rest) = strip_share_prefix(path) {
+    // Skip the distribution name.
+    let rest = rest.split_once(is_separator).map_or("", |(_, rest)| rest);
+    (String::new(), rest)
+  } else {
+    let (drive, rest) = path.split_at_checked(2)?;
+    let letter = drive_letter(drive.strip_suffix(':')?)?;
+    if !(rest.is_empty() || rest.starts_with(is_separator)) {
+      // `C:foo` is relative to the current directory of the drive.
+      return None;
+    }
+    (format!("/mnt/{}", letter.to_ascii_lowercase()), rest)
+  };
+
+  for component in rest
+    .split(is_separator)
+    .filter(|component| !component.is_empty())
+  {
+    wsl.push('/');
+    wsl.push_str(component);
+  }
+
+  if wsl.is_empty() {
+    wsl.push('/');
+  }
+
+  Some(PathBuf::from(wsl))
+}
+
+fn strip_share_prefix(path: &str) -> Option<&str> {
+  let path = path
+    .strip_prefix(r"\\")
+    .or_else(|| path.strip_prefix("//"))
+    .or_else(|| path.strip_prefix(r"UNC\"))?;
+
+  let (host, rest) = path.split_once(is_separator)?;
+
+  (host.eq_ignore_ascii_case("wsl$") || host.eq_ignore_ascii_case("wsl.localhost")).then_some(rest)
+}
+
+pub(crate) fn drive_letter(drive: &str) -> Option<char> {
+  let mut chars = drive.chars();
+
+  match (chars.next(), chars.next()) {
+    (Some(letter), None) if letter.is_ascii_alphabetic() => Some(letter),
+    _ => None,
+  }
+}
+
+fn is_separator(c: char) -> bool {
+  c == '\\' || c == '/'
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_translate_wsl_paths_to_windows() {
+    assert_eq!(
+      wsl_to_windows(Path::new("/mnt/d/work//app/"), None),
+      Some(PathBuf::from(r"D:\work\app"))
+    );
+    assert_eq!(
+      wsl_to_windows(Path::new("/mnt/c"), None),
+      Some(PathBuf::from(r"C:\"))
+    );
+    assert_eq!(
+      wsl_to_windows(Path::new("/"), Some("Debian")),
+      Some(PathBuf::from(r"\\wsl$\Debian\"))
+    );
+    assert_eq!(
+      wsl_to_windows(Path::new("/mnt/cd/app"), Some("Debian")),
+      Some(PathBuf::from(r"\\wsl$\Debian\mnt\cd\app"))
+    );
+    assert_eq!(wsl_to_windows(Path::new("/home/me"), None), None);
+    assert_eq!(wsl_to_windows(Path::new("mnt/c"), None), None);
+  }
+
+  #[test]
+  fn should_translate_windows_paths_to_wsl() {
+    assert_eq!(
+      windows_to_wsl(Path::new("D:/work/app")),
+      Some(PathBuf::from("/mnt/d/work/app"))
+    );
+    assert_eq!(
+      windows_to_wsl(Path::new(r"\\?\C:\")),
+      Some(PathBuf::from("/mnt/c"))
+    );
+    assert_eq!(
+      windows_to_wsl(Path::new(r"\\wsl.localhost\Ubuntu")),
+      Some(PathBuf::from("/"))
+    );
+    assert_eq!(
+      windows_to_wsl(Path::new(r"\\?\UNC\wsl$\Ubuntu\etc\hosts")),
+      Some(PathBuf::from("/etc/hosts"))
+    );
+    assert_eq!(windows_to_wsl(Path::new(r"\\server\share\a")), None);
+    assert_eq!(windows_to_wsl(Path::new("C:foo")), None);
+    assert_eq!(windows_to_wsl(Path::new("/home/me")), None);
+  }
+}
@@ -0,0 +1,86 @@
+use serde_yaml_ng::Value;
+
+/// A filter on the YAML contents of candidates, for use with
+/// [`UpFinder::find_up_where`](crate::UpFinder::find_up_where).
+///
+/// Candidates that are not valid YAML or hold more than one document never
+/// match. Paths are dotted keys like `catalog.react`, quoting is not supported.
+///
+/// # Example
+///
+/// ```rust
+/// use up_finder::{UpFinder, YamlMatcher};
+///
+/// let find_up = UpFinder::builder().cwd(".").build();
+///
+/// let workspace_root = YamlMatcher::has_key("packages");
+/// let paths = find_up.find_up_where("pnpm-workspace.yaml", |_, contents| {
+///   workspace_root.matches(contents)
+/// });
+///
+/// println!("{:#?}", paths);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct YamlMatcher(Condition);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+  HasKey(String),
+  PathEquals(String, Value),
+}
+
+impl YamlMatcher {
+  /// Match documents with a value at `path`, whatever it is.
+  pub fn has_key(path: impl Into<String>) -> Self {
+    Self(Condition::HasKey(path.into()))
+  }
+
+  /// Match documents where the value at `path` equals `value`.
+  pub fn path_equals(path: impl Into<String>, value: impl Into<Value>) -> Self {
+    Self(Condition::PathEquals(path.into(), value.into()))
+  }
+
+  /// Whether `contents` satisfy the condition.
+  pub fn matches(&self, contents: &[u8]) -> bool {
+    let Ok(document) = serde_yaml_ng::from_slice::<Value>(contents) else {
+      return false;
+    };
+
+    match &self.0 {
+      Condition::HasKey(path) => lookup(&document, path).is_some(),
+      Condition::PathEquals(path, value) => lookup(&document, path) == Some(value),
+    }
+  }
+}
+
+fn lookup<'a>(document: &'a Value, path: &str) -> Option<&'a Value> {
+  path
+    .split('.')
+    .try_fold(document, |value, key| value.as_mapping()?.get(key))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_match_keys_and_values() {
+    let workflow = b"
+name: ci
+on:
+  push:
+    branches: [main]
+jobs:
+  test:
+    runs-on: ubuntu-latest
+";
+
+    assert!(YamlMatcher::has_key("jobs.test").matches(workflow));
+    assert!(!YamlMatcher::has_key("jobs.lint").matches(workflow));
+    assert!(YamlMatcher::path_equals("name", "ci").matches(workflow));
+    assert!(YamlMatcher::path_equals("jobs.test.runs-on", "ubuntu-latest").matches(workflow));
+    assert!(!YamlMatcher::path_equals("name", "release").matches(workflow));
+    assert!(!YamlMatcher::has_key("name").matches(b"name: [ci"));
+    assert!(!YamlMatcher::has_key("name").matches(b"name: a\n---\nname: b\n"));
+  }
+}